@@ -1,14 +1,10 @@
+use crate::inclusion::verify_blob_inclusion;
 use crate::traits::RollupProof;
 use crate::types::{AdapterPrivateInputs, AdapterPublicInputs, RollupProofWithPublicInputs};
 use anyhow::{anyhow, Error};
 use nexus_core::types::{AppAccountId, AvailHeader, Extension, StatementDigest, V3Extension, H256};
 use nexus_core::utils::hasher::ShaHasher;
-#[cfg(feature = "zkvm-risc0")]
-use risc0_zkvm::{
-    guest::env::{self, verify},
-    serde::to_vec,
-    sha::rust_crypto::Digest,
-};
+use nexus_core::zkvm::traits::ZKVMEnv;
 
 #[cfg(feature = "zkvm-sp1")]
 use digest::Update;
@@ -71,8 +67,11 @@ use serde::Serialize;
 /// # Note
 ///
 /// Ensure that the types `Proof`, `RollupPublicInputs`, `AdapterPublicInputs`, `AdapterPrivateInputs`, `Error`, and `Digest` are properly defined and implemented.
+///
+/// The previous adapter proof is verified through `Z::verify`, so the recursion
+/// check is enforced for whichever zkVM the adapter guest is built for.
 
-pub fn verify_proof<P: RollupProof>(
+pub fn verify_proof<P: RollupProof, Z: ZKVMEnv>(
     rollup_proof: Option<RollupProofWithPublicInputs<P>>,
     prev_adapter_public_inputs: Option<AdapterPublicInputs>,
     private_inputs: AdapterPrivateInputs,
@@ -83,7 +82,7 @@ pub fn verify_proof<P: RollupProof>(
     1. Check if first proof or not, for first proof, proof should be at start height - ✅
     2. Check if proof height is sequential as per previous proof provided - ✅
     3. Verify if previous proof is valid - ✅
-    4. Check for current height if input is valid, this is checked against header - 😢
+    4. Check for current height if input is valid, this is checked against header - ✅
     5. Check if current proof is sequential as per last proof - ✅
     6. Verify current proof -  ✅
     7. Hash the header provided - ✅
@@ -96,7 +95,6 @@ pub fn verify_proof<P: RollupProof>(
     if current_avail_hash != private_inputs.nexus_header.avail_header_hash {
         return Err(anyhow!("Incorrect Nexus Header provided."));
     }
-    //TODO: Check inclusion proof for data blob, app index check, and empty block check.
     let mut hasher = ShaHasher::new();
 
     digest::Digest::update(&mut hasher.0, &private_inputs.app_id.0.to_be_bytes());
@@ -125,15 +123,25 @@ pub fn verify_proof<P: RollupProof>(
             }
 
             return Ok(match prev_adapter_public_inputs {
-                Some(i) => AdapterPublicInputs {
-                    nexus_hash,
-                    height: i.height,
-                    state_root: i.state_root,
-                    start_nexus_hash: i.start_nexus_hash,
-                    app_id: app_account_id,
-                    img_id: i.img_id,
-                    rollup_hash: i.rollup_hash,
-                },
+                Some(i) => {
+                    if i.nexus_hash != private_inputs.nexus_header.parent_hash {
+                        return Err(anyhow!("Proof for previous avail height not provided."));
+                    }
+
+                    if Z::verify(img_id.0, &i).is_err() {
+                        return Err(anyhow!("Invalid proof"));
+                    }
+
+                    AdapterPublicInputs {
+                        nexus_hash,
+                        height: i.height,
+                        state_root: i.state_root,
+                        start_nexus_hash: i.start_nexus_hash,
+                        app_id: app_account_id,
+                        img_id: i.img_id,
+                        rollup_hash: i.rollup_hash,
+                    }
+                }
                 None => AdapterPublicInputs {
                     nexus_hash: nexus_hash.clone(),
                     height: 0,
@@ -147,6 +155,17 @@ pub fn verify_proof<P: RollupProof>(
         }
     };
 
+    let blob = match &private_inputs.blob {
+        Some(i) => i,
+        None => return Err(anyhow!("Blob inclusion proof not provided.")),
+    };
+    let blob_hash =
+        verify_blob_inclusion(blob, &private_inputs.avail_header, &private_inputs.app_id)?;
+
+    if blob_hash != rollup_public_inputs.blob_hash {
+        return Err(anyhow!("Proof not for the blob posted in the header."));
+    }
+
     let prev_state_root: H256 = rollup_public_inputs.prev_state_root;
     let post_state_root: H256 = rollup_public_inputs.post_state_root;
 
@@ -185,13 +204,9 @@ pub fn verify_proof<P: RollupProof>(
         ));
     }
 
-    #[cfg(feature = "zkvm-risc0")]
-    match env::verify(img_id.0, &to_vec(&prev_public_input).unwrap()) {
-        Ok(()) => {
-            println!("Verified proof");
-            ()
-        }
-        Err(e) => return Err(anyhow::anyhow!("Invalid proof")),
+    match Z::verify(img_id.0, &prev_public_input) {
+        Ok(()) => (),
+        Err(_) => return Err(anyhow::anyhow!("Invalid proof")),
     }
 
     Ok(AdapterPublicInputs {
@@ -1,106 +1,263 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
 
-use anyhow::anyhow;
-use nexus_core::types::{AccountState, AccountWithProof, NexusHeader, Transaction, H256};
+use nexus_core::traits::NexusTransaction;
+use nexus_core::types::{
+    AccountOwner, AccountWithProof, AccountWithProofHex, NexusBlockWithTransactions, NexusHeader,
+    Transaction, TransactionWithStatus, H256,
+};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use tokio::time::sleep;
+
+pub use crate::types::DEFAULT_NEXUS_URL;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+
+#[derive(Debug)]
+pub enum NexusAPIError {
+    /// Nexus could not be reached, or did not respond in time.
+    Unreachable(reqwest::Error),
+    NotFound(String),
+    /// Nexus responded with an error status.
+    Status {
+        status: StatusCode,
+        message: String,
+    },
+    /// Response body could not be decoded.
+    Decode(reqwest::Error),
+}
+
+impl fmt::Display for NexusAPIError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreachable(e) => write!(f, "Nexus unreachable: {}", e),
+            Self::NotFound(message) => write!(f, "Not found: {}", message),
+            Self::Status { status, message } => {
+                write!(
+                    f,
+                    "Request failed with status code: {}, {}",
+                    status, message
+                )
+            }
+            Self::Decode(e) => write!(f, "Invalid response from nexus: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for NexusAPIError {}
+
+impl NexusAPIError {
+    /// Whether the request might succeed if sent again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Unreachable(_) => true,
+            Self::Status { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct NexusAPI {
     url: String,
     client: reqwest::Client,
+    max_retries: u32,
 }
 
 impl NexusAPI {
     pub fn new(url: &str) -> Self {
+        Self::with_config(url, DEFAULT_TIMEOUT, DEFAULT_MAX_RETRIES)
+    }
+
+    pub fn with_config(url: &str, timeout: Duration, max_retries: u32) -> Self {
+        let client = match reqwest::Client::builder().timeout(timeout).build() {
+            Ok(i) => i,
+            Err(_) => reqwest::Client::new(),
+        };
+
         Self {
-            url: String::from(url),
-            client: reqwest::Client::new(),
+            url: String::from(url.trim_end_matches('/')),
+            client,
+            max_retries,
         }
     }
 
-    pub async fn send_tx(&self, tx: Transaction) -> Result<String, anyhow::Error> {
-        let url_with_tx = format!("{}/tx", self.url);
-
-        let response = self.client.post(url_with_tx).json(&tx).send().await?;
-
-        // Check if the request was successful
-        if response.status().is_success() {
-            Ok(match response.text().await {
-                Ok(i) => i,
-                Err(e) => return Err(anyhow!(e)),
-            })
-        } else {
-            Err(anyhow!(
-                "Post transaction call failed with status code: {}",
-                response.status()
-            ))
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends the request, retrying up to `max_retries` times with exponential backoff if
+    /// nexus is unreachable or fails with a server error. Client errors are returned
+    /// right away. Only idempotent requests should be retried.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        max_retries: u32,
+    ) -> Result<Response, NexusAPIError> {
+        let mut attempt: u32 = 0;
+
+        loop {
+            let result = match request.try_clone() {
+                Some(i) => i.send().await,
+                //Streaming bodies cannot be retried.
+                None => return request.send().await.map_err(NexusAPIError::Unreachable),
+            };
+
+            let error = match result {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) if response.status() == StatusCode::NOT_FOUND => {
+                    return Err(NexusAPIError::NotFound(
+                        response.text().await.unwrap_or_default(),
+                    ))
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let error = NexusAPIError::Status {
+                        status,
+                        message: response.text().await.unwrap_or_default(),
+                    };
+
+                    if !status.is_server_error() {
+                        return Err(error);
+                    }
+
+                    error
+                }
+                Err(e) => NexusAPIError::Unreachable(e),
+            };
+
+            if attempt >= max_retries {
+                return Err(error);
+            }
+
+            sleep(BASE_BACKOFF * 2u32.pow(attempt)).await;
+            attempt += 1;
         }
     }
 
-    pub async fn get_header(&self, hash: &H256) -> Result<NexusHeader, anyhow::Error> {
-        let hash_hex = hex::encode(hash.as_slice());
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<T, NexusAPIError> {
+        let request = self
+            .client
+            .get(format!("{}/{}", self.url, path))
+            .query(params);
 
-        let url_with_hash = format!("{}/header?hash={}", self.url, hash_hex);
+        self.send(request, self.max_retries)
+            .await?
+            .json()
+            .await
+            .map_err(NexusAPIError::Decode)
+    }
 
-        let response = self.client.get(&url_with_hash).send().await?;
+    /// Submits the transaction. Submissions are not idempotent, so a failed one is only
+    /// sent again once nexus confirms it does not know the transaction hash.
+    pub async fn send_tx(&self, tx: Transaction) -> Result<String, NexusAPIError> {
+        let tx_hash = tx.hash();
+        let mut attempt: u32 = 0;
 
-        if response.status().is_success() {
-            let nexus_header: NexusHeader = response.json().await?;
+        loop {
+            let request = self.client.post(format!("{}/tx", self.url)).json(&tx);
 
-            Ok(nexus_header)
-        } else {
-            Err(anyhow!(
-                "Request failed with status code: {}",
-                response.status()
-            ))
+            let error = match self.send(request, 0).await {
+                Ok(response) => return response.text().await.map_err(NexusAPIError::Decode),
+                Err(e) if e.is_transient() => e,
+                Err(e) => return Err(e),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error);
+            }
+
+            sleep(BASE_BACKOFF * 2u32.pow(attempt)).await;
+            attempt += 1;
+
+            //The transaction might have reached the mempool before the request failed.
+            match self.tx_status(&tx_hash).await {
+                Ok(_) => return Ok(String::from("Added tx")),
+                Err(NexusAPIError::NotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
         }
     }
 
-    pub async fn get_range(&self) -> Result<Vec<H256>, anyhow::Error> {
-        let response = reqwest::get(format!("{}/range", self.url)).await?;
+    pub async fn tx_status(&self, tx_hash: &H256) -> Result<TransactionWithStatus, NexusAPIError> {
+        let mut params = HashMap::new();
+        params.insert("tx_hash".to_string(), hex::encode(tx_hash.as_slice()));
 
-        // Check if the request was successful
-        if !response.status().is_success() {
-            println!(
-                "⛔️ Request to nexus failed with status {}. Nexus must be down",
-                response.status()
-            );
+        self.get("tx_status", &params).await
+    }
 
-            return Err(anyhow!(
-                "GET request failed with status: {}",
-                response.status()
-            ));
+    /// Fetches a block by hash or number, or the latest block if neither is given.
+    pub async fn get_block(
+        &self,
+        block_hash: Option<&H256>,
+        block_number: Option<u32>,
+    ) -> Result<NexusBlockWithTransactions, NexusAPIError> {
+        let mut params = HashMap::new();
+        if let Some(hash) = block_hash {
+            params.insert("block_hash".to_string(), hex::encode(hash.as_slice()));
         }
+        if let Some(number) = block_number {
+            params.insert("block_number".to_string(), number.to_string());
+        }
+
+        self.get("block", &params).await
+    }
 
-        let range: Vec<H256> = response.json().await?;
+    pub async fn get_header(&self, hash: &H256) -> Result<NexusHeader, NexusAPIError> {
+        let mut params = HashMap::new();
+        params.insert("hash".to_string(), hex::encode(hash.as_slice()));
 
-        Ok(range)
+        self.get("header", &params).await
+    }
+
+    pub async fn get_range(&self) -> Result<Vec<H256>, NexusAPIError> {
+        self.get("range", &HashMap::new()).await
     }
 
     pub async fn get_account_state(
         &self,
         app_account_id: &H256,
-    ) -> Result<AccountWithProof, anyhow::Error> {
-        let app_account_id = hex::encode(app_account_id.as_slice());
+    ) -> Result<AccountWithProof, NexusAPIError> {
         let mut params = HashMap::new();
-        params.insert("app_account_id".to_string(), app_account_id.to_string());
+        params.insert(
+            "app_account_id".to_string(),
+            hex::encode(app_account_id.as_slice()),
+        );
 
-        let response = self
-            .client
-            .get(&format!("{}/account", self.url))
-            .query(&params)
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            let account: AccountWithProof = response.json().await?;
-
-            Ok(account)
-        } else {
-            Err(anyhow!(
-                "Request failed with status code: {}, url: {}",
-                response.status(),
-                &format!("{}/account", self.url)
-            ))
-        }
+        self.get("account", &params).await
+    }
+
+    pub async fn get_account_owner(
+        &self,
+        app_account_id: &H256,
+    ) -> Result<AccountOwner, NexusAPIError> {
+        let mut params = HashMap::new();
+        params.insert(
+            "app_account_id".to_string(),
+            hex::encode(app_account_id.as_slice()),
+        );
+
+        self.get("account_owner", &params).await
+    }
+
+    pub async fn get_account_state_hex(
+        &self,
+        app_account_id: &H256,
+    ) -> Result<AccountWithProofHex, NexusAPIError> {
+        let mut params = HashMap::new();
+        params.insert(
+            "app_account_id".to_string(),
+            hex::encode(app_account_id.as_slice()),
+        );
+
+        self.get("account-hex", &params).await
     }
 }
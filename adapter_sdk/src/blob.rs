@@ -0,0 +1,84 @@
+use crate::inclusion::keccak256;
+use crate::types::BlobStatus;
+use anyhow::{anyhow, Error};
+use nexus_core::types::{AppId, H256};
+use relayer::SimpleRelayer;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub(crate) type Submissions = Arc<Mutex<HashMap<H256, BlobStatus>>>;
+
+/// Submits rollup blobs to Avail under the app ID of the adapter. Submitted blobs are
+/// tracked until finalized, and picked up by the adapter queue once their block is
+/// processed, so rollup proofs for them can be matched by blob hash. Tracking ends once
+/// the adapter proof of their block is generated.
+#[derive(Clone)]
+pub struct BlobSubmitter {
+    relayer: Arc<SimpleRelayer>,
+    seed: String,
+    app_id: AppId,
+    submissions: Submissions,
+}
+
+impl BlobSubmitter {
+    pub(crate) fn new(
+        avail_url: &str,
+        seed: String,
+        app_id: AppId,
+        submissions: Submissions,
+    ) -> Self {
+        Self {
+            relayer: Arc::new(SimpleRelayer::new(avail_url)),
+            seed,
+            app_id,
+            submissions,
+        }
+    }
+
+    /// Submits the blob in the background, returning the blob hash rollup proofs are
+    /// expected to commit to.
+    pub async fn submit_blob(&self, data: Vec<u8>) -> Result<H256, Error> {
+        let blob_hash = keccak256(&data);
+
+        {
+            let mut submissions = self.submissions.lock().await;
+            match submissions.get(&blob_hash) {
+                None | Some(BlobStatus::Failed(_)) => (),
+                Some(_) => return Err(anyhow!("Blob already submitted")),
+            }
+            submissions.insert(blob_hash.clone(), BlobStatus::Submitted);
+        }
+
+        let submitter = self.clone();
+        let hash = blob_hash.clone();
+        tokio::spawn(async move {
+            let result = submitter
+                .relayer
+                .submit_data(&submitter.seed, submitter.app_id.clone(), data)
+                .await;
+            let mut submissions = submitter.submissions.lock().await;
+
+            match result {
+                Ok(block_hash) => {
+                    println!("📦 Blob {:?} finalized in block {:?}", hash, block_hash);
+
+                    //Queue might have picked up the blob already.
+                    if submissions.get(&hash) == Some(&BlobStatus::Submitted) {
+                        submissions.insert(hash, BlobStatus::Finalized { block_hash });
+                    }
+                }
+                Err(e) => {
+                    println!("❌ Blob submission failed for {:?}: {}", hash, e);
+                    submissions.insert(hash, BlobStatus::Failed(e.to_string()));
+                }
+            }
+        });
+
+        Ok(blob_hash)
+    }
+
+    pub async fn status(&self, blob_hash: &H256) -> Option<BlobStatus> {
+        self.submissions.lock().await.get(blob_hash).cloned()
+    }
+}
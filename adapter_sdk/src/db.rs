@@ -1,6 +1,6 @@
 use crate::state::QueueItem;
 use crate::traits::RollupProof;
-use crate::types::AdapterPublicInputs;
+use crate::types::{AdapterPublicInputs, RollupProofWithPublicInputs};
 use anyhow::Error;
 use nexus_core::db::NodeDB;
 use nexus_core::zkvm::traits::ZKVMProof;
@@ -50,4 +50,49 @@ impl<
     ) -> Result<(), Error> {
         self.0.put(b"last_proof", &proof)
     }
+
+    pub(crate) fn store_quarantined_proof(
+        &self,
+        height: u32,
+        proof: &RollupProofWithPublicInputs<P>,
+    ) -> Result<(), Error> {
+        let key = [b"quarantined-".as_slice(), height.to_be_bytes().as_slice()].concat();
+
+        self.0.put(&key, proof)
+    }
+
+    pub(crate) fn get_quarantined_proof(
+        &self,
+        height: u32,
+    ) -> Result<Option<RollupProofWithPublicInputs<P>>, Error> {
+        let key = [b"quarantined-".as_slice(), height.to_be_bytes().as_slice()].concat();
+
+        self.0.get(&key)
+    }
+
+    pub(crate) fn get_adapter_proof(
+        &self,
+        height: u32,
+    ) -> Result<Option<(ZP, AdapterPublicInputs, u32)>, Error> {
+        let key = [
+            b"adapter-proof-".as_slice(),
+            height.to_be_bytes().as_slice(),
+        ]
+        .concat();
+
+        self.0.get(&key)
+    }
+
+    pub(crate) fn store_adapter_proof(
+        &self,
+        proof: &(ZP, AdapterPublicInputs, u32),
+    ) -> Result<(), Error> {
+        let key = [
+            b"adapter-proof-".as_slice(),
+            proof.2.to_be_bytes().as_slice(),
+        ]
+        .concat();
+
+        self.0.put(&key, proof)
+    }
 }
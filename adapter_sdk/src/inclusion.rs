@@ -0,0 +1,108 @@
+use crate::types::BlobInclusionProof;
+use anyhow::{anyhow, Error};
+use nexus_core::types::{AppId, AvailHeader, Extension, H256};
+use tiny_keccak::{Hasher, Keccak};
+
+pub fn keccak256(data: &[u8]) -> H256 {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+
+    hasher.update(data);
+    hasher.finalize(&mut output);
+
+    H256::from(output)
+}
+
+/// Computes the root of the data merkle tree from a leaf and its proof. Nodes are
+/// ordered by position, as done for the data root of Avail headers.
+pub fn compute_data_root(
+    leaf: &H256,
+    proof: &Vec<H256>,
+    number_of_leaves: u32,
+    leaf_index: u32,
+) -> Result<H256, Error> {
+    if leaf_index >= number_of_leaves {
+        return Err(anyhow!("Leaf index out of bounds of the data tree"));
+    }
+
+    let mut position = leaf_index;
+    let mut width = number_of_leaves;
+    let mut computed = leaf.clone();
+
+    for sibling in proof.iter() {
+        computed = if position % 2 == 1 || position + 1 == width {
+            keccak256(&[sibling.as_slice(), computed.as_slice()].concat())
+        } else {
+            keccak256(&[computed.as_slice(), sibling.as_slice()].concat())
+        };
+
+        position /= 2;
+        width = ((width - 1) / 2) + 1;
+    }
+
+    Ok(computed)
+}
+
+/// Checks that the blob was posted in the given header under the given app id,
+/// returning the blob hash which rollup proofs are expected to commit to.
+///
+/// Leaves of the data tree are the data submissions of the block, in block order, while
+/// the data lookup counts the chunks of each app in the data matrix. The app id of every
+/// submission comes along with the proof: the one of the proven leaf has to be the app
+/// id, and the apps submitting data have to be the ones the lookup has chunks for. App
+/// ids are not part of the data root, so the ones of other submissions are only checked
+/// against the lookup.
+pub fn verify_blob_inclusion(
+    blob: &BlobInclusionProof,
+    avail_header: &AvailHeader,
+    app_id: &AppId,
+) -> Result<H256, Error> {
+    let extension = match &avail_header.extension {
+        Extension::V3(extension) => extension,
+    };
+
+    let leaf = keccak256(&blob.data);
+    if leaf != blob.data_proof.leaf {
+        return Err(anyhow!("Blob data does not match the proven leaf"));
+    }
+
+    if blob.data_proof.data_root != extension.commitment.data_root {
+        return Err(anyhow!("Data proof not against data root of the header"));
+    }
+
+    if blob.submissions.len() != blob.data_proof.number_of_leaves as usize {
+        return Err(anyhow!("Data proof does not cover the data submissions"));
+    }
+
+    let leaf_index = blob.data_proof.leaf_index;
+    if blob.submissions.get(leaf_index as usize) != Some(app_id) {
+        return Err(anyhow!("Proven leaf not submitted under the app id"));
+    }
+
+    let has_chunks = |app_id: &AppId| match extension.app_lookup.range_of(app_id) {
+        Some(range) => range.end > range.start,
+        None => false,
+    };
+    let lookup_matches = blob.submissions.iter().all(has_chunks)
+        && extension
+            .app_lookup
+            .index
+            .iter()
+            .filter(|i| has_chunks(&i.app_id))
+            .all(|i| blob.submissions.contains(&i.app_id));
+    if !lookup_matches {
+        return Err(anyhow!("Data submissions do not match the data lookup"));
+    }
+
+    let data_root = compute_data_root(
+        &leaf,
+        &blob.data_proof.proof,
+        blob.data_proof.number_of_leaves,
+        leaf_index,
+    )?;
+    if data_root != extension.commitment.data_root {
+        return Err(anyhow!("Invalid data inclusion proof"));
+    }
+
+    Ok(leaf)
+}
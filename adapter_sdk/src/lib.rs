@@ -2,11 +2,19 @@ pub mod adapter_zkvm;
 #[cfg(any(feature = "native"))]
 pub mod api;
 #[cfg(any(feature = "native"))]
+pub mod blob;
+#[cfg(any(feature = "native"))]
 mod db;
-//Note: Commenting service for now as not as not being used anywhere.
-// #[cfg(any(feature = "native"))]
-// pub mod service;
+pub mod inclusion;
+#[cfg(any(feature = "native"))]
+pub mod multi;
+#[cfg(any(feature = "native"))]
+pub mod service;
+#[cfg(any(feature = "native"))]
+pub mod signer;
 #[cfg(any(feature = "native"))]
 pub mod state;
+#[cfg(test)]
+mod tests;
 pub mod traits;
 pub mod types;
@@ -0,0 +1,163 @@
+use crate::api::NexusAPI;
+use crate::service;
+use crate::state::{fetch_app_data, AdapterState};
+use crate::traits::RollupProof;
+use crate::types::{AdapterConfig, MultiAdapterConfig};
+use anyhow::{anyhow, Error};
+use nexus_core::types::{AvailHeader, Proof};
+use nexus_core::zkvm::any::ProofConversion;
+use nexus_core::zkvm::traits::{ZKVMEnv, ZKVMProof};
+use relayer::{Relayer, SimpleRelayer};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Runs several adapters in one process. Adapters share a single avail header stream,
+/// Nexus client and API server, while each keeps its own queue, store and proving loop.
+pub struct MultiAdapterHost<
+    P: RollupProof + Clone + DeserializeOwned + Serialize + 'static,
+    Z: ZKVMEnv + 'static,
+    ZP: ZKVMProof + Debug + Clone + DeserializeOwned + Serialize + Send + TryInto<Proof> + 'static,
+> where
+    <ZP as TryInto<Proof>>::Error: Into<anyhow::Error>,
+{
+    config: MultiAdapterConfig,
+    nexus_api: NexusAPI,
+    adapters: Vec<AdapterState<P, Z, ZP>>,
+}
+
+impl<
+        P: RollupProof + Clone + DeserializeOwned + Serialize + Send,
+        Z: ZKVMEnv + Send,
+        ZP: ZKVMProof
+            + Debug
+            + Clone
+            + DeserializeOwned
+            + Serialize
+            + Send
+            + TryInto<Proof>
+            + ProofConversion,
+    > MultiAdapterHost<P, Z, ZP>
+where
+    <ZP as TryInto<Proof>>::Error: Into<anyhow::Error>,
+{
+    pub fn new(config: MultiAdapterConfig) -> Self {
+        Self {
+            nexus_api: NexusAPI::new(&config.nexus_url),
+            config,
+            adapters: vec![],
+        }
+    }
+
+    /// Adds an adapter, storing its data at `storage_path`. The avail and Nexus
+    /// endpoints of the host are used instead of the ones in `config`.
+    pub fn add_adapter(&mut self, storage_path: &str, config: AdapterConfig) -> Result<(), Error> {
+        if self.adapters.iter().any(|i| i.app_id == config.app_id) {
+            return Err(anyhow!(
+                "Adapter already added for app id {}",
+                config.app_id.0
+            ));
+        }
+
+        let mut adapter = AdapterState::new(storage_path, config);
+        adapter.nexus_api = self.nexus_api.clone();
+        adapter.avail_url = self.config.avail_url.clone();
+        self.adapters.push(adapter);
+
+        Ok(())
+    }
+
+    pub async fn run(self) -> Result<(), Error> {
+        let api_addr: SocketAddr = self
+            .config
+            .api_addr
+            .parse()
+            .map_err(|e| anyhow!("Invalid API address {}: {}", self.config.api_addr, e))?;
+
+        let mut relayer = SimpleRelayer::new(&self.config.avail_url);
+        let receiver = relayer.receiver();
+        let relayer = Arc::new(relayer);
+        let relayer_clone = relayer.clone();
+
+        let mut feeds = vec![];
+        let mut handles = HashMap::new();
+        let mut tasks = vec![];
+
+        for mut adapter in self.adapters.into_iter() {
+            let (signer, start_height) = adapter.prepare(&relayer).await?;
+            let handle = adapter.handle()?;
+
+            handles.insert(adapter.app_id.0, handle.clone());
+            feeds.push((start_height, handle));
+
+            let submission_handle = adapter.spawn_submissions(signer);
+            let app_id = adapter.app_id.0;
+            tasks.push(tokio::spawn(async move {
+                if let Err(e) = adapter.process_queue().await {
+                    println!(
+                        "Adapter for app id {} exiting because of error: {:?}",
+                        app_id, e
+                    );
+                }
+            }));
+            tasks.push(tokio::spawn(async move {
+                match submission_handle.await {
+                    Ok(Err(e)) => println!("Submissions for app id {} stopped: {:?}", app_id, e),
+                    Err(e) => println!("Submissions for app id {} stopped: {:?}", app_id, e),
+                    Ok(Ok(_)) => (),
+                }
+            }));
+        }
+
+        let start_height = match feeds.iter().map(|(height, _)| *height).min() {
+            Some(i) => i,
+            None => return Err(anyhow!("No adapters added to host")),
+        };
+
+        tasks.push(tokio::spawn(async move {
+            println!("Start height {}", start_height);
+            relayer.start(start_height).await;
+        }));
+
+        tasks.push(tokio::spawn(async move {
+            let mut receiver = receiver.lock().await;
+
+            while let Some(header) = receiver.recv().await {
+                let header = AvailHeader::from(&header);
+                let active: Vec<_> = feeds
+                    .iter()
+                    .filter(|(height, _)| *height <= header.number)
+                    .map(|(_, handle)| handle)
+                    .collect();
+
+                //Data of all apps is fetched at once for the header.
+                let app_data = fetch_app_data(
+                    &relayer_clone,
+                    &header,
+                    active.iter().map(|i| i.app_id.clone()).collect(),
+                )
+                .await;
+
+                for handle in active {
+                    if let Err(e) = handle.push_header(header.clone(), app_data.clone()).await {
+                        println!(
+                            "Failed to queue header {} for app id {}: {:?}",
+                            header.number, handle.app_id.0, e
+                        );
+                    }
+                }
+            }
+        }));
+
+        tasks.push(tokio::spawn(service::multi_server(handles, api_addr)));
+
+        for task in tasks {
+            task.await?;
+        }
+
+        Ok(())
+    }
+}
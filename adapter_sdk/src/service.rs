@@ -1,13 +1,60 @@
+use crate::state::{add_proof_to_queue, AdapterHandle};
 use crate::traits::RollupProof;
-use crate::{state::AdapterState, types::RollupProofWithPublicInputs};
-use nexus_core::types::Proof;
-use nexus_core::zkvm::traits::{ZKVMEnv, ZKVMProof};
+use crate::types::{
+    AdapterPublicInputs, AdapterStatus, ProofStatus, QueueItemSummary, RollupProofWithPublicInputs,
+};
+use anyhow::Error;
+use nexus_core::types::H256;
+use nexus_core::zkvm::traits::ZKVMProof;
 use serde::de::DeserializeOwned;
-use serde::Serialize;
-use std::fmt::Debug as DebugTrait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use warp::{http::StatusCode, reject::Rejection, reply::Reply, Filter};
+use warp::{
+    http::StatusCode,
+    hyper::body::Bytes,
+    reject::Rejection,
+    reply::{Reply, WithStatus},
+    Filter,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredAdapterProof<ZP> {
+    pub height: u32,
+    pub public_inputs: AdapterPublicInputs,
+    pub proof: ZP,
+}
+
+fn reply<T: Serialize>(result: Result<T, Error>) -> Result<WithStatus<String>, Rejection> {
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(i) => Ok(warp::reply::with_status(i, StatusCode::OK)),
+            Err(_) => Ok(warp::reply::with_status(
+                "Internal error".to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        },
+        Err(e) => Ok(warp::reply::with_status(
+            e.to_string(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
+}
+
+fn parse_height(params: &HashMap<String, String>) -> Result<u32, WithStatus<String>> {
+    match params.get("height").map(|i| i.parse::<u32>()) {
+        Some(Ok(i)) => Ok(i),
+        Some(Err(_)) => Err(warp::reply::with_status(
+            "Invalid height".to_string(),
+            StatusCode::BAD_REQUEST,
+        )),
+        None => Err(warp::reply::with_status(
+            "Height parameter not provided".to_string(),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
 
 async fn health_check_handler() -> Result<impl Reply, Rejection> {
     Ok(warp::reply::with_status("OK", StatusCode::OK))
@@ -15,46 +62,317 @@ async fn health_check_handler() -> Result<impl Reply, Rejection> {
 
 async fn handle_proof_handler<
     P: RollupProof + Clone + Serialize + DeserializeOwned + Send,
-    Z: ZKVMEnv,
-    ZP: ZKVMProof + DebugTrait + Clone + Serialize + DeserializeOwned + Send + TryInto<Proof>,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned + Send,
 >(
-    state: Arc<Mutex<AdapterState<P, Z, ZP>>>,
+    handle: AdapterHandle<P, ZP>,
     proof: RollupProofWithPublicInputs<P>,
-) -> Result<impl Reply, Rejection>
-where
-    <ZP as TryInto<Proof>>::Error: Into<anyhow::Error>,
-{
-    let mut locked_state = state.lock().await;
+) -> Result<WithStatus<String>, Rejection> {
+    let AdapterHandle { queue, db, vk, .. } = handle;
+    //Queue is locked first, as the prover pops the proven item and stores the last
+    //proof under the same lock.
+    let mut queue = queue.lock().await;
+    let last_state_root = match db.lock().await.get_last_proof() {
+        Ok(Some(i)) => i.1.state_root,
+        Ok(None) => H256::zero(),
+        Err(e) => {
+            return Ok(warp::reply::with_status(
+                e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+        }
+    };
 
-    locked_state.add_proof(proof).await;
+    if let Err(e) = add_proof_to_queue(&mut queue, proof, &vk, last_state_root) {
+        return Ok(warp::reply::with_status(
+            e.to_string(),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
 
-    Ok(warp::reply::with_status("Proof received", StatusCode::OK))
+    //Stored right away, so the proof is not lost if the adapter restarts before proving.
+    match db.lock().await.store_last_known_queue(&queue) {
+        Ok(()) => Ok(warp::reply::with_status(
+            "Proof received".to_string(),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            e.to_string(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
 }
 
-pub async fn server<
-    P: RollupProof + Send + Clone + Sync + DeserializeOwned + Serialize,
-    Z: ZKVMEnv,
-    ZP: ZKVMProof + DebugTrait + Clone + Serialize + DeserializeOwned + Send + TryInto<Proof>,
+async fn submit_blob_handler<
+    P: RollupProof + Clone + Serialize + DeserializeOwned,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned,
 >(
-    state: Arc<Mutex<AdapterState<P, Z, ZP>>>,
-) where
-    <ZP as TryInto<Proof>>::Error: Into<anyhow::Error>,
-{
-    // Health check route
-    let health_check_route = warp::get()
-        .and(warp::path("health"))
-        .and_then(health_check_handler);
+    handle: AdapterHandle<P, ZP>,
+    data: Bytes,
+) -> Result<WithStatus<String>, Rejection> {
+    let blob_submitter = match handle.blob_submitter {
+        Some(i) => i,
+        None => {
+            return Ok(warp::reply::with_status(
+                "Blob submission not configured".to_string(),
+                StatusCode::NOT_IMPLEMENTED,
+            ))
+        }
+    };
+
+    match blob_submitter.submit_blob(data.to_vec()).await {
+        Ok(blob_hash) => reply(Ok(blob_hash)),
+        Err(e) => Ok(warp::reply::with_status(
+            e.to_string(),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+async fn blob_status_handler<
+    P: RollupProof + Clone + Serialize + DeserializeOwned,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned,
+>(
+    handle: AdapterHandle<P, ZP>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    let blob_hash = match params.get("blob_hash").map(|i| H256::try_from(i.as_str())) {
+        Some(Ok(i)) => i,
+        Some(Err(_)) => {
+            return Ok(warp::reply::with_status(
+                "Invalid hash".to_string(),
+                StatusCode::BAD_REQUEST,
+            ))
+        }
+        None => {
+            return Ok(warp::reply::with_status(
+                "Hash parameter not provided".to_string(),
+                StatusCode::BAD_REQUEST,
+            ))
+        }
+    };
+
+    let status = match &handle.blob_submitter {
+        Some(i) => i.status(&blob_hash).await,
+        None => None,
+    };
+
+    match status {
+        Some(i) => reply(Ok(i)),
+        None => Ok(warp::reply::with_status(
+            "Blob not submitted through adapter".to_string(),
+            StatusCode::NOT_FOUND,
+        )),
+    }
+}
+
+async fn queue_handler<
+    P: RollupProof + Clone + Serialize + DeserializeOwned,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned,
+>(
+    handle: AdapterHandle<P, ZP>,
+) -> Result<WithStatus<String>, Rejection> {
+    let summary: Vec<QueueItemSummary> = handle
+        .queue
+        .lock()
+        .await
+        .iter()
+        .map(|i| i.summary())
+        .collect();
+
+    reply(Ok(summary))
+}
+
+async fn status_handler<
+    P: RollupProof + Clone + Serialize + DeserializeOwned,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned,
+>(
+    handle: AdapterHandle<P, ZP>,
+) -> Result<WithStatus<String>, Rejection> {
+    let queue_length = handle.queue.lock().await.len();
+    let last_proof = handle.db.lock().await.get_last_proof();
+
+    reply(last_proof.map(|last_proof| AdapterStatus {
+        last_proven_height: last_proof.as_ref().map(|i| i.2),
+        state_root: last_proof.as_ref().map(|i| i.1.state_root.clone()),
+        nexus_hash: last_proof.as_ref().map(|i| i.1.nexus_hash.clone()),
+        queue_length,
+    }))
+}
+
+async fn proof_status_handler<
+    P: RollupProof + Clone + Serialize + DeserializeOwned,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned,
+>(
+    handle: AdapterHandle<P, ZP>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    let AdapterHandle { queue, db, .. } = handle;
+    let height = match parse_height(&params) {
+        Ok(i) => i,
+        Err(e) => return Ok(e),
+    };
+
+    let queued = queue
+        .lock()
+        .await
+        .iter()
+        .find(|i| i.height() == height)
+        .map(|i| i.is_awaiting_proof());
+    let db = db.lock().await;
+
+    let status = match queued {
+        Some(false) => Ok(ProofStatus::Proving),
+        Some(true) => match db.get_quarantined_proof(height) {
+            Ok(Some(_)) => Ok(ProofStatus::Quarantined),
+            Ok(None) => Ok(ProofStatus::AwaitingRollupProof),
+            Err(e) => Err(e),
+        },
+        None => match db.get_last_proof() {
+            Ok(Some(last_proof)) if height <= last_proof.2 => Ok(ProofStatus::Proven),
+            Ok(_) => Ok(ProofStatus::Unknown),
+            Err(e) => Err(e),
+        },
+    };
+
+    reply(status)
+}
 
+async fn adapter_proof_handler<
+    P: RollupProof + Clone + Serialize + DeserializeOwned,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned,
+>(
+    handle: AdapterHandle<P, ZP>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    let height = match parse_height(&params) {
+        Ok(i) => i,
+        Err(e) => return Ok(e),
+    };
+
+    match handle.db.lock().await.get_adapter_proof(height) {
+        Ok(Some((proof, public_inputs, height))) => reply(Ok(StoredAdapterProof {
+            height,
+            public_inputs,
+            proof,
+        })),
+        Ok(None) => Ok(warp::reply::with_status(
+            "Adapter proof not found".to_string(),
+            StatusCode::NOT_FOUND,
+        )),
+        Err(e) => reply::<()>(Err(e)),
+    }
+}
+
+/// Routes of a single adapter, with the adapter resolved by `handle` after the
+/// route name.
+pub(crate) fn routes<
+    P: RollupProof + Send + Clone + DeserializeOwned + Serialize + 'static,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned + Send + 'static,
+>(
+    handle: impl Filter<Extract = (AdapterHandle<P, ZP>,), Error = Rejection> + Clone + Send + Sync,
+) -> impl Filter<Extract = (WithStatus<String>,), Error = Rejection> + Clone {
     // Proof handling route
     let proof_route = warp::post()
         .and(warp::path("proof"))
-        .and(warp::any().map(move || state.clone()))
+        .and(handle.clone())
         .and(warp::body::json())
         .and_then(handle_proof_handler);
 
-    // Combined routes
-    let routes = health_check_route.or(proof_route);
+    let blob_route = warp::post()
+        .and(warp::path("blob"))
+        .and(handle.clone())
+        .and(warp::body::bytes())
+        .and_then(submit_blob_handler);
+
+    let blob_status_route = warp::get()
+        .and(warp::path("blob_status"))
+        .and(handle.clone())
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(blob_status_handler);
+
+    let queue_route = warp::get()
+        .and(warp::path("queue"))
+        .and(handle.clone())
+        .and_then(queue_handler);
+
+    let status_route = warp::get()
+        .and(warp::path("status"))
+        .and(handle.clone())
+        .and_then(status_handler);
+
+    let proof_status_route = warp::get()
+        .and(warp::path("proof_status"))
+        .and(handle.clone())
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(proof_status_handler);
+
+    let adapter_proof_route = warp::get()
+        .and(warp::path("adapter_proof"))
+        .and(handle)
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(adapter_proof_handler);
+
+    proof_route
+        .or(blob_route)
+        .unify()
+        .or(blob_status_route)
+        .unify()
+        .or(queue_route)
+        .unify()
+        .or(status_route)
+        .unify()
+        .or(proof_status_route)
+        .unify()
+        .or(adapter_proof_route)
+        .unify()
+}
+
+pub(crate) async fn server<
+    P: RollupProof + Send + Clone + DeserializeOwned + Serialize + 'static,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned + Send + 'static,
+>(
+    handle: AdapterHandle<P, ZP>,
+    addr: SocketAddr,
+) {
+    // Health check route
+    let health_check_route = warp::get()
+        .and(warp::path("health"))
+        .and_then(health_check_handler);
+
+    let routes = health_check_route.or(routes(warp::any().map(move || handle.clone())));
+
+    println!("🌐 Adapter API listening on {}", addr);
+    warp::serve(routes).run(addr).await;
+}
+
+/// Serves the API of several adapters, with the app ID following the route name,
+/// for example `POST /proof/{app_id}`.
+pub(crate) async fn multi_server<
+    P: RollupProof + Send + Clone + DeserializeOwned + Serialize + 'static,
+    ZP: ZKVMProof + Clone + Serialize + DeserializeOwned + Send + 'static,
+>(
+    handles: HashMap<u32, AdapterHandle<P, ZP>>,
+    addr: SocketAddr,
+) {
+    let handles = Arc::new(handles);
+    let handle = warp::path::param::<u32>().and_then(move |app_id: u32| {
+        let handles = handles.clone();
+
+        async move {
+            match handles.get(&app_id) {
+                Some(i) => Ok(i.clone()),
+                None => Err(warp::reject::not_found()),
+            }
+        }
+    });
+
+    // Health check route
+    let health_check_route = warp::get()
+        .and(warp::path("health"))
+        .and_then(health_check_handler);
+
+    let routes = health_check_route.or(routes(handle));
 
-    // Start the server
-    warp::serve(routes).run(([127, 0, 0, 1], 3031)).await;
+    println!("🌐 Multi adapter API listening on {}", addr);
+    warp::serve(routes).run(addr).await;
 }
@@ -0,0 +1,78 @@
+use crate::api::NexusAPI;
+use crate::types::SigningKeySource;
+use anyhow::{anyhow, Error};
+use ed25519_dalek::{Signer, SigningKey};
+use nexus_core::traits::NexusTransaction;
+use nexus_core::types::{AppAccountId, Transaction, TxParams, TxSignature, H256};
+
+pub(crate) fn read_seed(source: &SigningKeySource) -> Result<String, Error> {
+    match source {
+        SigningKeySource::Keystore(path) => std::fs::read_to_string(path)
+            .map(|seed| String::from(seed.trim()))
+            .map_err(|e| anyhow!("Could not read keystore {}: {}", path, e)),
+        SigningKeySource::Env(var) => {
+            std::env::var(var).map_err(|_| anyhow!("Environment variable {} not set", var))
+        }
+        SigningKeySource::DevSeed(seed) => Ok(seed.clone()),
+    }
+}
+
+/// Signs the Nexus transactions of an adapter.
+#[derive(Clone)]
+pub struct TxSigner {
+    key: SigningKey,
+}
+
+impl TxSigner {
+    pub fn from_seed(seed: &str) -> Result<Self, Error> {
+        let bytes = hex::decode(seed.trim().trim_start_matches("0x"))
+            .map_err(|_| anyhow!("Signing key seed is not valid hex"))?;
+        let seed: [u8; 32] = match bytes.try_into() {
+            Ok(i) => i,
+            Err(_) => return Err(anyhow!("Signing key seed must be 32 bytes")),
+        };
+
+        Ok(Self {
+            key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    pub fn from_source(source: &SigningKeySource) -> Result<Self, Error> {
+        Self::from_seed(&read_seed(source)?)
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    pub fn sign(&self, params: TxParams) -> Transaction {
+        let mut tx = Transaction {
+            signature: TxSignature([0u8; 64]),
+            params,
+            signer: Some(self.public_key()),
+        };
+        tx.signature = TxSignature(self.key.sign(tx.hash().as_slice()).to_bytes());
+
+        tx
+    }
+
+    /// Fails if the account was registered on Nexus by a different key. Accounts that are
+    /// not registered yet, or were registered without a signature, are accepted.
+    pub async fn check_owner(
+        &self,
+        nexus_api: &NexusAPI,
+        app_account_id: &AppAccountId,
+    ) -> Result<(), Error> {
+        let account_owner = nexus_api
+            .get_account_owner(&app_account_id.as_h256())
+            .await?;
+
+        match account_owner.owner {
+            Some(owner) if owner != H256::from(self.public_key()) => Err(anyhow!(
+                "Signing key does not match owner {:?} registered for the app",
+                owner
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -2,56 +2,237 @@
 // track the last queried block of the rollup
 // manage a basic data store for the proof generated with the following data: till_avail_block, proof, receipt
 
-use crate::api::NexusAPI;
+use crate::api::{NexusAPI, NexusAPIError};
+use crate::blob::{BlobSubmitter, Submissions};
 use crate::db::DB;
+use crate::service;
+use crate::signer::{read_seed, TxSigner};
 use crate::traits::RollupProof;
 use crate::types::{
-    AdapterConfig, AdapterPrivateInputs, AdapterPublicInputs, RollupProofWithPublicInputs,
-    RollupPublicInputs,
+    AdapterConfig, AdapterPrivateInputs, AdapterPublicInputs, BlobInclusionProof, BlobStatus,
+    QueueItemSummary, RollupProofWithPublicInputs, RollupPublicInputs, SigningKeySource,
 };
 use anyhow::{anyhow, Error};
+use nexus_core::traits::NexusTransaction;
 use nexus_core::types::Proof;
 use nexus_core::types::{
-    AppAccountId, AppId, AvailHeader, InitAccount, NexusHeader, Proof as ZKProof, StatementDigest,
-    SubmitProof, Transaction, TxParams, TxSignature, H256,
+    AccountState, AppAccountId, AppId, AvailHeader, InitAccount, NexusHeader, Proof as ZKProof,
+    StatementDigest, SubmitProof, Transaction, TransactionStatus, TxParams, TxSignature, H256,
 };
-#[cfg(feature = "native-risc0")]
-use nexus_core::zkvm::risczero::{ProofConversion, RiscZeroProver};
-#[cfg(any(feature = "native-sp1"))]
-use nexus_core::zkvm::sp1::{ProofConversion, Sp1Prover};
+use nexus_core::zkvm::any::{AnyProof, AnyProver, ProofConversion};
+use nexus_core::zkvm::proving::{prove_async, ProvingOptions};
 use nexus_core::zkvm::traits::{ZKVMEnv, ZKVMProof, ZKVMProver};
-use nexus_core::zkvm::ProverMode;
+use nexus_core::zkvm::{ProverMode, ZKVMBackend};
+use relayer::types::AppBlockData;
 use relayer::{Relayer, SimpleRelayer};
-#[cfg(feature = "native-risc0")]
-use risc0_zkvm::{default_prover, ExecutorEnvBuilder, Journal, Prover, Receipt, ReceiptClaim};
-#[cfg(feature = "native-risc0")]
-use risc0_zkvm::{serde::from_slice, ExecutorEnv};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::clone;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug as DebugTrait;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::net::SocketAddr;
 use std::rc::Rc;
 use std::sync::Arc;
-use std::{clone, thread};
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use tokio::time::{sleep, Duration};
 
-#[cfg(feature = "native-sp1")]
-use sp1_sdk::{utils, ProverClient, SP1PublicValues, SP1Stdin};
-
-// #[cfg(feature = "native-sp1")]
-//const ELF: &[u8] = include_bytes!("../../zksync_adapter/methods/sp1-guest/elf/riscv32im-succinct-zkvm-elf");
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct InclusionProof(pub Vec<u8>);
+const MAX_PROVING_ATTEMPTS: u32 = 3;
+const MAX_PROVING_BACKOFF_SECS: u64 = 300;
+const MAX_REGISTRATION_POLLS: u32 = 60;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct QueueItem<P: RollupProof + Clone> {
-    proof: Option<RollupProofWithPublicInputs<P>>,
-    blob: Option<(H256, InclusionProof)>,
-    header: AvailHeader,
+    pub(crate) proof: Option<RollupProofWithPublicInputs<P>>,
+    pub(crate) blob: Option<(H256, BlobInclusionProof)>,
+    pub(crate) header: AvailHeader,
+}
+
+impl<P: RollupProof + Clone> QueueItem<P> {
+    pub(crate) fn height(&self) -> u32 {
+        self.header.number
+    }
+
+    pub(crate) fn summary(&self) -> QueueItemSummary {
+        QueueItemSummary {
+            height: self.header.number,
+            blob_hash: self.blob.as_ref().map(|(hash, _)| hash.clone()),
+            has_proof: self.proof.is_some(),
+        }
+    }
+
+    pub(crate) fn is_awaiting_proof(&self) -> bool {
+        self.blob.is_some() && self.proof.is_none()
+    }
+}
+
+/// Delay before the next proving attempt, doubling with each failed attempt up to
+/// `MAX_PROVING_BACKOFF_SECS`.
+pub(crate) fn proving_backoff(failed_attempts: u32) -> Duration {
+    let secs = 2u64.saturating_pow(failed_attempts.min(32));
+
+    Duration::from_secs(secs.min(MAX_PROVING_BACKOFF_SECS))
+}
+
+/// Attaches the rollup proof to the queued blob it commits to, once the proof
+/// verifies against `vk` and chains from the last accepted proof. `last_state_root`
+/// is the state root of the last adapter proof, used when no earlier queued blob
+/// has a proof.
+pub(crate) fn add_proof_to_queue<P: RollupProof + Clone>(
+    queue: &mut VecDeque<QueueItem<P>>,
+    proof: RollupProofWithPublicInputs<P>,
+    vk: &[u8; 32],
+    last_state_root: H256,
+) -> Result<(), Error> {
+    let mut expected_prev_state_root = last_state_root;
+
+    for item in queue.iter_mut() {
+        let blob_hash = match &item.blob {
+            Some((blob_hash, _)) => blob_hash,
+            //Empty blocks do not change the rollup state.
+            None => continue,
+        };
+
+        //Check if blob hash matches the blob hash in PI,
+        if blob_hash != &proof.public_inputs.blob_hash {
+            match &item.proof {
+                Some(i) => {
+                    expected_prev_state_root = i.public_inputs.post_state_root.clone();
+
+                    continue;
+                }
+                None => return Err(anyhow!("Proof for an earlier blob not submitted yet")),
+            }
+        }
+
+        if item.proof.is_some() {
+            return Err(anyhow!("Proof already accepted for blob"));
+        }
+
+        if proof.public_inputs.prev_state_root != expected_prev_state_root {
+            return Err(anyhow!(
+                "Not sequential proof, expected previous state root {:?}",
+                expected_prev_state_root
+            ));
+        }
+
+        proof
+            .proof
+            .verify(vk, &proof.public_inputs)
+            .map_err(|e| anyhow!("Rollup proof verification failed: {}", e))?;
+
+        item.proof = Some(proof);
+
+        return Ok(());
+    }
+
+    Err(anyhow!("Blob not found for given proof"))
+}
+
+/// Shared parts of an adapter, used to feed it headers and serve its API while the
+/// adapter itself is proving.
+#[derive(Clone)]
+pub(crate) struct AdapterHandle<P: RollupProof + Clone, ZP> {
+    pub(crate) app_id: AppId,
+    pub(crate) queue: Arc<Mutex<VecDeque<QueueItem<P>>>>,
+    pub(crate) db: Arc<Mutex<DB<P, ZP>>>,
+    pub(crate) vk: [u8; 32],
+    pub(crate) submissions: Submissions,
+    pub(crate) blob_submitter: Option<BlobSubmitter>,
+}
+
+impl<
+        P: RollupProof + Clone + DeserializeOwned + Serialize,
+        ZP: ZKVMProof + DeserializeOwned + Serialize + Clone,
+    > AdapterHandle<P, ZP>
+{
+    /// Adds a new avail header to the queue, along with the blob of the app in it if any.
+    pub(crate) async fn push_header(
+        &self,
+        header: AvailHeader,
+        app_data: Vec<AppBlockData>,
+    ) -> Result<(), Error> {
+        let mut submissions = self.submissions.lock().await;
+        let pending: Vec<H256> = submissions
+            .iter()
+            .filter(|(_, status)| {
+                matches!(status, BlobStatus::Submitted | BlobStatus::Finalized { .. })
+            })
+            .map(|(hash, _)| hash.clone())
+            .collect();
+
+        let blob = app_data
+            .into_iter()
+            .filter(|data| data.app_id == self.app_id)
+            .find_map(|data| {
+                let mut extrinsics = data.extrinsics;
+                if extrinsics.len() > 1 {
+                    println!(
+                        "More than one blob found at height {}, using the one submitted by the adapter, or the first one.",
+                        header.number
+                    );
+                }
+                let index = extrinsics
+                    .iter()
+                    .position(|i| pending.contains(&i.data_proof.leaf))
+                    .unwrap_or(0);
+                if index >= extrinsics.len() {
+                    return None;
+                }
+                let extrinsic = extrinsics.swap_remove(index);
+
+                Some((
+                    extrinsic.data_proof.leaf.clone(),
+                    BlobInclusionProof {
+                        data: extrinsic.data,
+                        submissions: data.submissions,
+                        data_proof: extrinsic.data_proof,
+                    },
+                ))
+            });
+
+        if let Some((blob_hash, _)) = &blob {
+            if submissions.contains_key(blob_hash) {
+                submissions.insert(
+                    blob_hash.clone(),
+                    BlobStatus::Queued {
+                        height: header.number,
+                    },
+                );
+            }
+        }
+        drop(submissions);
+
+        let new_queue_item = QueueItem {
+            proof: None,
+            blob,
+            header,
+        };
+        let mut queue = self.queue.lock().await;
+        queue.push_back(new_queue_item);
+
+        //Storing the queue in storage.
+        self.db.lock().await.store_last_known_queue(&queue)
+    }
+}
+
+/// Fetches the data of the given apps in the block, retrying until avail responds.
+pub(crate) async fn fetch_app_data(
+    relayer: &SimpleRelayer,
+    header: &AvailHeader,
+    app_ids: Vec<AppId>,
+) -> Vec<AppBlockData> {
+    loop {
+        match relayer.get_app_data(header.hash(), app_ids.clone()).await {
+            Ok(i) => return i,
+            Err(e) => {
+                println!("Error fetching app data, retrying in 2 seconds: {}", e);
+                sleep(Duration::from_secs(2)).await;
+            }
+        }
+    }
 }
 
 // usage : create an object for this struct and use as a global dependency
@@ -82,7 +263,13 @@ pub struct AdapterState<
     pub pp: PhantomData<ZP>,
     pub nexus_api: NexusAPI,
     pub prover_mode: ProverMode,
+    pub proving_system: ZKVMBackend,
     pub avail_url: String,
+    pub signing_key: SigningKeySource,
+    pub start_nexus_hash: Option<H256>,
+    pub api_addr: String,
+    pub avail_signing_key: Option<SigningKeySource>,
+    pub blob_submissions: Submissions,
 }
 
 impl<
@@ -115,13 +302,101 @@ where
             db: Arc::new(Mutex::new(db)),
             p: PhantomData,
             pp: PhantomData,
-            nexus_api: NexusAPI::new(&"http://127.0.0.1:7000"),
+            nexus_api: NexusAPI::new(&config.nexus_url),
             prover_mode: config.prover_mode,
+            proving_system: config.proving_system.unwrap_or_default(),
             avail_url: config.avail_url,
+            signing_key: config.signing_key,
+            start_nexus_hash: config.start_nexus_hash,
+            api_addr: config.api_addr,
+            avail_signing_key: config.avail_signing_key,
+            blob_submissions: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Returns a handle to submit blobs to Avail for this adapter, which can be used
+    /// while the adapter is running.
+    pub fn blob_submitter(&self) -> Result<BlobSubmitter, Error> {
+        let seed = match &self.avail_signing_key {
+            Some(i) => read_seed(i)?,
+            None => return Err(anyhow!("Avail signing key not configured for adapter")),
+        };
+
+        Ok(BlobSubmitter::new(
+            &self.avail_url,
+            seed,
+            self.app_id.clone(),
+            self.blob_submissions.clone(),
+        ))
+    }
+
     pub async fn run(&mut self) -> Result<(), Error> {
+        let api_addr: SocketAddr = self
+            .api_addr
+            .parse()
+            .map_err(|e| anyhow!("Invalid API address {}: {}", self.api_addr, e))?;
+
+        //On every new header,
+        //Check if the block is empty for the stored app ID.
+        let mut relayer = SimpleRelayer::new(&self.avail_url);
+        let receiver = relayer.receiver();
+        let relayer = Arc::new(relayer);
+        let relayer_clone = relayer.clone();
+        let (signer, start_height) = self.prepare(&relayer).await?;
+
+        let relayer_handle = tokio::spawn(async move {
+            println!("Start height {}", start_height);
+            //TODO: Should be able to start from last processed height.
+            relayer.start(start_height).await;
+        });
+
+        let handle = self.handle()?;
+        let handle_clone = handle.clone();
+
+        let avail_syncer_handle = tokio::spawn(async move {
+            let mut receiver = receiver.lock().await;
+
+            while let Some(header) = receiver.recv().await {
+                let header = AvailHeader::from(&header);
+                let app_data =
+                    fetch_app_data(&relayer_clone, &header, vec![handle_clone.app_id.clone()])
+                        .await;
+
+                handle_clone.push_header(header, app_data).await.unwrap();
+            }
+        });
+        let api_handle = tokio::spawn(service::server(handle, api_addr));
+        let submission_handle = self.spawn_submissions(signer);
+
+        match self.process_queue().await {
+            Ok(_) => (),
+            Err(e) => println!("Exiting because of error: {:?}", e),
+        };
+
+        tokio::try_join!(
+            avail_syncer_handle,
+            relayer_handle,
+            submission_handle,
+            api_handle
+        )
+        .unwrap()
+        .2
+        .unwrap();
+
+        Ok(())
+    }
+
+    /// Loads the signing key, restores the stored queue and registers the account if
+    /// required. Returns the signer along with the avail height to sync from.
+    pub(crate) async fn prepare(
+        &mut self,
+        relayer: &SimpleRelayer,
+    ) -> Result<(TxSigner, u32), Error> {
+        let signer = TxSigner::from_source(&self.signing_key)?;
+        signer
+            .check_owner(&self.nexus_api, &AppAccountId::from(self.app_id.clone()))
+            .await?;
+
         let (stored_queue, previous_adapter_proof) = {
             let db = self.db.lock().await;
 
@@ -138,98 +413,151 @@ where
         drop(queue);
         self.previous_adapter_proof = previous_adapter_proof;
 
-        //On every new header,
-        //Check if the block is empty for the stored app ID.
-        let mut relayer = SimpleRelayer::new(&self.avail_url);
-        let receiver = relayer.receiver();
         let start_height = match &self.previous_adapter_proof {
             Some(i) => i.2,
             None => self.starting_block_number,
         };
-        if self.previous_adapter_proof.is_none() {
-            let header_hash = relayer.get_header_hash(self.starting_block_number).await;
-            let nexus_hash: H256 = self.nexus_api.get_header(&header_hash).await?.hash();
-            let tx = Transaction {
-                signature: TxSignature([0u8; 64]),
-                params: TxParams::InitAccount(InitAccount {
-                    app_id: AppAccountId::from(self.app_id.clone()),
-                    statement: self.elf_id.clone(),
-                    start_nexus_hash: nexus_hash,
-                }),
-            };
+        self.register_account(&signer, relayer).await?;
 
-            let client = reqwest::Client::new();
+        Ok((signer, start_height))
+    }
 
-            let response = client
-                .post("http://127.0.0.1:7000/tx")
-                .json(&tx)
-                .send()
-                .await?;
+    pub(crate) fn handle(&self) -> Result<AdapterHandle<P, ZP>, Error> {
+        Ok(AdapterHandle {
+            app_id: self.app_id.clone(),
+            queue: self.queue.clone(),
+            db: self.db.clone(),
+            vk: self.vk,
+            submissions: self.blob_submissions.clone(),
+            blob_submitter: match &self.avail_signing_key {
+                Some(_) => Some(self.blob_submitter()?),
+                None => None,
+            },
+        })
+    }
 
-            // Check if the request was successful
-            if response.status().is_success() {
-                let body = response.text().await?;
-                println!("✅ Initiated rollup {}", body);
-            } else {
-                println!("❌ Failed to initiate rollup {}", response.status());
-            }
-        }
+    pub(crate) fn spawn_submissions(&self, signer: TxSigner) -> JoinHandle<Result<P, Error>> {
+        let db = self.db.clone();
+        let nexus_api = self.nexus_api.clone();
 
-        let relayer_handle = tokio::spawn(async move {
-            println!("Start height {}", start_height);
-            //TODO: Should be able to start from last processed height.
-            relayer.start(start_height).await;
-        });
+        tokio::spawn(async move { Self::manage_submissions(db, &nexus_api, &signer).await })
+    }
 
-        let queue_clone = self.queue.clone();
-        let db_clone = self.db.clone();
-        let db_clone_2 = self.db.clone();
+    /// Registers the app account on Nexus if not done already, and waits for the
+    /// registration to be included before returning.
+    async fn register_account(
+        &self,
+        signer: &TxSigner,
+        relayer: &SimpleRelayer,
+    ) -> Result<(), Error> {
+        let app_account_id = AppAccountId::from(self.app_id.clone());
+        let account = loop {
+            match self
+                .nexus_api
+                .get_account_state(&app_account_id.as_h256())
+                .await
+            {
+                Ok(i) => break i.account,
+                //Nexus serves accounts once it has built its first block.
+                Err(NexusAPIError::NotFound(_)) => {
+                    println!("⏳ Nexus has no blocks yet, waiting to check account registration");
+                    sleep(Duration::from_secs(2)).await;
+                }
+                Err(e) => return Err(anyhow!(e)),
+            }
+        };
 
-        let avail_syncer_handle = tokio::spawn(async move {
-            let mut receiver = receiver.lock().await;
+        let proving_system = self.proving_system;
 
-            while let Some(header) = receiver.recv().await {
-                let new_queue_item = QueueItem {
-                    proof: None,
-                    blob: None,
-                    header: AvailHeader::from(&header),
-                };
-                let mut queue = queue_clone.lock().await;
-                queue.push_back(new_queue_item);
-
-                //Storing the queue in storage.
-                db_clone
-                    .lock()
-                    .await
-                    .store_last_known_queue(&queue)
-                    .unwrap();
+        if account != AccountState::zero() {
+            if account.proving_system != proving_system {
+                return Err(anyhow!(
+                    "Account registered with {:?} proving system, adapter proves with {:?}",
+                    account.proving_system,
+                    proving_system
+                ));
             }
-        });
-        let nexus_api_clone = self.nexus_api.clone();
-        let submission_handle = tokio::spawn(async move {
-            let nexus_api = nexus_api_clone;
-            Self::manage_submissions(db_clone_2, &nexus_api).await
-        });
 
-        match self.process_queue().await {
-            Ok(_) => (),
-            Err(e) => println!("Exiting because of error: {:?}", e),
+            if account.statement != self.elf_id {
+                return Err(anyhow!(
+                    "Account registered with statement {:?}, adapter uses {:?}",
+                    account.statement,
+                    self.elf_id
+                ));
+            }
+
+            return Ok(());
+        }
+
+        let start_nexus_hash: H256 = match &self.start_nexus_hash {
+            Some(i) => i.clone(),
+            None => {
+                let header_hash = relayer.get_header_hash(self.starting_block_number).await;
+
+                loop {
+                    match self.nexus_api.get_header(&header_hash).await {
+                        Ok(i) => break i.hash(),
+                        Err(NexusAPIError::NotFound(_)) => {
+                            println!(
+                                "⏳ Nexus header not available for avail height {}, waiting to register account",
+                                self.starting_block_number
+                            );
+                            sleep(Duration::from_secs(2)).await;
+                        }
+                        Err(e) => return Err(anyhow!(e)),
+                    }
+                }
+            }
         };
 
-        tokio::try_join!(avail_syncer_handle, relayer_handle, submission_handle)
-            .unwrap()
-            .2
-            .unwrap();
+        let tx = signer.sign(TxParams::InitAccount(InitAccount {
+            app_id: app_account_id,
+            statement: self.elf_id.clone(),
+            start_nexus_hash,
+            proving_system,
+        }));
+        let tx_hash = tx.hash();
+        self.nexus_api.send_tx(tx).await?;
+        println!("⏳ Submitted account registration, waiting for inclusion");
 
-        Ok(())
+        for _ in 0..MAX_REGISTRATION_POLLS {
+            sleep(Duration::from_secs(2)).await;
+
+            let tx = match self.nexus_api.tx_status(&tx_hash).await {
+                Ok(i) => i,
+                Err(NexusAPIError::NotFound(_)) => continue,
+                Err(e) => return Err(anyhow!(e)),
+            };
+
+            match tx.status {
+                TransactionStatus::InPool => continue,
+                TransactionStatus::Successful => {
+                    println!("✅ Registered rollup account");
+
+                    return Ok(());
+                }
+                TransactionStatus::Failed => {
+                    return Err(anyhow!(
+                        "Account registration failed: {}",
+                        match tx.error {
+                            Some(e) => e.message,
+                            None => String::from("Unknown reason"),
+                        }
+                    ))
+                }
+            }
+        }
+
+        Err(anyhow!("Account registration not included in time"))
     }
 
     async fn manage_submissions(
         db: Arc<Mutex<DB<P, ZP>>>,
         nexus_api: &NexusAPI,
+        signer: &TxSigner,
     ) -> Result<P, Error> {
         loop {
-            thread::sleep(Duration::from_secs(2));
+            sleep(Duration::from_secs(2)).await;
 
             let latest_proof = {
                 let db_lock = db.lock().await;
@@ -247,22 +575,13 @@ where
                 &latest_proof.2, &latest_proof.1.state_root
             );
 
-            let response = reqwest::get("http://127.0.0.1:7000/range").await?;
-
-            // Check if the request was successful
-            if !response.status().is_success() {
-                println!(
-                    "⛔️ Request to nexus failed with status {}. Nexus must be down",
-                    response.status()
-                );
-
-                // Deserialize the response body into a Vec<H256>
-                continue;
-            }
-
             let range: Vec<H256> = match nexus_api.get_range().await {
                 Ok(i) => i,
-                Err(e) => continue,
+                Err(e) => {
+                    println!("⛔️ Request to nexus failed: {}. Nexus must be down", e);
+
+                    continue;
+                }
             };
 
             let mut is_in_range = false;
@@ -274,43 +593,27 @@ where
             }
 
             if is_in_range {
-                println!("INside match");
-                let client = reqwest::Client::new();
-                let tx = Transaction {
-                    signature: TxSignature([0u8; 64]),
-                    params: TxParams::SubmitProof(SubmitProof {
-                        proof: match latest_proof.0.try_into() {
-                            Ok(i) => i,
-                            Err(e) => return Err(anyhow!(e)),
-                        },
-                        height: latest_proof.1.height,
-                        nexus_hash: latest_proof.1.nexus_hash,
-                        state_root: latest_proof.1.state_root,
-                        app_id: latest_proof.1.app_id,
-                        data: None,
-                    }),
-                };
-
-                let response = client
-                    .post("http://127.0.0.1:7000/tx")
-                    .json(&tx)
-                    .send()
-                    .await?;
-
-                // Check if the request was successful
-                if response.status().is_success() {
-                    let body = response.text().await?;
-                    println!(
+                let tx = signer.sign(TxParams::SubmitProof(SubmitProof {
+                    proof: match latest_proof.0.try_into() {
+                        Ok(i) => i,
+                        Err(e) => return Err(anyhow!(e)),
+                    },
+                    height: latest_proof.1.height,
+                    nexus_hash: latest_proof.1.nexus_hash,
+                    state_root: latest_proof.1.state_root,
+                    app_id: latest_proof.1.app_id,
+                    data: None,
+                }));
+
+                match nexus_api.send_tx(tx).await {
+                    Ok(_) => println!(
                         "✅ Posted proof for avail height: {:?}, state root: {:?}",
                         &latest_proof.2, &latest_proof.1.state_root
-                    );
-                } else {
-                    println!(
-                        "❌ Request failed with status: {}, for avail height: {:?}, state root: {:?}",
-                        response.status(),
-                        &latest_proof.2,
-                        &latest_proof.1.state_root
-                    );
+                    ),
+                    Err(e) => println!(
+                        "❌ Request failed: {}, for avail height: {:?}, state root: {:?}",
+                        e, &latest_proof.2, &latest_proof.1.state_root
+                    ),
                 }
             } else {
                 println!("⏳ Not in range yet. Rollup at height: {}", latest_proof.2);
@@ -318,7 +621,7 @@ where
         }
     }
 
-    async fn process_queue(&mut self) -> Result<ZP, Error>
+    pub(crate) async fn process_queue(&mut self) -> Result<ZP, Error>
     where
         ZP: ZKVMProof
             + DebugTrait
@@ -329,6 +632,8 @@ where
             + TryInto<Proof>
             + Debug,
     {
+        let mut failed_attempts: u32 = 0;
+
         loop {
             let queue_item = {
                 let queue_lock = self.queue.lock().await;
@@ -337,73 +642,123 @@ where
             };
 
             if queue_item.is_none() {
-                thread::sleep(Duration::from_secs(2));
+                sleep(Duration::from_secs(2)).await;
 
                 continue; // Restart the loop
             };
             let queue_item = queue_item.unwrap();
-            if queue_item.blob.is_some() && queue_item.proof.is_none() {
-                thread::sleep(Duration::from_secs(10));
+            if queue_item.is_awaiting_proof() {
+                sleep(Duration::from_secs(10)).await;
 
                 continue; // Restart the loop
             }
 
-            let receipt = match self.verify_and_generate_proof(&queue_item).await {
+            //Nexus might not have processed the header yet, this is not a proving failure.
+            let nexus_header: NexusHeader =
+                match self.nexus_api.get_header(&queue_item.header.hash()).await {
+                    Ok(i) => i,
+                    Err(e) => {
+                        println!(
+                            "⏳ Nexus header not available for avail height {}: {:?}",
+                            queue_item.header.number, e
+                        );
+                        sleep(Duration::from_secs(2)).await;
+
+                        continue;
+                    }
+                };
+
+            let mut receipt = match self
+                .verify_and_generate_proof(&queue_item, nexus_header)
+                .await
+            {
+                Ok(i) => i,
                 Err(e) => {
-                    return Err(e);
+                    failed_attempts += 1;
+                    println!(
+                        "❌ Proof generation failed for avail height {}, attempt {}: {:?}",
+                        queue_item.header.number, failed_attempts, e
+                    );
+
+                    //Counter is kept across quarantines, so a block failing for other
+                    //reasons than its rollup proof keeps backing off.
+                    if failed_attempts % MAX_PROVING_ATTEMPTS == 0 {
+                        self.quarantine_rollup_proof(&queue_item).await?;
+                    }
+                    sleep(proving_backoff(failed_attempts)).await;
+
+                    continue;
                 }
-                Ok(i) => i,
             };
-            // TODO]
-            // let adapter_pi: AdapterPublicInputs = from_slice(&receipt.journal.bytes)?;
-            // self.queue.lock().await.pop_front();
-
-            // self.db.lock().await.store_last_proof(&(
-            //     receipt.clone(),
-            //     adapter_pi.clone(),
-            //     queue_item.header.number,
-            // ))?;
-            // self.previous_adapter_proof = Some((
-            //     receipt.clone(),
-            //     adapter_pi.clone(),
-            //     queue_item.header.number,
-            // ));
-        }
-    }
+            failed_attempts = 0;
 
-    pub async fn add_proof(&mut self, proof: RollupProofWithPublicInputs<P>) -> Result<(), Error> {
-        let mut queue = self.queue.lock().await;
+            let adapter_pi: AdapterPublicInputs = receipt.public_inputs()?;
+            let last_proof = (receipt, adapter_pi, queue_item.header.number);
 
-        let mut updated_proof: bool = false;
-        for height in queue.iter_mut() {
-            //Check if blob hash matches the blob hash in PI,
-            match height.blob.clone() {
-                Some(value) => {
-                    //If found, then set updated_proof to true and then reset the proof field from None to the given proof.
-                    if value.0 == proof.public_inputs.blob_hash {
-                        updated_proof = true;
-                        height.proof = Some(proof);
-                        break;
-                    }
-                }
-                None => continue,
+            {
+                let mut queue = self.queue.lock().await;
+                queue.pop_front();
+
+                let db = self.db.lock().await;
+                db.store_last_proof(&last_proof)?;
+                db.store_adapter_proof(&last_proof)?;
+                db.store_last_known_queue(&queue)?;
             }
-            //Improvement will be to check if the proof is verifying.
+            //Proven blobs are no longer tracked, so the same data can be submitted again.
+            if let Some((blob_hash, _)) = &queue_item.blob {
+                self.blob_submissions.lock().await.remove(blob_hash);
+            }
+
+            println!(
+                "✅ Generated adapter proof for avail height {}",
+                queue_item.header.number
+            );
+            self.previous_adapter_proof = Some(last_proof);
         }
+    }
 
-        if updated_proof {
-            return Ok(());
+    /// Moves the rollup proof of the item at the front of the queue to quarantine,
+    /// so the rollup can submit a new proof for the same blob.
+    async fn quarantine_rollup_proof(&mut self, queue_item: &QueueItem<P>) -> Result<(), Error> {
+        let proof = match &queue_item.proof {
+            Some(i) => i,
+            //Nothing to quarantine for empty blocks, will keep retrying.
+            None => return Ok(()),
+        };
+
+        let mut queue = self.queue.lock().await;
+        if let Some(front) = queue.front_mut() {
+            front.proof = None;
         }
 
-        Err(anyhow!("Blob not found for given proof"))
+        let db = self.db.lock().await;
+        db.store_quarantined_proof(queue_item.header.number, proof)?;
+        db.store_last_known_queue(&queue)?;
+
+        println!(
+            "🚫 Quarantined rollup proof for avail height {}",
+            queue_item.header.number
+        );
+
+        Ok(())
+    }
+
+    pub async fn add_proof(&mut self, proof: RollupProofWithPublicInputs<P>) -> Result<(), Error> {
+        let last_state_root = match &self.previous_adapter_proof {
+            Some(i) => i.1.state_root.clone(),
+            None => H256::zero(),
+        };
+        let mut queue = self.queue.lock().await;
+
+        add_proof_to_queue(&mut queue, proof, &self.vk, last_state_root)
     }
 
     // function to generate proof against avail data when proof is received and verified from the rollup
     async fn verify_and_generate_proof(
         &mut self,
         queue_item: &QueueItem<P>,
-        // TODO change the return type to ZP
-    ) -> Result<(ZP), Error>
+        nexus_header: NexusHeader,
+    ) -> Result<ZP, Error>
     where
         ZP: ZKVMProof
             + DebugTrait
@@ -414,13 +769,11 @@ where
             + TryInto<Proof>
             + Debug,
     {
-        let nexus_header: NexusHeader =
-            self.nexus_api.get_header(&queue_item.header.hash()).await?;
-
         let private_inputs = AdapterPrivateInputs {
             nexus_header,
             avail_header: queue_item.header.clone(),
             app_id: self.app_id.clone(),
+            blob: queue_item.blob.as_ref().map(|(_, blob)| blob.clone()),
         };
 
         let prev_pi_and_receipt = match &self.previous_adapter_proof {
@@ -434,35 +787,45 @@ where
             Some(i) => Some(i.clone()),
         };
 
-        //Add prover mode config
-        #[cfg(feature = "native-sp1")]
-        let mut zkvm = Sp1Prover::new(self.elf.clone(), self.prover_mode.clone());
-
-        #[cfg(feature = "native-risc0")]
-        let mut zkvm = RiscZeroProver::new(self.elf.clone(), self.prover_mode.clone());
-
-        let prev_pi: Option<AdapterPublicInputs> = match prev_pi_and_receipt {
-            None => None,
-            Some((receipt, pi, _)) => {
-                // env_builder.add_assumption(receipt);
-                Some(pi)
-            }
-        };
+        let (prev_pi, prev_proof): (Option<AdapterPublicInputs>, Option<AnyProof>) =
+            match prev_pi_and_receipt {
+                None => (None, None),
+                Some((receipt, pi, _)) => {
+                    let proof: Proof = receipt.try_into().map_err(Into::<Error>::into)?;
+                    (
+                        Some(pi),
+                        Some(AnyProof::from_proof(self.proving_system, proof)?),
+                    )
+                }
+            };
 
-        zkvm.add_input(&prev_pi);
-        zkvm.add_input(&queue_item.proof);
-        zkvm.add_input(&private_inputs);
-        zkvm.add_input(&self.elf_id);
-        zkvm.add_input(&self.vk);
+        let proving_system = self.proving_system;
+        let elf = self.elf.clone();
+        let prover_mode = self.prover_mode.clone();
+        let rollup_proof = queue_item.proof.clone();
+        let elf_id = self.elf_id.clone();
+        let vk = self.vk;
 
-        let zkvm_proof = zkvm.prove();
+        //Proving runs on the blocking pool, so adapters sharing the runtime are not stalled.
+        let zkvm_proof = prove_async(
+            move |_| {
+                let mut zkvm = AnyProver::with_backend(proving_system, elf, prover_mode)?;
 
-        let zkvm_proof = match zkvm_proof {
-            Ok(i) => {
-                let proof: ZP = i.try_into().map_err(|_| anyhow!("Conversion failed"))?;
-                return Ok(proof);
-            }
-            Err(e) => return Err(anyhow!(e)),
-        };
+                if let Some(proof) = prev_proof {
+                    zkvm.add_proof_for_recursion(proof)?;
+                }
+                zkvm.add_input(&prev_pi)?;
+                zkvm.add_input(&rollup_proof)?;
+                zkvm.add_input(&private_inputs)?;
+                zkvm.add_input(&elf_id)?;
+                zkvm.add_input(&vk)?;
+
+                zkvm.prove()
+            },
+            ProvingOptions::default(),
+        )
+        .await?;
+
+        ZP::from_any(zkvm_proof)
     }
 }
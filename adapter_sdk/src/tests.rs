@@ -0,0 +1,884 @@
+use crate::adapter_zkvm::verify_proof;
+use crate::inclusion::{keccak256, verify_blob_inclusion};
+#[cfg(feature = "native")]
+use crate::state::{add_proof_to_queue, proving_backoff, QueueItem};
+use crate::traits::RollupProof;
+use crate::types::{
+    AdapterPrivateInputs, AdapterPublicInputs, BlobInclusionProof, RollupProofWithPublicInputs,
+    RollupPublicInputs,
+};
+use anyhow::{anyhow, Error};
+use nexus_core::runtime::NEXUS_RUNTIME_VERSION;
+use nexus_core::types::{
+    AppAccountId, AppId, AvailHeader, DataLookup, DataLookupItem, DataProof, Digest, Extension,
+    KateCommitment, NexusHeader, StatementDigest, V3Extension, H256,
+};
+use nexus_core::zkvm::{traits::ZKVMEnv, ZKVMBackend};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::cell::RefCell;
+#[cfg(feature = "native")]
+use std::collections::VecDeque;
+
+thread_local! {
+    static PROVEN_PUBLIC_INPUTS: RefCell<Vec<Vec<u8>>> = RefCell::new(vec![]);
+}
+
+/// Accepts recursion only for public inputs registered as proven.
+struct MockZKVM;
+
+impl MockZKVM {
+    fn prove<T: Serialize>(public_inputs: &T) {
+        PROVEN_PUBLIC_INPUTS.with(|proven| {
+            proven
+                .borrow_mut()
+                .push(serde_json::to_vec(public_inputs).unwrap())
+        });
+    }
+}
+
+impl ZKVMEnv for MockZKVM {
+    const BACKEND: ZKVMBackend = ZKVMBackend::Mock;
+
+    fn verify<T: Serialize>(_: [u32; 8], public_inputs: &T) -> Result<(), Error> {
+        let encoded = serde_json::to_vec(public_inputs)?;
+
+        match PROVEN_PUBLIC_INPUTS.with(|proven| proven.borrow().contains(&encoded)) {
+            true => Ok(()),
+            false => Err(anyhow!("No proof for public inputs")),
+        }
+    }
+
+    fn read_input<T: DeserializeOwned>() -> Result<T, Error> {
+        Err(anyhow!("No inputs for adapter verification"))
+    }
+
+    fn commit<T: Serialize>(_: &T) {}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MockRollupProof {
+    valid: bool,
+}
+
+impl RollupProof for MockRollupProof {
+    fn verify(&self, _: &[u8; 32], _: &RollupPublicInputs) -> Result<(), Error> {
+        match self.valid {
+            true => Ok(()),
+            false => Err(anyhow!("Invalid rollup proof")),
+        }
+    }
+}
+
+const APP_ID: u32 = 1;
+const IMG_ID: StatementDigest = StatementDigest([7u32; 8]);
+
+fn avail_header(number: u32, blob: Option<&Vec<u8>>) -> AvailHeader {
+    let (app_lookup, data_root) = match blob {
+        Some(data) => (
+            DataLookup {
+                size: 1,
+                index: vec![DataLookupItem {
+                    app_id: AppId(APP_ID),
+                    start: 0,
+                }],
+            },
+            keccak256(data),
+        ),
+        None => (
+            DataLookup {
+                size: 0,
+                index: vec![],
+            },
+            H256::zero(),
+        ),
+    };
+
+    AvailHeader {
+        parent_hash: H256::zero(),
+        number,
+        state_root: H256::zero(),
+        extrinsics_root: H256::zero(),
+        digest: Digest::default(),
+        extension: Extension::V3(V3Extension {
+            app_lookup,
+            commitment: KateCommitment {
+                rows: 1,
+                cols: 1,
+                commitment: vec![],
+                data_root,
+            },
+        }),
+    }
+}
+
+fn private_inputs(
+    avail_header: AvailHeader,
+    parent_hash: H256,
+    blob: Option<&Vec<u8>>,
+) -> AdapterPrivateInputs {
+    let nexus_header = NexusHeader {
+        parent_hash,
+        prev_state_root: H256::zero(),
+        state_root: H256::zero(),
+        tx_root: H256::zero(),
+        avail_header_hash: avail_header.hash(),
+        number: avail_header.number,
+        runtime_version: NEXUS_RUNTIME_VERSION,
+    };
+
+    AdapterPrivateInputs {
+        nexus_header,
+        avail_header,
+        app_id: AppId(APP_ID),
+        blob: blob.map(|data| BlobInclusionProof {
+            data: data.clone(),
+            submissions: vec![AppId(APP_ID)],
+            data_proof: DataProof {
+                data_root: keccak256(data),
+                proof: vec![],
+                number_of_leaves: 1,
+                leaf_index: 0,
+                leaf: keccak256(data),
+            },
+        }),
+    }
+}
+
+const OTHER_APP_ID: u32 = 2;
+
+/// Block with four blobs of one chunk each in a 2x2 data matrix, the first two posted
+/// under `APP_ID` and the last two under `OTHER_APP_ID`, with the inclusion proof of each.
+fn multi_app_block(number: u32, blobs: &[Vec<u8>; 4]) -> (AvailHeader, Vec<BlobInclusionProof>) {
+    let leaves: Vec<H256> = blobs.iter().map(|data| keccak256(data)).collect();
+    let nodes = [
+        keccak256(&[leaves[0].as_slice(), leaves[1].as_slice()].concat()),
+        keccak256(&[leaves[2].as_slice(), leaves[3].as_slice()].concat()),
+    ];
+    let data_root = keccak256(&[nodes[0].as_slice(), nodes[1].as_slice()].concat());
+
+    let avail_header = AvailHeader {
+        parent_hash: H256::zero(),
+        number,
+        state_root: H256::zero(),
+        extrinsics_root: H256::zero(),
+        digest: Digest::default(),
+        extension: Extension::V3(V3Extension {
+            app_lookup: DataLookup {
+                size: 4,
+                index: vec![
+                    DataLookupItem {
+                        app_id: AppId(APP_ID),
+                        start: 0,
+                    },
+                    DataLookupItem {
+                        app_id: AppId(OTHER_APP_ID),
+                        start: 2,
+                    },
+                ],
+            },
+            commitment: KateCommitment {
+                rows: 2,
+                cols: 2,
+                commitment: vec![],
+                data_root: data_root.clone(),
+            },
+        }),
+    };
+
+    let submissions = vec![
+        AppId(APP_ID),
+        AppId(APP_ID),
+        AppId(OTHER_APP_ID),
+        AppId(OTHER_APP_ID),
+    ];
+    let proofs = blobs
+        .iter()
+        .enumerate()
+        .map(|(index, data)| BlobInclusionProof {
+            data: data.clone(),
+            submissions: submissions.clone(),
+            data_proof: DataProof {
+                data_root: data_root.clone(),
+                proof: vec![leaves[index ^ 1].clone(), nodes[(index / 2) ^ 1].clone()],
+                number_of_leaves: 4,
+                leaf_index: index as u32,
+                leaf: leaves[index].clone(),
+            },
+        })
+        .collect();
+
+    (avail_header, proofs)
+}
+
+/// Block in which `APP_ID` posted a blob spanning the first three chunks of a 2x2 data
+/// matrix, and `OTHER_APP_ID` a blob in the last one, with the inclusion proof of each.
+fn multi_chunk_block(number: u32, blobs: &[Vec<u8>; 2]) -> (AvailHeader, Vec<BlobInclusionProof>) {
+    let leaves: Vec<H256> = blobs.iter().map(|data| keccak256(data)).collect();
+    let data_root = keccak256(&[leaves[0].as_slice(), leaves[1].as_slice()].concat());
+
+    let avail_header = AvailHeader {
+        parent_hash: H256::zero(),
+        number,
+        state_root: H256::zero(),
+        extrinsics_root: H256::zero(),
+        digest: Digest::default(),
+        extension: Extension::V3(V3Extension {
+            app_lookup: DataLookup {
+                size: 4,
+                index: vec![
+                    DataLookupItem {
+                        app_id: AppId(APP_ID),
+                        start: 0,
+                    },
+                    DataLookupItem {
+                        app_id: AppId(OTHER_APP_ID),
+                        start: 3,
+                    },
+                ],
+            },
+            commitment: KateCommitment {
+                rows: 2,
+                cols: 2,
+                commitment: vec![],
+                data_root: data_root.clone(),
+            },
+        }),
+    };
+
+    let proofs = blobs
+        .iter()
+        .enumerate()
+        .map(|(index, data)| BlobInclusionProof {
+            data: data.clone(),
+            submissions: vec![AppId(APP_ID), AppId(OTHER_APP_ID)],
+            data_proof: DataProof {
+                data_root: data_root.clone(),
+                proof: vec![leaves[index ^ 1].clone()],
+                number_of_leaves: 2,
+                leaf_index: index as u32,
+                leaf: leaves[index].clone(),
+            },
+        })
+        .collect();
+
+    (avail_header, proofs)
+}
+
+fn rollup_proof(
+    prev_state_root: H256,
+    data: &Vec<u8>,
+) -> RollupProofWithPublicInputs<MockRollupProof> {
+    RollupProofWithPublicInputs {
+        proof: MockRollupProof { valid: true },
+        public_inputs: RollupPublicInputs {
+            prev_state_root,
+            post_state_root: H256::from([2u8; 32]),
+            height: 1,
+            blob_hash: keccak256(data),
+        },
+    }
+}
+
+fn prev_public_inputs(nexus_hash: H256) -> AdapterPublicInputs {
+    AdapterPublicInputs {
+        nexus_hash,
+        state_root: H256::from([1u8; 32]),
+        height: 0,
+        start_nexus_hash: nexus_hash,
+        app_id: AppAccountId::from(AppId(APP_ID)),
+        img_id: IMG_ID,
+        rollup_hash: Some(H256::zero()),
+    }
+}
+
+/// Inputs proving a block with `data` on top of `prev_public_inputs(parent_hash)`.
+fn recursion_inputs(
+    data: &Vec<u8>,
+    parent_hash: H256,
+) -> (
+    RollupProofWithPublicInputs<MockRollupProof>,
+    AdapterPublicInputs,
+    AdapterPrivateInputs,
+) {
+    let prev = prev_public_inputs(parent_hash);
+
+    (
+        rollup_proof(prev.state_root, data),
+        prev,
+        private_inputs(avail_header(2, Some(data)), parent_hash, Some(data)),
+    )
+}
+
+#[test]
+fn test_recursive_proof_accepted() {
+    let data = vec![1u8, 2, 3];
+    let (rollup_proof, prev, private_inputs) = recursion_inputs(&data, H256::from([9u8; 32]));
+    MockZKVM::prove(&prev);
+
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        Some(rollup_proof),
+        Some(prev),
+        private_inputs,
+        IMG_ID,
+        [0u8; 32],
+    );
+
+    assert_eq!(result.unwrap().state_root, H256::from([2u8; 32]));
+}
+
+#[test]
+fn test_unproven_previous_inputs_rejected() {
+    let data = vec![1u8, 2, 3];
+    //Not registered as proven, so recursion must fail.
+    let (rollup_proof, prev, private_inputs) = recursion_inputs(&data, H256::from([9u8; 32]));
+
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        Some(rollup_proof),
+        Some(prev),
+        private_inputs,
+        IMG_ID,
+        [0u8; 32],
+    );
+
+    assert_eq!(result.unwrap_err().to_string(), "Invalid proof");
+}
+
+#[test]
+fn test_broken_chain_rejected() {
+    let data = vec![1u8, 2, 3];
+    let prev = prev_public_inputs(H256::from([9u8; 32]));
+    MockZKVM::prove(&prev);
+
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        Some(rollup_proof(prev.state_root, &data)),
+        Some(prev),
+        private_inputs(
+            avail_header(2, Some(&data)),
+            H256::from([8u8; 32]),
+            Some(&data),
+        ),
+        IMG_ID,
+        [0u8; 32],
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_empty_block_with_unproven_previous_inputs_rejected() {
+    let parent_hash = H256::from([9u8; 32]);
+    let prev = prev_public_inputs(parent_hash);
+
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        None,
+        Some(prev.clone()),
+        private_inputs(avail_header(2, None), parent_hash, None),
+        IMG_ID,
+        [0u8; 32],
+    );
+    assert_eq!(result.unwrap_err().to_string(), "Invalid proof");
+
+    MockZKVM::prove(&prev);
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        None,
+        Some(prev.clone()),
+        private_inputs(avail_header(2, None), parent_hash, None),
+        IMG_ID,
+        [0u8; 32],
+    );
+    assert_eq!(result.unwrap().state_root, prev.state_root);
+}
+
+#[test]
+fn test_blob_inclusion_in_multi_app_block() {
+    let blobs = [vec![1u8], vec![2u8], vec![3u8], vec![4u8]];
+    let (avail_header, proofs) = multi_app_block(2, &blobs);
+
+    for index in 0..2 {
+        let blob_hash = verify_blob_inclusion(&proofs[index], &avail_header, &AppId(APP_ID));
+        assert_eq!(blob_hash.unwrap(), keccak256(&blobs[index]));
+    }
+    for index in 2..4 {
+        let blob_hash = verify_blob_inclusion(&proofs[index], &avail_header, &AppId(OTHER_APP_ID));
+        assert_eq!(blob_hash.unwrap(), keccak256(&blobs[index]));
+    }
+}
+
+#[test]
+fn test_blob_spanning_several_chunks() {
+    let blobs = [vec![1u8; 70], vec![2u8]];
+    let (avail_header, proofs) = multi_chunk_block(2, &blobs);
+
+    //Leaves count submissions, not chunks, so the blob of the other app is the second
+    //leaf although its chunk is the last one of the matrix.
+    let blob_hash = verify_blob_inclusion(&proofs[0], &avail_header, &AppId(APP_ID));
+    assert_eq!(blob_hash.unwrap(), keccak256(&blobs[0]));
+    let blob_hash = verify_blob_inclusion(&proofs[1], &avail_header, &AppId(OTHER_APP_ID));
+    assert_eq!(blob_hash.unwrap(), keccak256(&blobs[1]));
+
+    let result = verify_blob_inclusion(&proofs[1], &avail_header, &AppId(APP_ID));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Proven leaf not submitted under the app id"
+    );
+}
+
+#[test]
+fn test_blob_of_other_app_rejected() {
+    let blobs = [vec![1u8], vec![2u8], vec![3u8], vec![4u8]];
+    let (avail_header, proofs) = multi_app_block(2, &blobs);
+    let app_id = AppId(APP_ID);
+
+    let result = verify_blob_inclusion(&proofs[2], &avail_header, &app_id);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Proven leaf not submitted under the app id"
+    );
+
+    //Moving the proven leaf to a submission of the app breaks the proof, as its index
+    //is bound by the data root.
+    let mut blob = proofs[2].clone();
+    blob.data_proof.leaf_index = 1;
+    let result = verify_blob_inclusion(&blob, &avail_header, &app_id);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Invalid data inclusion proof"
+    );
+
+    //Relabelling the submissions of the other app leaves its chunks unaccounted for.
+    let mut blob = proofs[2].clone();
+    blob.submissions[2] = app_id.clone();
+    blob.submissions[3] = app_id.clone();
+    let result = verify_blob_inclusion(&blob, &avail_header, &app_id);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Data submissions do not match the data lookup"
+    );
+
+    //Nor can the submissions be of an app without data in the block.
+    let mut blob = proofs[2].clone();
+    blob.submissions[2] = AppId(3);
+    let result = verify_blob_inclusion(&blob, &avail_header, &AppId(3));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Data submissions do not match the data lookup"
+    );
+
+    let mut blob = proofs[0].clone();
+    blob.submissions.pop();
+    let result = verify_blob_inclusion(&blob, &avail_header, &app_id);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Data proof does not cover the data submissions"
+    );
+
+    //A rollup proof over the blob of another app cannot go through the adapter.
+    let parent_hash = H256::from([9u8; 32]);
+    let prev = prev_public_inputs(parent_hash);
+    MockZKVM::prove(&prev);
+    let mut inputs = private_inputs(avail_header, parent_hash, None);
+    inputs.blob = Some(proofs[2].clone());
+
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        Some(rollup_proof(prev.state_root, &blobs[2])),
+        Some(prev),
+        inputs,
+        IMG_ID,
+        [0u8; 32],
+    );
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "native")]
+fn queue_item(number: u32, data: &Vec<u8>) -> QueueItem<MockRollupProof> {
+    let blob = private_inputs(avail_header(number, Some(data)), H256::zero(), Some(data)).blob;
+
+    QueueItem {
+        proof: None,
+        blob: blob.map(|blob| (keccak256(data), blob)),
+        header: avail_header(number, Some(data)),
+    }
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn test_add_proof_to_queue_checks_chain() {
+    let first = vec![1u8];
+    let second = vec![2u8];
+    let mut queue = VecDeque::from(vec![queue_item(1, &first), queue_item(2, &second)]);
+    let last_state_root = H256::from([1u8; 32]);
+
+    //Earlier blob must be proven first.
+    let result = add_proof_to_queue(
+        &mut queue,
+        rollup_proof(H256::from([2u8; 32]), &second),
+        &[0u8; 32],
+        last_state_root.clone(),
+    );
+    assert!(result.is_err());
+
+    let result = add_proof_to_queue(
+        &mut queue,
+        rollup_proof(H256::zero(), &first),
+        &[0u8; 32],
+        last_state_root.clone(),
+    );
+    assert!(result.is_err());
+
+    add_proof_to_queue(
+        &mut queue,
+        rollup_proof(last_state_root.clone(), &first),
+        &[0u8; 32],
+        last_state_root.clone(),
+    )
+    .unwrap();
+    add_proof_to_queue(
+        &mut queue,
+        rollup_proof(H256::from([2u8; 32]), &second),
+        &[0u8; 32],
+        last_state_root,
+    )
+    .unwrap();
+
+    assert!(queue.iter().all(|i| i.proof.is_some()));
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn test_add_proof_to_queue_rejects_invalid_proof() {
+    let data = vec![1u8];
+    let mut queue = VecDeque::from(vec![queue_item(1, &data)]);
+    let mut proof = rollup_proof(H256::zero(), &data);
+    proof.proof.valid = false;
+
+    let result = add_proof_to_queue(&mut queue, proof, &[0u8; 32], H256::zero());
+
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .starts_with("Rollup proof verification failed"));
+    assert!(queue[0].proof.is_none());
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn test_proving_backoff_is_capped() {
+    use std::time::Duration;
+
+    assert_eq!(proving_backoff(1), Duration::from_secs(2));
+    assert_eq!(proving_backoff(4), Duration::from_secs(16));
+    //Keeps growing past quarantines, up to the cap.
+    assert_eq!(proving_backoff(8), Duration::from_secs(256));
+    assert_eq!(proving_backoff(9), Duration::from_secs(300));
+    assert_eq!(proving_backoff(u32::MAX), Duration::from_secs(300));
+}
+
+/// Adapter proof stored by the API tests, never verified.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MockAdapterProof(AdapterPublicInputs);
+
+#[cfg(feature = "native")]
+impl nexus_core::zkvm::traits::ZKVMProof for MockAdapterProof {
+    fn verify(
+        &self,
+        _: Option<[u8; 32]>,
+        _: Option<Vec<u8>>,
+        _: nexus_core::zkvm::ProverMode,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn public_inputs<V: Serialize + DeserializeOwned + Clone>(&mut self) -> Result<V, Error> {
+        Ok(serde_json::from_value(serde_json::to_value(&self.0)?)?)
+    }
+
+    fn compress(&mut self) -> Result<Self, Error> {
+        Ok(self.clone())
+    }
+
+    fn wrap_groth16(&mut self) -> Result<Self, Error> {
+        Ok(self.clone())
+    }
+
+    fn evm_seal(&self) -> Result<Vec<u8>, Error> {
+        Err(anyhow!("No seal for mock proofs"))
+    }
+}
+
+/// Handle of an adapter without blob submission, storing to a temporary database.
+#[cfg(feature = "native")]
+fn adapter_handle(
+    name: &str,
+) -> (
+    crate::state::AdapterHandle<MockRollupProof, MockAdapterProof>,
+    std::path::PathBuf,
+) {
+    use crate::db::DB;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    let db_path =
+        std::env::temp_dir().join(format!("adapter_sdk_test_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&db_path);
+
+    let handle = crate::state::AdapterHandle {
+        app_id: AppId(APP_ID),
+        queue: Arc::new(Mutex::new(VecDeque::new())),
+        db: Arc::new(Mutex::new(DB::from_path(db_path.to_str().unwrap()))),
+        vk: [0u8; 32],
+        submissions: Arc::new(Mutex::new(HashMap::new())),
+        blob_submitter: None,
+    };
+
+    (handle, db_path)
+}
+
+#[cfg(feature = "native")]
+#[tokio::test]
+async fn test_adapter_api() {
+    use crate::service::{routes, StoredAdapterProof};
+    use crate::types::{AdapterStatus, ProofStatus, QueueItemSummary};
+    use warp::Filter;
+
+    let (handle, db_path) = adapter_handle("api");
+    let first = vec![1u8];
+    let second = vec![2u8];
+    handle
+        .queue
+        .lock()
+        .await
+        .extend(vec![queue_item(1, &first), queue_item(2, &second)]);
+    let api = routes(warp::any().map({
+        let handle = handle.clone();
+        move || handle.clone()
+    }));
+    let get = |path: &str| warp::test::request().method("GET").path(path).reply(&api);
+
+    let response = get("/queue").await;
+    assert_eq!(response.status(), 200);
+    let queue: Vec<QueueItemSummary> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue[0].blob_hash, Some(keccak256(&first)));
+    assert!(!queue[0].has_proof);
+
+    let proof_status = |height: u32| async move {
+        let response = get(&format!("/proof_status?height={}", height)).await;
+        serde_json::from_slice::<ProofStatus>(response.body()).unwrap()
+    };
+    assert_eq!(proof_status(1).await, ProofStatus::AwaitingRollupProof);
+    assert_eq!(proof_status(7).await, ProofStatus::Unknown);
+    assert_eq!(get("/proof_status").await.status(), 400);
+
+    //Proofs are checked before being queued, and the reason returned.
+    let response = warp::test::request()
+        .method("POST")
+        .path("/proof")
+        .json(&rollup_proof(H256::from([1u8; 32]), &first))
+        .reply(&api)
+        .await;
+    assert_eq!(response.status(), 400);
+    assert!(String::from_utf8_lossy(response.body()).contains("Not sequential proof"));
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/proof")
+        .json(&rollup_proof(H256::zero(), &first))
+        .reply(&api)
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(proof_status(1).await, ProofStatus::Proving);
+
+    //Proving the first block, as the adapter does once the proof is generated.
+    let public_inputs = AdapterPublicInputs {
+        height: 1,
+        ..prev_public_inputs(H256::from([9u8; 32]))
+    };
+    let last_proof = (MockAdapterProof(public_inputs.clone()), public_inputs, 1);
+    {
+        handle.queue.lock().await.pop_front();
+        let db = handle.db.lock().await;
+        db.store_last_proof(&last_proof).unwrap();
+        db.store_adapter_proof(&last_proof).unwrap();
+    }
+
+    let response = get("/status").await;
+    let status: AdapterStatus = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(status.last_proven_height, Some(1));
+    assert_eq!(status.state_root, Some(H256::from([1u8; 32])));
+    assert_eq!(status.queue_length, 1);
+    assert_eq!(proof_status(1).await, ProofStatus::Proven);
+    assert_eq!(proof_status(2).await, ProofStatus::AwaitingRollupProof);
+
+    let response = get("/adapter_proof?height=1").await;
+    assert_eq!(response.status(), 200);
+    let stored: StoredAdapterProof<MockAdapterProof> =
+        serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(stored.height, 1);
+    assert_eq!(stored.public_inputs.height, 1);
+    assert_eq!(get("/adapter_proof?height=2").await.status(), 404);
+
+    let _ = std::fs::remove_dir_all(&db_path);
+}
+
+/// App data of a block in which the app posted the blobs at the first two positions.
+#[cfg(feature = "native")]
+fn app_block_data(
+    avail_header: &AvailHeader,
+    proofs: &[BlobInclusionProof],
+) -> Vec<relayer::types::AppBlockData> {
+    use relayer::types::{AppBlockData, AppExtrinsicWithProof};
+
+    let Extension::V3(extension) = &avail_header.extension;
+
+    vec![AppBlockData {
+        header_hash: avail_header.hash(),
+        app_id: AppId(APP_ID),
+        lookup_range: extension.app_lookup.range_of(&AppId(APP_ID)),
+        extrinsics: proofs[..2]
+            .iter()
+            .enumerate()
+            .map(|(index, proof)| AppExtrinsicWithProof {
+                index: index as u32 + 1,
+                data: proof.data.clone(),
+                data_proof: proof.data_proof.clone(),
+            })
+            .collect(),
+        submissions: proofs[0].submissions.clone(),
+    }]
+}
+
+#[cfg(feature = "native")]
+#[tokio::test]
+async fn test_push_header_queues_first_blob_of_app() {
+    let blobs = [vec![1u8], vec![2u8], vec![3u8], vec![4u8]];
+    let (avail_header, proofs) = multi_app_block(2, &blobs);
+    let (handle, db_path) = adapter_handle("push_header");
+
+    handle
+        .push_header(avail_header.clone(), app_block_data(&avail_header, &proofs))
+        .await
+        .unwrap();
+
+    //Nothing submitted through the adapter, so the first blob of the app is queued.
+    let queue = handle.queue.lock().await;
+    let (blob_hash, blob) = queue[0].blob.clone().unwrap();
+    assert_eq!(blob_hash, keccak256(&blobs[0]));
+    assert_eq!(blob.submissions, proofs[0].submissions);
+    assert_eq!(
+        verify_blob_inclusion(&blob, &avail_header, &AppId(APP_ID)).unwrap(),
+        blob_hash
+    );
+
+    let _ = std::fs::remove_dir_all(&db_path);
+}
+
+#[cfg(feature = "native")]
+#[tokio::test]
+async fn test_push_header_proves_second_blob_of_block() {
+    use crate::types::BlobStatus;
+
+    let blobs = [vec![1u8], vec![2u8], vec![3u8], vec![4u8]];
+    let (avail_header, proofs) = multi_app_block(2, &blobs);
+    let (handle, db_path) = adapter_handle("second_blob");
+    handle
+        .submissions
+        .lock()
+        .await
+        .insert(keccak256(&blobs[1]), BlobStatus::Submitted);
+
+    handle
+        .push_header(avail_header.clone(), app_block_data(&avail_header, &proofs))
+        .await
+        .unwrap();
+
+    //The blob submitted by the adapter is queued, with the proof of its own leaf.
+    let (blob_hash, blob) = handle.queue.lock().await[0].blob.clone().unwrap();
+    assert_eq!(blob_hash, keccak256(&blobs[1]));
+    assert_eq!(blob.data_proof.leaf_index, 1);
+    assert_eq!(
+        handle.submissions.lock().await.get(&blob_hash),
+        Some(&BlobStatus::Queued { height: 2 })
+    );
+
+    let parent_hash = H256::from([9u8; 32]);
+    let prev = prev_public_inputs(parent_hash);
+    MockZKVM::prove(&prev);
+    let mut inputs = private_inputs(avail_header, parent_hash, None);
+    inputs.blob = Some(blob);
+
+    let result = verify_proof::<MockRollupProof, MockZKVM>(
+        Some(rollup_proof(prev.state_root, &blobs[1])),
+        Some(prev),
+        inputs,
+        IMG_ID,
+        [0u8; 32],
+    );
+    assert_eq!(result.unwrap().state_root, H256::from([2u8; 32]));
+
+    let _ = std::fs::remove_dir_all(&db_path);
+}
+
+#[cfg(feature = "native-mock")]
+const ADAPTER_MOCK_ELF: &[u8] = b"adapter-mock";
+
+/// Adapter guest, run in-process by the mock prover.
+#[cfg(feature = "native-mock")]
+fn adapter_program() {
+    use nexus_core::zkvm::mock::MockZKVM as Env;
+
+    let rollup_proof = Env::read_input().unwrap();
+    let prev = Env::read_input().unwrap();
+    let private_inputs = Env::read_input().unwrap();
+    let result =
+        verify_proof::<MockRollupProof, Env>(rollup_proof, prev, private_inputs, IMG_ID, [0u8; 32])
+            .unwrap();
+
+    Env::commit(&result);
+}
+
+#[cfg(feature = "native-mock")]
+#[test]
+fn test_mock_prover_recursion() {
+    use nexus_core::zkvm::{
+        mock::{mock_img_id, register_program, MockProof, MockProver},
+        traits::{ZKVMProof, ZKVMProver},
+        ProverMode,
+    };
+
+    register_program(ADAPTER_MOCK_ELF, adapter_program);
+    let data = vec![1u8, 2, 3];
+    let (rollup_proof, prev, private_inputs) = recursion_inputs(&data, H256::from([9u8; 32]));
+    let prover = |with_recursion: bool| {
+        let mut prover = MockProver::new(ADAPTER_MOCK_ELF.to_vec(), ProverMode::MockProof);
+        prover.add_input(&Some(rollup_proof.clone())).unwrap();
+        prover.add_input(&Some(prev.clone())).unwrap();
+        prover.add_input(&private_inputs).unwrap();
+
+        if with_recursion {
+            prover
+                .add_proof_for_recursion(MockProof {
+                    img_id: IMG_ID,
+                    journal: vec![bincode::serialize(&prev).unwrap()],
+                })
+                .unwrap();
+        }
+
+        prover
+    };
+
+    assert!(prover(false).prove().is_err());
+
+    let mut proof = prover(true).prove().unwrap();
+    proof
+        .verify(None, Some(ADAPTER_MOCK_ELF.to_vec()), ProverMode::MockProof)
+        .unwrap();
+    assert!(proof
+        .verify(Some(IMG_ID.to_le_bytes()), None, ProverMode::MockProof)
+        .is_err());
+    assert_eq!(proof.img_id, mock_img_id(ADAPTER_MOCK_ELF));
+
+    let public_inputs: AdapterPublicInputs = proof.public_inputs().unwrap();
+    assert_eq!(public_inputs.state_root, H256::from([2u8; 32]));
+}
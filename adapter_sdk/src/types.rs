@@ -1,16 +1,30 @@
 use crate::traits::RollupProof;
 pub use nexus_core::types::NexusRollupPI as AdapterPublicInputs;
 use nexus_core::{
-    types::{AppId, AvailHeader, NexusHeader, StatementDigest, H256},
-    zkvm::ProverMode,
+    types::{AppId, AvailHeader, DataProof, NexusHeader, StatementDigest, H256},
+    zkvm::{ProverMode, ZKVMBackend},
 };
 use serde::{Deserialize, Serialize};
 
+pub const DEFAULT_NEXUS_URL: &str = "http://127.0.0.1:7000";
+pub const DEFAULT_API_ADDR: &str = "127.0.0.1:3031";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdapterPrivateInputs {
     pub nexus_header: NexusHeader,
     pub avail_header: AvailHeader,
     pub app_id: AppId,
+    pub blob: Option<BlobInclusionProof>,
+}
+
+/// Rollup blob posted on Avail, along with the proof of it being included in the
+/// data root of the header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobInclusionProof {
+    pub data: Vec<u8>,
+    /// App id of every data submission of the block, in the order of the data tree leaves.
+    pub submissions: Vec<AppId>,
+    pub data_proof: DataProof,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,5 +49,92 @@ pub struct AdapterConfig {
     pub vk: [u8; 32],
     pub rollup_start_height: u32,
     pub prover_mode: ProverMode,
+    /// zkVM the adapter proves with, `elf` and `adapter_elf_id` must be built for it.
+    /// Defaults to the first backend compiled in.
+    #[serde(default)]
+    pub proving_system: Option<ZKVMBackend>,
     pub avail_url: String,
+    #[serde(default = "default_nexus_url")]
+    pub nexus_url: String,
+    /// Key signing the Nexus transactions of the adapter, registered as account owner.
+    pub signing_key: SigningKeySource,
+    /// Nexus hash the account is registered from. Defaults to the Nexus header of
+    /// `rollup_start_height`.
+    #[serde(default)]
+    pub start_nexus_hash: Option<H256>,
+    /// Address the adapter HTTP API binds to.
+    #[serde(default = "default_api_addr")]
+    pub api_addr: String,
+    /// Key used to submit blobs to Avail, as a secret URI or hex seed of an sr25519 key.
+    #[serde(default)]
+    pub avail_signing_key: Option<SigningKeySource>,
+}
+
+/// Shared settings of a host running several adapters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiAdapterConfig {
+    pub avail_url: String,
+    #[serde(default = "default_nexus_url")]
+    pub nexus_url: String,
+    #[serde(default = "default_api_addr")]
+    pub api_addr: String,
+}
+
+/// Where the adapter loads the ed25519 key used to sign its Nexus transactions from.
+/// Keys are 32 byte seeds in hex.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SigningKeySource {
+    /// Path to a file containing the seed.
+    Keystore(String),
+    /// Name of the environment variable holding the seed.
+    Env(String),
+    /// Seed given inline, only meant for development.
+    DevSeed(String),
+}
+
+fn default_nexus_url() -> String {
+    String::from(DEFAULT_NEXUS_URL)
+}
+
+fn default_api_addr() -> String {
+    String::from(DEFAULT_API_ADDR)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueItemSummary {
+    pub height: u32,
+    pub blob_hash: Option<H256>,
+    pub has_proof: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterStatus {
+    pub last_proven_height: Option<u32>,
+    pub state_root: Option<H256>,
+    pub nexus_hash: Option<H256>,
+    pub queue_length: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProofStatus {
+    Proven,
+    /// In the queue, with everything required to generate the adapter proof.
+    Proving,
+    AwaitingRollupProof,
+    /// Rollup proof failed to prove and was removed, a new proof is awaited.
+    Quarantined,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BlobStatus {
+    Submitted,
+    Finalized {
+        block_hash: H256,
+    },
+    /// Picked up from the finalized block, and waiting for a rollup proof.
+    Queued {
+        height: u32,
+    },
+    Failed(String),
 }
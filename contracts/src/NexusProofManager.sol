@@ -2,9 +2,16 @@
 pragma solidity ^0.8.21;
 
 import {JellyfishMerkleTreeVerifier} from "./lib/JellyfishMerkleTreeVerifier.sol";
+import {INexusBlockVerifier} from "./interfaces/INexusBlockVerifier.sol";
 
 contract NexusProofManager {
     uint256 public latestNexusBlockNumber = 0;
+    // parent hash, previous state root, state root, tx root, avail header hash, number
+    // and runtime version
+    uint256 constant NEXUS_HEADER_LENGTH = 168;
+
+    address public immutable owner;
+    INexusBlockVerifier public nexusBlockVerifier;
 
     struct NexusBlock {
         bytes32 stateRoot;
@@ -18,6 +25,9 @@ contract NexusProofManager {
     error AlreadyUpdatedBlock(uint256 blockNumber);
     error InvalidBlockNumber(uint256 blockNumber, uint256 latestBlockNumber);
     error NexusLeafInclusionCheckFailed();
+    error Unauthorized();
+    error ProofRequired();
+    error InvalidNexusHeader();
 
     struct AccountState {
         bytes32 statementDigest;
@@ -25,25 +35,83 @@ contract NexusProofManager {
         bytes32 startNexusHash;
         uint128 lastProofHeight;
         uint128 height;
+        // index of the zkVM backend, 0 for risc0 and 1 for sp1
+        uint8 provingSystem;
+        // ed25519 key of the account owner, zero if the account has none
+        bytes32 owner;
+    }
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    // once set, nexus blocks can only be updated along with a proof
+    function setNexusBlockVerifier(INexusBlockVerifier verifier) external {
+        if (msg.sender != owner) {
+            revert Unauthorized();
+        }
+        nexusBlockVerifier = verifier;
     }
 
     // nexus state root
-    // updated when we verify the zk proof and then st block updated
+    // trusted update, only allowed until a block verifier is set
     function updateNexusBlock(
         uint256 blockNumber,
         NexusBlock calldata nexusBlockInfo
     ) external {
+        if (address(nexusBlockVerifier) != address(0)) {
+            revert ProofRequired();
+        }
+        _updateNexusBlock(blockNumber, nexusBlockInfo);
+    }
+
+    // nexus state root
+    // updated when we verify the zk proof of the encoded header and then st block updated
+    function updateNexusBlock(
+        uint256 blockNumber,
+        NexusBlock calldata nexusBlockInfo,
+        bytes calldata header,
+        bytes calldata proof
+    ) external {
+        if (address(nexusBlockVerifier) == address(0)) {
+            revert ProofRequired();
+        }
+        if (
+            header.length != NEXUS_HEADER_LENGTH ||
+            sha256(header) != nexusBlockInfo.blockHash ||
+            bytes32(header[64:96]) != nexusBlockInfo.stateRoot ||
+            headerNumber(header) != blockNumber
+        ) {
+            revert InvalidNexusHeader();
+        }
+        nexusBlockVerifier.verifyNexusBlock(header, proof);
+
+        _updateNexusBlock(blockNumber, nexusBlockInfo);
+    }
+
+    function _updateNexusBlock(
+        uint256 blockNumber,
+        NexusBlock calldata nexusBlockInfo
+    ) internal {
         if (nexusBlock[blockNumber].stateRoot != bytes32(0)) {
             revert AlreadyUpdatedBlock(blockNumber);
         }
         nexusBlock[blockNumber] = nexusBlockInfo;
-        // TODO: verify a zk proof from nexus
 
         if (blockNumber > latestNexusBlockNumber) {
             latestNexusBlockNumber = blockNumber;
         }
     }
 
+    // block number follows the hashes of the header, little endian
+    function headerNumber(
+        bytes calldata header
+    ) internal pure returns (uint256 number) {
+        for (uint256 i = 0; i < 4; i++) {
+            number |= uint256(uint8(header[160 + i])) << (8 * i);
+        }
+    }
+
     function updateChainState(
         uint256 nexusBlockNumber,
         bytes32[] calldata siblings,
@@ -56,7 +124,9 @@ contract NexusProofManager {
                 accountState.stateRoot,
                 accountState.startNexusHash,
                 accountState.lastProofHeight,
-                accountState.height
+                accountState.height,
+                accountState.provingSystem,
+                accountState.owner
             )
         );
         JellyfishMerkleTreeVerifier.Leaf
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.21;
+
+interface INexusBlockVerifier {
+    // header is the SCALE encoded nexus header committed by the nexus runtime
+    function verifyNexusBlock(
+        bytes calldata header,
+        bytes calldata proof
+    ) external view;
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.21;
+
+// image IDs of the nexus runtime versions, each proving blocks from its activation height on
+abstract contract NexusRuntimeSchedule {
+    struct Runtime {
+        uint32 version;
+        uint32 activationHeight;
+        bytes32 imageId;
+    }
+
+    address public immutable owner;
+    Runtime[] public runtimes;
+
+    error Unauthorized();
+    error InvalidRuntimeUpgrade();
+    error InactiveRuntime(uint32 version);
+
+    constructor(bytes32 imageId, uint32 version) {
+        owner = msg.sender;
+        runtimes.push(Runtime(version, 0, imageId));
+    }
+
+    // upgrades have to activate later and with a higher version than the last one
+    function scheduleRuntime(
+        uint32 version,
+        uint32 activationHeight,
+        bytes32 imageId
+    ) external {
+        if (msg.sender != owner) {
+            revert Unauthorized();
+        }
+        Runtime storage last = runtimes[runtimes.length - 1];
+        if (
+            version <= last.version ||
+            activationHeight <= last.activationHeight
+        ) {
+            revert InvalidRuntimeUpgrade();
+        }
+        runtimes.push(Runtime(version, activationHeight, imageId));
+    }
+
+    function activeRuntime(
+        uint256 blockNumber
+    ) public view returns (Runtime memory) {
+        uint256 i = runtimes.length - 1;
+        while (runtimes[i].activationHeight > blockNumber) {
+            i--;
+        }
+
+        return runtimes[i];
+    }
+
+    // image ID of the runtime active at the block, which has to be the one that produced it
+    function runtimeImageId(
+        bytes calldata header
+    ) internal view returns (bytes32) {
+        Runtime memory runtime = activeRuntime(readU32(header, 160));
+        uint32 version = readU32(header, 164);
+        if (version != runtime.version) {
+            revert InactiveRuntime(version);
+        }
+
+        return runtime.imageId;
+    }
+
+    // block number and runtime version are the last fields of the header, little endian
+    function readU32(
+        bytes calldata header,
+        uint256 offset
+    ) internal pure returns (uint32 value) {
+        for (uint256 i = 0; i < 4; i++) {
+            value |= uint32(uint8(header[offset + i])) << uint32(8 * i);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.21;
+
+import {INexusBlockVerifier} from "../../interfaces/INexusBlockVerifier.sol";
+import {NexusRuntimeSchedule} from "./NexusRuntimeSchedule.sol";
+
+interface IRiscZeroVerifier {
+    function verify(
+        bytes calldata seal,
+        bytes32 imageId,
+        bytes32 journalDigest
+    ) external view;
+}
+
+contract RiscZeroNexusBlockVerifier is
+    INexusBlockVerifier,
+    NexusRuntimeSchedule
+{
+    IRiscZeroVerifier public immutable verifier;
+
+    constructor(
+        IRiscZeroVerifier _verifier,
+        bytes32 _imageId,
+        uint32 _runtimeVersion
+    ) NexusRuntimeSchedule(_imageId, _runtimeVersion) {
+        verifier = _verifier;
+    }
+
+    function verifyNexusBlock(
+        bytes calldata header,
+        bytes calldata proof
+    ) external view {
+        verifier.verify(
+            proof,
+            runtimeImageId(header),
+            sha256(journal(header))
+        );
+    }
+
+    // risc0 serde writes every byte of the hashes as a u32 word, and the block number
+    // and runtime version as one each
+    function journal(bytes calldata header) public pure returns (bytes memory) {
+        bytes memory words = new bytes(648);
+        for (uint256 i = 0; i < 160; i++) {
+            words[i * 4] = header[i];
+        }
+        for (uint256 i = 0; i < 8; i++) {
+            words[640 + i] = header[160 + i];
+        }
+
+        return words;
+    }
+}
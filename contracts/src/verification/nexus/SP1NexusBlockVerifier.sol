@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.21;
+
+import {INexusBlockVerifier} from "../../interfaces/INexusBlockVerifier.sol";
+import {NexusRuntimeSchedule} from "./NexusRuntimeSchedule.sol";
+
+interface ISP1Verifier {
+    function verifyProof(
+        bytes32 programVKey,
+        bytes calldata publicValues,
+        bytes calldata proofBytes
+    ) external view;
+}
+
+contract SP1NexusBlockVerifier is
+    INexusBlockVerifier,
+    NexusRuntimeSchedule
+{
+    ISP1Verifier public immutable verifier;
+
+    constructor(
+        ISP1Verifier _verifier,
+        bytes32 _programVKey,
+        uint32 _runtimeVersion
+    ) NexusRuntimeSchedule(_programVKey, _runtimeVersion) {
+        verifier = _verifier;
+    }
+
+    // public values committed by the sp1 runtime are the encoded header itself,
+    // image IDs of the schedule are the program verifying keys
+    function verifyNexusBlock(
+        bytes calldata header,
+        bytes calldata proof
+    ) external view {
+        verifier.verifyProof(runtimeImageId(header), header, proof);
+    }
+}
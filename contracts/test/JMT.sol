@@ -28,8 +28,8 @@ contract EthereumVerifierTest is Test {
     function testEmptyProof() public {
         uint256 blockNumber = 123;
 
-        bytes32 stateRoot = 0x118eabaae552430cdecf445736d2e57c5dbcf70c1688f053e70f0c3a6a80411f;
-        bytes32 blockHash = 0x118eabaae552430cdecf445736d2e57c5dbcf70c1688f053e70f0c3a6a80411f;
+        bytes32 stateRoot = 0x48fd9a12bdf0b29bdc6f71026644f8810cc8640a1dd35eb8ccf491466a5f243e;
+        bytes32 blockHash = 0x48fd9a12bdf0b29bdc6f71026644f8810cc8640a1dd35eb8ccf491466a5f243e;
         bytes32 appid = 0x3655ca59b7d566ae06297c200f98d04da2e8e89812d627bc29297c25db60362d;
 
         proofManager.updateNexusBlock(
@@ -43,7 +43,9 @@ contract EthereumVerifierTest is Test {
                 0xd62c0e6039b3b76b0c70301de2dee44f1f8d1335e7df9bd26fc3bdb6f33a2574,
                 0x378f4888b185704cb8c8e86792838c2fed7f7d4bd58cd9e66b34050a9c42aad1,
                 570,
-                123
+                123,
+                0,
+                bytes32(0)
             );
 
         proofManager.updateChainState(blockNumber, siblings, appid, state);
@@ -51,8 +53,8 @@ contract EthereumVerifierTest is Test {
 
     function testNonEmptyProof() public {
         uint256 blockNumber = 16;
-        bytes32 stateRoot = 0x01eabe125b5f4f9ce2b9c3cc3c306fe789bd6f6ef28aa8d2fb2254e1be045e38;
-        bytes32 blockHash = 0x01eabe125b5f4f9ce2b9c3cc3c306fe789bd6f6ef28aa8d2fb2254e1be045e38;
+        bytes32 stateRoot = 0x1ab338bd197310b656a66a9885998e4eb84d6f26fb28b04390b7ae5543513d1d;
+        bytes32 blockHash = 0x1ab338bd197310b656a66a9885998e4eb84d6f26fb28b04390b7ae5543513d1d;
         bytes32 appid = 0xa40fb80ad4287819ecda5efac01c74c78d7cb00ca5f9eb5f6c0f19bd09936ac1;
 
         proofManager.updateNexusBlock(
@@ -70,7 +72,9 @@ contract EthereumVerifierTest is Test {
                 0x0000000000000000000000000000000000000000000000000000000000000000,
                 0x7d762e1332bba77a369bee1204580472039f972d2d445d7499e814ff485fe76f,
                 0,
-                0
+                0,
+                0,
+                bytes32(0)
             );
 
         proofManager.updateChainState(blockNumber, siblings, appid, state);
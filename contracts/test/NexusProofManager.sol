@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.21;
+
+import "forge-std/test.sol";
+import "../src/NexusProofManager.sol";
+import "../src/interfaces/INexusBlockVerifier.sol";
+
+contract AcceptingBlockVerifier is INexusBlockVerifier {
+    function verifyNexusBlock(bytes calldata, bytes calldata) external view {}
+}
+
+contract NexusProofManagerTest is Test {
+    NexusProofManager proofManager;
+
+    bytes32 stateRoot = bytes32(uint256(3));
+
+    function setUp() public {
+        proofManager = new NexusProofManager();
+        proofManager.setNexusBlockVerifier(new AcceptingBlockVerifier());
+    }
+
+    function header(uint32 number) internal view returns (bytes memory) {
+        return
+            abi.encodePacked(
+                bytes32(uint256(1)),
+                bytes32(uint256(2)),
+                stateRoot,
+                bytes32(uint256(4)),
+                bytes32(uint256(5)),
+                // little endian, as SCALE encodes it
+                bytes4(
+                    uint32(
+                        ((number & 0xff) << 24) |
+                            ((number & 0xff00) << 8) |
+                            ((number & 0xff0000) >> 8) |
+                            (number >> 24)
+                    )
+                ),
+                // runtime version 1
+                bytes4(0x01000000)
+            );
+    }
+
+    function testUpdateWithProof() public {
+        bytes memory encoded = header(16);
+
+        proofManager.updateNexusBlock(
+            16,
+            NexusProofManager.NexusBlock(stateRoot, sha256(encoded)),
+            encoded,
+            ""
+        );
+
+        (bytes32 storedRoot, ) = proofManager.nexusBlock(16);
+        assertEq(storedRoot, stateRoot);
+        assertEq(proofManager.latestNexusBlockNumber(), 16);
+    }
+
+    function testUpdateWithoutProofRejected() public {
+        vm.expectRevert(NexusProofManager.ProofRequired.selector);
+        proofManager.updateNexusBlock(
+            16,
+            NexusProofManager.NexusBlock(stateRoot, bytes32(0))
+        );
+    }
+
+    function testMismatchedHeaderRejected() public {
+        bytes memory encoded = header(16);
+
+        vm.expectRevert(NexusProofManager.InvalidNexusHeader.selector);
+        proofManager.updateNexusBlock(
+            17,
+            NexusProofManager.NexusBlock(stateRoot, sha256(encoded)),
+            encoded,
+            ""
+        );
+    }
+
+    function testOnlyOwnerSetsVerifier() public {
+        vm.prank(address(1));
+        vm.expectRevert(NexusProofManager.Unauthorized.selector);
+        proofManager.setNexusBlockVerifier(INexusBlockVerifier(address(0)));
+    }
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.21;
+
+import "forge-std/test.sol";
+import "../src/verification/nexus/NexusRuntimeSchedule.sol";
+import "../src/verification/nexus/SP1NexusBlockVerifier.sol";
+
+contract ExpectingSP1Verifier is ISP1Verifier {
+    bytes32 public immutable expectedVKey;
+
+    constructor(bytes32 _expectedVKey) {
+        expectedVKey = _expectedVKey;
+    }
+
+    function verifyProof(
+        bytes32 programVKey,
+        bytes calldata,
+        bytes calldata
+    ) external view {
+        require(programVKey == expectedVKey, "wrong program");
+    }
+}
+
+contract NexusRuntimeScheduleTest is Test {
+    bytes32 constant V1 = bytes32(uint256(1));
+    bytes32 constant V2 = bytes32(uint256(2));
+
+    function header(
+        uint8 number,
+        uint8 version
+    ) internal pure returns (bytes memory) {
+        return
+            abi.encodePacked(
+                new bytes(160),
+                // little endian, as SCALE encodes it
+                bytes4(uint32(number) << 24),
+                bytes4(uint32(version) << 24)
+            );
+    }
+
+    function upgradedVerifier(
+        bytes32 expectedVKey
+    ) internal returns (SP1NexusBlockVerifier blockVerifier) {
+        blockVerifier = new SP1NexusBlockVerifier(
+            new ExpectingSP1Verifier(expectedVKey),
+            V1,
+            1
+        );
+        blockVerifier.scheduleRuntime(2, 10, V2);
+    }
+
+    function testBlocksVerifiedWithActiveRuntime() public {
+        upgradedVerifier(V1).verifyNexusBlock(header(9, 1), "");
+        upgradedVerifier(V2).verifyNexusBlock(header(10, 2), "");
+        upgradedVerifier(V2).verifyNexusBlock(header(11, 2), "");
+    }
+
+    function testInactiveRuntimeRejected() public {
+        SP1NexusBlockVerifier blockVerifier = upgradedVerifier(V1);
+
+        vm.expectRevert(
+            abi.encodeWithSelector(
+                NexusRuntimeSchedule.InactiveRuntime.selector,
+                1
+            )
+        );
+        blockVerifier.verifyNexusBlock(header(10, 1), "");
+
+        vm.expectRevert(
+            abi.encodeWithSelector(
+                NexusRuntimeSchedule.InactiveRuntime.selector,
+                2
+            )
+        );
+        blockVerifier.verifyNexusBlock(header(9, 2), "");
+    }
+
+    function testUpgradesMustMoveForward() public {
+        SP1NexusBlockVerifier blockVerifier = upgradedVerifier(V1);
+
+        vm.expectRevert(NexusRuntimeSchedule.InvalidRuntimeUpgrade.selector);
+        blockVerifier.scheduleRuntime(3, 10, V2);
+
+        vm.expectRevert(NexusRuntimeSchedule.InvalidRuntimeUpgrade.selector);
+        blockVerifier.scheduleRuntime(2, 20, V2);
+
+        vm.prank(address(1));
+        vm.expectRevert(NexusRuntimeSchedule.Unauthorized.selector);
+        blockVerifier.scheduleRuntime(3, 20, V2);
+    }
+}
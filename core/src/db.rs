@@ -27,6 +27,11 @@ impl BatchTransaction {
         debug!("Put operation added successfully");
         Ok(())
     }
+
+    #[instrument(level = "debug", skip(self), fields(key = ?hex::encode(serialized_key)))]
+    pub fn delete(&mut self, serialized_key: &[u8]) {
+        self.0.delete(serialized_key);
+    }
 }
 
 impl NodeDB {
@@ -0,0 +1,78 @@
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(any(feature = "native"))]
+use utoipa::ToSchema;
+
+/// Reasons for which a transaction can fail during state transition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NexusTxError {
+    AccountNotInitiated,
+    AccountAlreadyInitiated,
+    IncorrectAppAccountId,
+    InvalidStartNexusHash,
+    IncorrectHeaderList,
+    NotRightFork,
+    StatementMismatch,
+    InvalidProof,
+    InvalidSignature,
+    NotAccountOwner,
+    UnsupportedProvingSystem,
+}
+
+/// Failure reason persisted along with transaction results.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[cfg_attr(feature = "native", derive(ToSchema))]
+pub struct TxError {
+    pub code: u32,
+    pub message: String,
+}
+
+impl NexusTxError {
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::AccountNotInitiated => 1,
+            Self::AccountAlreadyInitiated => 2,
+            Self::IncorrectAppAccountId => 3,
+            Self::InvalidStartNexusHash => 4,
+            Self::IncorrectHeaderList => 5,
+            Self::NotRightFork => 6,
+            Self::StatementMismatch => 7,
+            Self::InvalidProof => 8,
+            Self::InvalidSignature => 9,
+            Self::NotAccountOwner => 10,
+            Self::UnsupportedProvingSystem => 11,
+        }
+    }
+}
+
+impl fmt::Display for NexusTxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::AccountNotInitiated => "Invalid transaction, account not initiated.",
+            Self::AccountAlreadyInitiated => "Account already initiated.",
+            Self::IncorrectAppAccountId => "Incorrect app account id",
+            Self::InvalidStartNexusHash => "Not a recursive proof from registered start hash.",
+            Self::IncorrectHeaderList => "Incorrect header list given by sequencer.",
+            Self::NotRightFork => "Not right fork, or against last 32 blocks",
+            Self::StatementMismatch => "The same img_id not used for recursion",
+            Self::InvalidProof => "Invalid proof",
+            Self::InvalidSignature => "Invalid transaction signature",
+            Self::NotAccountOwner => "Transaction not signed by the owner of the account",
+            Self::UnsupportedProvingSystem => "Proving system not supported by the Nexus runtime",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for NexusTxError {}
+
+impl From<&NexusTxError> for TxError {
+    fn from(value: &NexusTxError) -> Self {
+        Self {
+            code: value.code(),
+            message: value.to_string(),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::state::types::{AccountState, StatementDigest};
+use crate::types::{AppAccountId, NexusHeader, H256};
+use crate::utils::hasher::{Digest, ShaHasher};
+use crate::zkvm::ZKVMBackend;
+use anyhow::{anyhow, Error};
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// App account registered at genesis, as if initiated through an `InitAccount` signed by `owner`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Encode, Decode)]
+pub struct GenesisAccount {
+    pub app_id: AppAccountId,
+    pub statement: StatementDigest,
+    pub start_nexus_hash: H256,
+    pub proving_system: ZKVMBackend,
+    #[serde(default)]
+    pub owner: Option<H256>,
+}
+
+impl GenesisAccount {
+    pub fn account_state(&self) -> AccountState {
+        let mut account = AccountState::zero();
+
+        account.statement = self.statement.clone();
+        account.start_nexus_hash = self.start_nexus_hash.as_fixed_slice().clone();
+        account.proving_system = self.proving_system;
+        if let Some(owner) = &self.owner {
+            account.owner = owner.as_fixed_slice().clone();
+        }
+
+        account
+    }
+}
+
+/// Initial state of a Nexus chain, committed as block 0.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Genesis {
+    pub network_id: String,
+    /// Avail block the chain starts at, block 1 being built on the Avail block after it.
+    pub start_avail_block: u32,
+    pub start_avail_hash: H256,
+    pub runtime_version: u32,
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+}
+
+impl Genesis {
+    /// Reads and validates a JSON genesis file.
+    pub fn from_file(path: &str) -> Result<Self, Error> {
+        let file = std::fs::read_to_string(path)?;
+        let genesis: Self = serde_json::from_str(&file)?;
+
+        genesis.validate()?;
+
+        Ok(genesis)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.network_id.is_empty() {
+            return Err(anyhow!("Genesis has no network ID"));
+        }
+        if self.runtime_version == 0 {
+            return Err(anyhow!("Genesis runtime version has to be at least 1"));
+        }
+
+        let mut app_ids: HashSet<[u8; 32]> = HashSet::new();
+        for account in self.accounts.iter() {
+            //A zero account is the same as no account in the state tree.
+            if account.account_state() == AccountState::zero() {
+                return Err(anyhow!("Genesis account {} is empty", account.app_id));
+            }
+            if !app_ids.insert(account.app_id.0.clone()) {
+                return Err(anyhow!(
+                    "Genesis account {} registered more than once",
+                    account.app_id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// State tree entries of the genesis accounts.
+    pub fn account_states(&self) -> HashMap<H256, Option<AccountState>> {
+        self.accounts
+            .iter()
+            .map(|account| (account.app_id.as_h256(), Some(account.account_state())))
+            .collect()
+    }
+
+    /// Parent hash of block 0, so chains of different networks never share a block.
+    pub fn network_hash(&self) -> H256 {
+        let mut hasher = ShaHasher::new();
+        hasher.0.update(self.network_id.as_bytes());
+
+        hasher.finish()
+    }
+
+    /// Commitment to the genesis accounts, owners included, used as tx root of block 0.
+    pub fn accounts_root(&self) -> H256 {
+        let mut hasher = ShaHasher::new();
+        hasher.0.update(self.accounts.encode());
+
+        hasher.finish()
+    }
+
+    /// Block 0 of the chain, given the root of the state tree holding the genesis accounts.
+    pub fn header(&self, state_root: H256) -> NexusHeader {
+        NexusHeader {
+            parent_hash: self.network_hash(),
+            prev_state_root: H256::zero(),
+            state_root,
+            tx_root: self.accounts_root(),
+            avail_header_hash: self.start_avail_hash.clone(),
+            number: 0,
+            runtime_version: self.runtime_version,
+        }
+    }
+
+    /// Checks a stored block 0 was committed from this genesis, leaving out its state root.
+    pub fn check_header(&self, header: &NexusHeader) -> Result<(), Error> {
+        let expected = self.header(header.state_root.clone());
+
+        if header != &expected {
+            return Err(anyhow!(
+                "Block 0 {} was not committed from the genesis of network {}",
+                header.hash(),
+                self.network_id
+            ));
+        }
+
+        Ok(())
+    }
+}
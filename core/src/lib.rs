@@ -1,10 +1,14 @@
 #[cfg(any(feature = "native"))]
 pub mod db;
+pub mod errors;
+#[cfg(any(feature = "native"))]
+pub mod genesis;
 //mod new_stf;
 mod h256;
 #[cfg(any(feature = "native"))]
 pub mod mempool;
 pub mod prover;
+pub mod runtime;
 pub mod state;
 #[cfg(any(feature = "native"))]
 pub mod state_machine;
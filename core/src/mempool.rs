@@ -1,7 +1,8 @@
 use crate::{
     db::NodeDB,
     traits::NexusTransaction,
-    types::{Transaction, TransactionStatus, TransactionWithStatus},
+    types::{AccountState, Transaction, TransactionStatus, TransactionWithStatus, TxParams},
+    zkvm::{foreign::foreign_receipt_kind, ZKVMBackend},
 };
 use anyhow::anyhow;
 use std::sync::Arc;
@@ -12,18 +13,65 @@ use tracing::{debug, error, event, info, instrument, span, warn, Level};
 pub struct Mempool {
     tx_list: Arc<Mutex<Vec<Transaction>>>,
     node_db: Arc<Mutex<NodeDB>>,
+    backend: ZKVMBackend,
 }
 
 impl Mempool {
     #[instrument(level = "debug", skip(node_db))]
-    pub fn new(node_db: Arc<Mutex<NodeDB>>) -> Self {
+    pub fn new(node_db: Arc<Mutex<NodeDB>>, backend: ZKVMBackend) -> Self {
         debug!("Creating new Mempool");
         Self {
             tx_list: Arc::new(Mutex::new(vec![])),
             node_db,
+            backend,
         }
     }
 
+    /// Checks the transaction can be admitted, given the latest state of the account it
+    /// targets. Submitted proofs must be of the backend used by the node, or of a receipt
+    /// of another backend the runtime verifies itself, and of the statement registered by
+    /// the account.
+    pub fn check_tx(
+        &self,
+        tx: &Transaction,
+        account: Option<&AccountState>,
+    ) -> Result<(), anyhow::Error> {
+        if let TxParams::SubmitProof(submit_proof) = &tx.params {
+            let envelope = submit_proof.proof.envelope()?;
+
+            if envelope.backend != self.backend
+                && foreign_receipt_kind(self.backend, envelope.backend) != Some(envelope.kind)
+            {
+                return Err(anyhow!(
+                    "{:?} receipts of {:?} proofs cannot be verified by the {:?} runtime",
+                    envelope.kind,
+                    envelope.backend,
+                    self.backend
+                ));
+            }
+
+            if let Some(account) = account {
+                if envelope.backend != account.proving_system {
+                    return Err(anyhow!(
+                        "Proof generated with {:?} backend, account proves with {:?}",
+                        envelope.backend,
+                        account.proving_system
+                    ));
+                }
+
+                if envelope.img_id != account.statement {
+                    return Err(anyhow!(
+                        "Proof generated for statement {:?}, account statement is {:?}",
+                        envelope.img_id,
+                        account.statement
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[instrument(level = "debug", skip(self))]
     pub async fn get_current_txs(&self) -> (Vec<Transaction>, Option<usize>) {
         debug!("Getting current transactions from mempool");
@@ -51,6 +99,7 @@ impl Mempool {
         }
     }
 
+    /// Adds the transaction to the pool, it must have passed `check_tx` first.
     #[instrument(level = "debug", skip(self))]
     pub async fn add_tx(&self, tx: Transaction) -> Result<(), anyhow::Error> {
         debug!("Adding transaction to mempool");
@@ -68,6 +117,7 @@ impl Mempool {
                         transaction: tx.clone(),
                         status: TransactionStatus::InPool,
                         block_hash: None,
+                        error: None,
                     },
                 );
                 let mut tx_list = self.tx_list.lock().await;
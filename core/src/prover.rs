@@ -6,9 +6,6 @@ use crate::zkvm::traits::ZKVMEnv;
 use crate::zkvm_state_machine::ZKVMStateMachine;
 
 pub fn run<Z: ZKVMEnv>() {
-    // let start = env::cycle_count();
-    // eprintln!("Start cycle {}", start);
-
     let txs: Vec<TransactionZKVM> = Z::read_input::<Vec<TransactionZKVM>>().unwrap();
     let touched_states: StateUpdate = Z::read_input::<StateUpdate>().unwrap();
     let header: AvailHeader = Z::read_input::<AvailHeader>().unwrap();
@@ -19,8 +16,5 @@ pub fn run<Z: ZKVMEnv>() {
         .execute_batch(&header, &mut header_store, &txs, touched_states)
         .expect("Should not have panicked.");
 
-    // let after_stf = env::cycle_count();
-    // eprintln!("after STF {}", after_stf);
-
     Z::commit(&zkvm_result);
 }
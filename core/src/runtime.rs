@@ -0,0 +1,204 @@
+#[cfg(any(feature = "native"))]
+use crate::types::{NexusHeader, StatementDigest};
+#[cfg(any(feature = "native"))]
+use crate::zkvm::ZKVMBackend;
+#[cfg(any(
+    feature = "native-risc0",
+    feature = "native-sp1",
+    feature = "native-mock"
+))]
+use crate::{
+    types::Proof,
+    zkvm::{any::AnyProof, traits::ZKVMProof, ProverMode},
+};
+#[cfg(any(feature = "native"))]
+use anyhow::{anyhow, Error};
+#[cfg(any(feature = "native"))]
+use serde::{Deserialize, Serialize};
+#[cfg(any(feature = "native"))]
+use std::path::PathBuf;
+
+/// Version of the state transition function compiled in, committed in every header it produces.
+/// Bumped with every change to the runtime, which is then activated through a [`RuntimeSchedule`].
+pub const NEXUS_RUNTIME_VERSION: u32 = 1;
+
+/// Runtime proving the blocks of a backend from `activation_height` on.
+#[cfg(any(feature = "native"))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeUpgrade {
+    pub version: u32,
+    pub activation_height: u32,
+    pub backend: ZKVMBackend,
+    pub img_id: StatementDigest,
+    /// Guest ELF of the runtime, only needed to prove blocks. Not set for the
+    /// runtime compiled into the node.
+    #[serde(default)]
+    pub elf: Option<PathBuf>,
+}
+
+/// Runtimes the chain was and will be proven with, so blocks are proven and
+/// verified with the runtime active at their height. Blocks are executed natively by
+/// the runtime compiled into the node though, see [`RuntimeUpgrade::check_native`].
+#[cfg(any(feature = "native"))]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeSchedule {
+    upgrades: Vec<RuntimeUpgrade>,
+}
+
+#[cfg(any(feature = "native"))]
+impl RuntimeUpgrade {
+    /// Checks the node can execute the blocks of this runtime. The STF executed natively
+    /// is the one compiled in, so a node only produces the blocks of its own version and
+    /// has to be swapped at every upgrade.
+    pub fn check_native(&self) -> Result<(), Error> {
+        if self.version != NEXUS_RUNTIME_VERSION {
+            return Err(anyhow!(
+                "Runtime version {} is active from height {}, the node executes version {}",
+                self.version,
+                self.activation_height,
+                NEXUS_RUNTIME_VERSION
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "native"))]
+impl RuntimeSchedule {
+    pub fn new(mut upgrades: Vec<RuntimeUpgrade>) -> Result<Self, Error> {
+        upgrades.sort_by_key(|upgrade| (upgrade.activation_height, upgrade.version));
+
+        let mut schedule = Self { upgrades: vec![] };
+        for upgrade in upgrades {
+            schedule.push(upgrade)?;
+        }
+
+        Ok(schedule)
+    }
+
+    /// Reads a JSON list of [`RuntimeUpgrade`].
+    pub fn from_file(path: &str) -> Result<Self, Error> {
+        let file = std::fs::read_to_string(path)?;
+
+        Self::new(serde_json::from_str(&file)?)
+    }
+
+    /// Schedules an upgrade, which has to activate later and with a higher version
+    /// than the last upgrade of its backend.
+    pub fn push(&mut self, upgrade: RuntimeUpgrade) -> Result<(), Error> {
+        if let Some(last) = self
+            .upgrades
+            .iter()
+            .rev()
+            .find(|i| i.backend == upgrade.backend)
+        {
+            if upgrade.activation_height <= last.activation_height
+                || upgrade.version <= last.version
+            {
+                return Err(anyhow!(
+                    "Runtime version {} at height {} does not follow version {} at height {}",
+                    upgrade.version,
+                    upgrade.activation_height,
+                    last.version,
+                    last.activation_height
+                ));
+            }
+        }
+
+        self.upgrades.push(upgrade);
+
+        Ok(())
+    }
+
+    pub fn upgrades(&self) -> &[RuntimeUpgrade] {
+        &self.upgrades
+    }
+
+    /// Runtime of the backend active at `height`.
+    pub fn active(&self, backend: ZKVMBackend, height: u32) -> Result<&RuntimeUpgrade, Error> {
+        match self
+            .upgrades
+            .iter()
+            .rev()
+            .find(|i| i.backend == backend && i.activation_height <= height)
+        {
+            Some(i) => Ok(i),
+            None => Err(anyhow!(
+                "No {:?} runtime scheduled at height {}",
+                backend,
+                height
+            )),
+        }
+    }
+
+    /// Checks a block was produced by the runtime active at its height.
+    pub fn check_block(
+        &self,
+        backend: ZKVMBackend,
+        header: &NexusHeader,
+        img_id: &StatementDigest,
+    ) -> Result<(), Error> {
+        let runtime = self.active(backend, header.number)?;
+
+        if header.runtime_version != runtime.version {
+            return Err(anyhow!(
+                "Block {} produced by runtime version {}, expected {}",
+                header.number,
+                header.runtime_version,
+                runtime.version
+            ));
+        }
+        if img_id != &runtime.img_id {
+            return Err(anyhow!(
+                "Block {} not proven with the image ID of runtime version {}",
+                header.number,
+                runtime.version
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the proof of a block is of a block produced by the runtime active at its
+    /// height, without verifying it, returning the header of the block.
+    #[cfg(any(
+        feature = "native-risc0",
+        feature = "native-sp1",
+        feature = "native-mock"
+    ))]
+    pub fn check_block_proof(&self, proof: &Proof) -> Result<NexusHeader, Error> {
+        let envelope = proof.envelope()?;
+        let header: NexusHeader = AnyProof::try_from(proof.clone())?.public_inputs()?;
+
+        self.check_block(envelope.backend, &header, &envelope.img_id)?;
+
+        Ok(header)
+    }
+
+    /// Verifies the proof of a block against the runtime active at its height,
+    /// returning the header of the block. The ELF of the runtime is only needed
+    /// for proofs not carrying their verifying key.
+    #[cfg(any(
+        feature = "native-risc0",
+        feature = "native-sp1",
+        feature = "native-mock"
+    ))]
+    pub fn verify_block_proof(
+        &self,
+        proof: &Proof,
+        elf: Option<Vec<u8>>,
+        prover_mode: ProverMode,
+    ) -> Result<NexusHeader, Error> {
+        let header = self.check_block_proof(proof)?;
+        let envelope = proof.envelope()?;
+
+        AnyProof::try_from(proof.clone())?.verify(
+            Some(envelope.img_id.to_le_bytes()),
+            elf,
+            prover_mode,
+        )?;
+
+        Ok(header)
+    }
+}
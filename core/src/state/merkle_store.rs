@@ -2,7 +2,7 @@ use crate::types::H256;
 use anyhow::{anyhow, Error};
 use jmt::storage::{LeafNode, Node, NodeBatch, NodeKey, TreeReader, TreeWriter};
 use jmt::{KeyHash, OwnedValue, Version};
-use rocksdb::DB;
+use rocksdb::{IteratorMode, DB};
 use rocksdb::{WriteBatch, WriteOptions};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{from_slice, to_vec};
@@ -140,6 +140,36 @@ impl MerkleStore {
         Ok(())
     }
 
+    /// Committed leaf values of the tree at `max_version`, sorted by key hash.
+    pub fn values(&self, max_version: Version) -> Result<Vec<(KeyHash, OwnedValue)>, Error> {
+        let db = match self.db.lock() {
+            Ok(i) => i,
+            Err(e) => return Err(anyhow!("No lock obtained.")),
+        };
+        let mut values = vec![];
+
+        for item in db.iterator(IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| anyhow!(e.to_string()))?;
+            //Leaf values are the only entries keyed by a bare key hash, see `write_node_batch`.
+            let key_hash: [u8; 32] = match key.as_ref().try_into() {
+                Ok(i) => i,
+                Err(_) => continue,
+            };
+            let versions: Vec<(Version, OwnedValue)> =
+                from_slice(&value).map_err(|e| anyhow!(e.to_string()))?;
+
+            if let Some((_, value)) = versions
+                .into_iter()
+                .filter(|(version, _)| version <= &max_version)
+                .max_by_key(|(version, _)| *version)
+            {
+                values.push((KeyHash(key_hash), value));
+            }
+        }
+
+        Ok(values)
+    }
+
     pub fn clear_cache(&mut self) -> Result<(), Error> {
         let mut cache = match self.cache.lock() {
             Ok(i) => i,
@@ -1,4 +1,5 @@
 use crate::utils::hasher::Sha256;
+use crate::zkvm::ZKVMBackend;
 use ethabi::{decode, encode, ParamType, Token};
 use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,12 @@ pub struct AccountState {
     pub start_nexus_hash: [u8; 32],
     pub last_proof_height: u32,
     pub height: u32,
+    /// zkVM the rollup proves with. Proofs of other backends than the one Nexus runs
+    /// on are verified by the runtime itself, rather than recursed.
+    pub proving_system: ZKVMBackend,
+    /// Ed25519 key that signed the `InitAccount` of the account, the only one allowed to
+    /// submit its proofs. Zero for accounts registered unsigned.
+    pub owner: [u8; 32],
 }
 
 impl AccountState {
@@ -28,9 +35,15 @@ impl AccountState {
             start_nexus_hash: [0; 32],
             last_proof_height: 0,
             height: 0,
+            proving_system: ZKVMBackend::Risc0,
+            owner: [0; 32],
         }
     }
 
+    pub fn has_owner(&self) -> bool {
+        self.owner != [0; 32]
+    }
+
     pub fn encode(&self) -> Vec<u8> {
         let tokens = vec![
             self.statement.encode(),
@@ -38,6 +51,8 @@ impl AccountState {
             Token::FixedBytes(self.start_nexus_hash.to_vec()),
             Token::Uint(self.last_proof_height.into()),
             Token::Uint(self.height.into()),
+            Token::Uint((self.proving_system as u8).into()),
+            Token::FixedBytes(self.owner.to_vec()),
         ];
         encode(&tokens)
     }
@@ -50,11 +65,13 @@ impl AccountState {
                 ParamType::FixedBytes(32),
                 ParamType::Uint(32),
                 ParamType::Uint(32),
+                ParamType::Uint(8),
+                ParamType::FixedBytes(32),
             ],
             encoded,
         )?;
 
-        if tokens.len() != 5 {
+        if tokens.len() != 7 {
             return Err(ethabi::Error::InvalidData);
         }
 
@@ -81,6 +98,23 @@ impl AccountState {
             .into_uint()
             .ok_or(ethabi::Error::InvalidData)?
             .as_u32();
+        let proving_system = match tokens[5]
+            .clone()
+            .into_uint()
+            .ok_or(ethabi::Error::InvalidData)?
+            .as_u32()
+        {
+            0 => ZKVMBackend::Risc0,
+            1 => ZKVMBackend::Sp1,
+            2 => ZKVMBackend::Mock,
+            _ => return Err(ethabi::Error::InvalidData),
+        };
+        let owner: [u8; 32] = tokens[6]
+            .clone()
+            .into_fixed_bytes()
+            .ok_or(ethabi::Error::InvalidData)?
+            .try_into()
+            .map_err(|_| ethabi::Error::InvalidData)?;
 
         Ok(AccountState {
             statement,
@@ -88,6 +122,8 @@ impl AccountState {
             start_nexus_hash,
             last_proof_height,
             height,
+            proving_system,
+            owner,
         })
     }
 }
@@ -124,6 +160,26 @@ impl StatementDigest {
     fn zero() -> Self {
         Self([0u32; 8])
     }
+
+    /// Bytes of the digest words in little endian, as taken by `ZKVMProof::verify`.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, num) in self.0.iter().enumerate() {
+            bytes[i * 4..(i + 1) * 4].copy_from_slice(&num.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Inverse of [`StatementDigest::to_le_bytes`].
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let mut words = [0u32; 8];
+        for (i, chunk) in bytes.chunks(4).enumerate() {
+            words[i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+
+        Self(words)
+    }
 }
 
 // impl From<RiscZeroDigest> for StatementDigest {
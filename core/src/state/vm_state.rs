@@ -121,6 +121,18 @@ impl VmState {
         }
     }
 
+    /// Accounts in the committed state at `version`, sorted by key.
+    pub fn accounts(&self, version: Version) -> Result<Vec<(H256, AccountState)>, Error> {
+        self.merkle_store
+            .values(version)?
+            .into_iter()
+            .map(|(key, value)| match AccountState::decode(&value) {
+                Ok(account_state) => Ok((H256::from(key.0), account_state)),
+                Err(e) => Err(e.into()),
+            })
+            .collect()
+    }
+
     //Gets from state even if not committed.
     pub fn get_with_proof(
         &self,
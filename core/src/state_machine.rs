@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::errors::NexusTxError;
 use crate::state::VmState;
 use crate::stf::StateTransitionFunction;
 use crate::types::{
@@ -7,6 +8,7 @@ use crate::types::{
     TransactionZKVM, TxParams, H256,
 };
 use crate::zkvm::traits::{ZKVMEnv, ZKVMProof};
+use crate::zkvm::ZKVMBackend;
 use anyhow::{anyhow, Error};
 use jmt::storage::{NodeBatch, TreeUpdateBatch};
 use jmt::Version;
@@ -24,17 +26,26 @@ pub struct StateMachine<Z: ZKVMEnv, P: ZKVMProof + DebugTrait + Clone> {
 }
 
 impl<Z: ZKVMEnv, P: ZKVMProof + Serialize + DebugTrait + Clone> StateMachine<Z, P> {
-    #[instrument(level = "debug", skip(state))]
     pub fn new(state: Arc<Mutex<VmState>>) -> Self {
+        Self::with_backend(state, Z::BACKEND)
+    }
+
+    /// Creates a state machine for a Nexus runtime proven with `backend`.
+    #[instrument(level = "debug", skip(state))]
+    pub fn with_backend(state: Arc<Mutex<VmState>>, backend: ZKVMBackend) -> Self {
         debug!("Creating new StateMachine");
         StateMachine {
-            stf: StateTransitionFunction::new(),
+            stf: StateTransitionFunction::with_backend(backend),
             //      db: node_db,
             p: PhantomData,
             state,
         }
     }
 
+    pub fn backend(&self) -> ZKVMBackend {
+        self.stf.backend()
+    }
+
     #[instrument(level = "debug", skip(self, state_root))]
     pub async fn commit_state(
         &mut self,
@@ -73,7 +84,14 @@ impl<Z: ZKVMEnv, P: ZKVMProof + Serialize + DebugTrait + Clone> StateMachine<Z,
         avail_header: &AvailHeader,
         old_nexus_headers: &HeaderStore,
         txs: &Vec<Transaction>,
-    ) -> Result<(Option<TreeUpdateBatch>, StateUpdate, HashMap<H256, bool>), Error> {
+    ) -> Result<
+        (
+            Option<TreeUpdateBatch>,
+            StateUpdate,
+            HashMap<H256, Result<(), NexusTxError>>,
+        ),
+        Error,
+    > {
         debug!("Executing batch in state machine");
         //TODO: Increment version for each update.
         let mut pre_state: HashMap<[u8; 32], AccountState> = HashMap::new();
@@ -114,15 +132,7 @@ impl<Z: ZKVMEnv, P: ZKVMProof + Serialize + DebugTrait + Clone> StateMachine<Z,
 
         let version = prev_version + 1;
         //TODO: Need to simplify this part.
-        let zkvm_txs: Vec<TransactionZKVM> = txs
-            .iter()
-            .map(|tx| {
-                return TransactionZKVM {
-                    params: tx.params.clone(),
-                    signature: tx.signature.clone(),
-                };
-            })
-            .collect();
+        let zkvm_txs: Vec<TransactionZKVM> = txs.iter().map(TransactionZKVM::from).collect();
         let (stf_state_result, tx_result) = self.stf.execute_batch_with_results(
             avail_header,
             old_nexus_headers,
@@ -1,16 +1,22 @@
+use crate::errors::NexusTxError;
 use crate::traits::NexusTransaction;
 use crate::{
     types::{
-        AccountState, AppAccountId, AvailHeader, HeaderStore, InitAccount, NexusRollupPI,
-        SubmitProof, TransactionZKVM, TxParams, H256,
+        verify_tx_signature, AccountState, AppAccountId, AvailHeader, HeaderStore, InitAccount,
+        NexusRollupPI, SubmitProof, TransactionZKVM, TxParams, H256,
+    },
+    zkvm::{
+        foreign::{foreign_receipt_kind, verify_foreign_proof},
+        traits::ZKVMEnv,
+        ZKVMBackend, RECURSION_REGION,
     },
-    zkvm::traits::ZKVMEnv,
 };
 use anyhow::{anyhow, Error};
 use std::collections::HashMap;
 use std::marker::PhantomData;
 pub struct StateTransitionFunction<Z: ZKVMEnv> {
     z: PhantomData<Z>,
+    backend: ZKVMBackend,
 }
 
 //Notes
@@ -21,8 +27,28 @@ pub struct StateTransitionFunction<Z: ZKVMEnv> {
 
 impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
     pub fn new() -> Self {
-        StateTransitionFunction { z: PhantomData }
+        Self::with_backend(Z::BACKEND)
+    }
+
+    /// Creates the state transition function for a Nexus runtime proven with `backend`,
+    /// which decides the rollup proofs that are recursed.
+    pub fn with_backend(backend: ZKVMBackend) -> Self {
+        StateTransitionFunction {
+            z: PhantomData,
+            backend,
+        }
+    }
+
+    pub fn backend(&self) -> ZKVMBackend {
+        self.backend
     }
+
+    /// Whether the runtime can recurse proofs of `proving_system`, or verify them itself.
+    pub fn can_verify(&self, proving_system: ZKVMBackend) -> bool {
+        proving_system == self.backend
+            || foreign_receipt_kind(self.backend, proving_system).is_some()
+    }
+
     pub fn execute_batch_common<F>(
         &self,
         new_avail_header: &AvailHeader,
@@ -32,7 +58,7 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         mut on_tx_result: F,
     ) -> Result<HashMap<[u8; 32], AccountState>, anyhow::Error>
     where
-        F: FnMut(H256, Result<(), anyhow::Error>),
+        F: FnMut(H256, Result<(), NexusTxError>),
     {
         if let Some(last_header) = prev_headers.first() {
             if new_avail_header.parent_hash != last_header.avail_header_hash {
@@ -89,7 +115,13 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         prev_headers: &HeaderStore,
         txs: &Vec<TransactionZKVM>,
         pre_state: &HashMap<[u8; 32], AccountState>,
-    ) -> Result<(HashMap<[u8; 32], AccountState>, HashMap<H256, bool>), anyhow::Error> {
+    ) -> Result<
+        (
+            HashMap<[u8; 32], AccountState>,
+            HashMap<H256, Result<(), NexusTxError>>,
+        ),
+        anyhow::Error,
+    > {
         let mut tx_results = HashMap::new();
         let post_state = self.execute_batch_common(
             new_avail_header,
@@ -97,7 +129,7 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
             txs,
             pre_state,
             |tx_hash, result| {
-                tx_results.insert(tx_hash, result.is_ok());
+                tx_results.insert(tx_hash, result);
             },
         )?;
         Ok((post_state, tx_results))
@@ -108,11 +140,23 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         tx: &TransactionZKVM,
         pre_state: (&AppAccountId, &AccountState),
         headers: &HeaderStore,
-    ) -> Result<(AppAccountId, AccountState), anyhow::Error> {
-        //TODO: Signature verification
+    ) -> Result<(AppAccountId, AccountState), NexusTxError> {
+        //Unsigned transactions are allowed, but can only act on accounts without owner.
+        let signer = match &tx.signer {
+            Some(signer) => {
+                verify_tx_signature(signer, &tx.signature, &tx.hash())
+                    .map_err(|_| NexusTxError::InvalidSignature)?;
+
+                Some(signer)
+            }
+            None => None,
+        };
+
         let post_state = match &tx.params {
-            TxParams::SubmitProof(params) => self.submit_proof(params, pre_state, headers)?,
-            TxParams::InitAccount(params) => self.init_account(params, pre_state)?,
+            TxParams::SubmitProof(params) => {
+                self.submit_proof(params, pre_state, headers, signer)?
+            }
+            TxParams::InitAccount(params) => self.init_account(params, pre_state, signer)?,
         };
 
         Ok(post_state)
@@ -123,9 +167,14 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         params: &SubmitProof,
         pre_state: (&AppAccountId, &AccountState),
         headers: &HeaderStore,
-    ) -> Result<(AppAccountId, AccountState), Error> {
+        signer: Option<&[u8; 32]>,
+    ) -> Result<(AppAccountId, AccountState), NexusTxError> {
         if pre_state.1.clone() == AccountState::zero() {
-            return Err(anyhow!("Invalid transaction, account not initiated."));
+            return Err(NexusTxError::AccountNotInitiated);
+        }
+
+        if pre_state.1.has_owner() && signer != Some(&pre_state.1.owner) {
+            return Err(NexusTxError::NotAccountOwner);
         }
 
         let public_inputs: NexusRollupPI = NexusRollupPI {
@@ -139,11 +188,11 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         };
 
         if public_inputs.app_id != pre_state.0.clone() {
-            return Err(anyhow!("Incorrect app account id"));
+            return Err(NexusTxError::IncorrectAppAccountId);
         }
 
         if public_inputs.start_nexus_hash != H256::from(pre_state.1.start_nexus_hash) {
-            return Err(anyhow!("Not a recursive proof from registered start hash."));
+            return Err(NexusTxError::InvalidStartNexusHash);
         }
 
         let mut header_hash: H256 = match headers.first() {
@@ -154,7 +203,7 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
 
         for header in headers.inner().iter() {
             if header.hash() != header_hash {
-                return Err(anyhow!("Incorrect header list given by sequencer."));
+                return Err(NexusTxError::IncorrectHeaderList);
             }
 
             if header_hash == public_inputs.nexus_hash {
@@ -168,16 +217,44 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         }
 
         if found_header_height.is_none() {
-            return Err(anyhow!("Not right fork, or against last 32 blocks"));
+            return Err(NexusTxError::NotRightFork);
+        }
+
+        public_inputs
+            .check_consistency(&pre_state.1.statement)
+            .map_err(|_| NexusTxError::StatementMismatch)?;
+
+        if !self.can_verify(pre_state.1.proving_system) {
+            return Err(NexusTxError::UnsupportedProvingSystem);
         }
 
-        public_inputs.check_consistency(&pre_state.1.statement)?;
+        if pre_state.1.proving_system == self.backend {
+            //Native execution leaves verification to the runtime, which recurses the proof.
+            if Z::is_proving() {
+                Z::start_region(RECURSION_REGION);
+                let result = Z::verify(public_inputs.img_id.0, &public_inputs);
+                Z::end_region(RECURSION_REGION);
 
-        #[cfg(not(feature = "native"))]
-        {
-            match Z::verify(public_inputs.img_id.0, &public_inputs) {
+                match result {
+                    Ok(_) => (),
+                    Err(e) => return Err(NexusTxError::InvalidProof),
+                }
+            }
+        } else {
+            //Proofs of other backends cannot be recursed, so the runtime verifies the one in
+            //the transaction itself, as the node does when executing natively.
+            Z::start_region(RECURSION_REGION);
+            let result = verify_foreign_proof(
+                self.backend,
+                pre_state.1.proving_system,
+                &params.proof,
+                &public_inputs,
+            );
+            Z::end_region(RECURSION_REGION);
+
+            match result {
                 Ok(_) => (),
-                Err(e) => return Err(anyhow!("Invalid proof")),
+                Err(e) => return Err(NexusTxError::InvalidProof),
             }
         }
 
@@ -188,6 +265,8 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
             height: params.height,
             //Okay to do unwrap as we check above if it is None.
             last_proof_height: found_header_height.unwrap(),
+            proving_system: pre_state.1.proving_system,
+            owner: pre_state.1.owner,
         };
 
         Ok((public_inputs.app_id.clone(), post_state))
@@ -197,15 +276,24 @@ impl<Z: ZKVMEnv> StateTransitionFunction<Z> {
         &self,
         params: &InitAccount,
         pre_state: (&AppAccountId, &AccountState),
-    ) -> Result<(AppAccountId, AccountState), Error> {
+        signer: Option<&[u8; 32]>,
+    ) -> Result<(AppAccountId, AccountState), NexusTxError> {
         if pre_state.1.clone() != AccountState::zero() {
-            return Err(anyhow!("Account already initiated."));
+            return Err(NexusTxError::AccountAlreadyInitiated);
+        }
+
+        if !self.can_verify(params.proving_system) {
+            return Err(NexusTxError::UnsupportedProvingSystem);
         }
 
         let mut post_account = AccountState::zero();
 
         post_account.statement = params.statement.clone();
         post_account.start_nexus_hash = params.start_nexus_hash.as_fixed_slice().clone();
+        post_account.proving_system = params.proving_system;
+        if let Some(signer) = signer {
+            post_account.owner = signer.clone();
+        }
 
         Ok((pre_state.0.clone(), post_account))
     }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 
+pub use crate::errors::TxError;
 use crate::traits::NexusTransaction;
 pub use crate::utils::hasher::Sha256;
 use crate::utils::hasher::{Digest as RiscZeroDigestTrait, ShaHasher};
@@ -17,7 +18,6 @@ use parity_scale_codec::{Decode, Encode};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_big_array::BigArray;
-use serde_json::{from_slice, to_vec, Error};
 use solabi::{
     decode::{Decode as SolabiDecode, DecodeError, Decoder},
     encode::{Encode as SolabiEncode, Encoder, Size},
@@ -27,6 +27,7 @@ pub use crate::h256::H256;
 pub use crate::state::types::{AccountState, StatementDigest};
 #[cfg(any(feature = "native"))]
 use crate::zkvm::traits::ZKVMProof;
+use crate::zkvm::{ReceiptKind, ZKVMBackend};
 use core::fmt::Debug as DebugTrait;
 #[cfg(any(feature = "native"))]
 use utoipa::ToSchema;
@@ -55,6 +56,43 @@ pub struct AccountWithProof {
     pub nexus_state_root_hex: String,
 }
 
+/// Key registered as owner of an app account, signer of its `InitAccount` transaction.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "native", derive(ToSchema))]
+pub struct AccountOwner {
+    pub app_account_id: H256,
+    pub owner: Option<H256>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccountStateHex {
+    pub statement: String,
+    pub state_root: String,
+    pub start_nexus_hash: String,
+    pub last_proof_height: u32,
+    pub height: u32,
+    pub proving_system: ZKVMBackend,
+    pub owner: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NexusHeaderHex {
+    pub parent_hash: String,
+    pub prev_state_root: String,
+    pub state_root: String,
+    pub avail_header_hash: String,
+    pub number: u32,
+    pub runtime_version: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccountWithProofHex {
+    pub account: AccountStateHex,
+    pub proof: Vec<String>,
+    pub value_hash: String,
+    pub nexus_header: NexusHeaderHex,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Encode, Decode)]
 pub struct UpdatedBlob {
     commitment: [u8; 32],
@@ -75,12 +113,18 @@ pub enum TxParams {
 pub struct Transaction {
     pub signature: TxSignature,
     pub params: TxParams,
+    /// Ed25519 public key of the signer, not part of the transaction hash.
+    #[serde(default)]
+    pub signer: Option<[u8; 32]>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, Encode, Decode)]
 pub struct TransactionZKVM {
     pub signature: TxSignature,
     pub params: TxParams,
+    /// Checked by the state transition function against the owner of the account.
+    #[serde(default)]
+    pub signer: Option<[u8; 32]>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Encode, Decode)]
@@ -98,12 +142,28 @@ pub struct SubmitProof {
 #[cfg_attr(feature = "native", derive(ToSchema))]
 pub struct Proof(pub Vec<u8>);
 
+/// Current encoding version of `ProofEnvelope`.
+pub const PROOF_ENVELOPE_VERSION: u8 = 1;
+
+/// Self-describing proof carried by `Proof`, so nodes can tell which backend and
+/// statement a submitted proof is for without trying to deserialize it.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct ProofEnvelope {
+    pub version: u8,
+    pub backend: ZKVMBackend,
+    pub kind: ReceiptKind,
+    pub img_id: StatementDigest,
+    /// Proof encoded by the backend.
+    pub data: Vec<u8>,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Encode, Decode)]
 #[cfg_attr(feature = "native", derive(ToSchema))]
 pub struct InitAccount {
     pub app_id: AppAccountId,
     pub statement: StatementDigest,
     pub start_nexus_hash: H256,
+    pub proving_system: ZKVMBackend,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -126,12 +186,16 @@ pub struct NexusHeader {
     pub tx_root: H256,
     pub avail_header_hash: H256,
     pub number: u32,
+    /// Version of the runtime that produced the block.
+    pub runtime_version: u32,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Encode, Decode)]
 pub struct TransactionResult {
     pub hash: H256,
     pub result: bool,
+    #[serde(default)]
+    pub error: Option<TxError>,
 }
 
 #[cfg(any(feature = "native"))]
@@ -156,6 +220,8 @@ pub struct TransactionWithStatus {
     pub transaction: Transaction,
     pub status: TransactionStatus,
     pub block_hash: Option<H256>,
+    #[serde(default)]
+    pub error: Option<TxError>,
 }
 
 #[cfg(any(feature = "native"))]
@@ -249,7 +315,7 @@ pub struct DataLookupItem {
     pub start: u32,
 }
 
-#[derive(PartialEq, Eq, Clone, Encode, Decode)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Debug, Serialize, Deserialize)]
 pub struct Range<Idx> {
     pub start: Idx,
     pub end: Idx,
@@ -268,10 +334,59 @@ pub struct KateCommitment {
     pub data_root: H256,
 }
 
+/// Merkle proof of a single data submission against the `data_root` of the
+/// Avail header commitment, as returned by `kate_queryDataProof`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Debug, Serialize, Deserialize)]
+pub struct DataProof {
+    pub data_root: H256,
+    pub proof: Vec<H256>,
+    pub number_of_leaves: u32,
+    pub leaf_index: u32,
+    pub leaf: H256,
+}
+
 //--------------
 //Implementations
 //--------------
 
+impl From<NexusHeader> for NexusHeaderHex {
+    fn from(value: NexusHeader) -> Self {
+        Self {
+            parent_hash: hex::encode(value.parent_hash.as_fixed_slice()),
+            prev_state_root: hex::encode(value.prev_state_root.as_fixed_slice()),
+            state_root: hex::encode(value.state_root.as_fixed_slice()),
+            avail_header_hash: hex::encode(value.avail_header_hash.as_fixed_slice()),
+            number: value.number,
+            runtime_version: value.runtime_version,
+        }
+    }
+}
+
+impl From<AccountState> for AccountStateHex {
+    fn from(value: AccountState) -> Self {
+        Self {
+            statement: value.statement.encode().to_string(),
+            state_root: hex::encode(value.state_root),
+            start_nexus_hash: hex::encode(value.start_nexus_hash),
+            last_proof_height: value.last_proof_height,
+            height: value.height,
+            proving_system: value.proving_system,
+            owner: hex::encode(value.owner),
+        }
+    }
+}
+
+impl From<AccountWithProof> for AccountWithProofHex {
+    fn from(value: AccountWithProof) -> Self {
+        Self {
+            account: AccountStateHex::from(value.account),
+            proof: value.proof_hex,
+            value_hash: value.value_hash_hex,
+            nexus_header: NexusHeaderHex::from(value.nexus_header),
+        }
+    }
+}
+
 impl Encode for DigestItem {
     fn encode(&self) -> Vec<u8> {
         let mut v = Vec::new();
@@ -397,6 +512,20 @@ impl AvailHeader {
     }
 }
 
+impl DataLookup {
+    /// Returns the range of chunks in the data matrix occupied by the given app.
+    pub fn range_of(&self, app_id: &AppId) -> Option<Range<u32>> {
+        let position = self.index.iter().position(|item| &item.app_id == app_id)?;
+        let start = self.index[position].start;
+        let end = match self.index.get(position + 1) {
+            Some(next) => next.start,
+            None => self.size,
+        };
+
+        Some(Range { start, end })
+    }
+}
+
 impl HeaderStore {
     pub fn new(max_size: usize) -> Self {
         Self {
@@ -453,6 +582,56 @@ impl AppAccountId {
     }
 }
 
+impl ProofEnvelope {
+    pub fn new(
+        backend: ZKVMBackend,
+        kind: ReceiptKind,
+        img_id: StatementDigest,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            version: PROOF_ENVELOPE_VERSION,
+            backend,
+            kind,
+            img_id,
+            data,
+        }
+    }
+
+    /// Checks the proof was generated with `backend`.
+    pub fn check_backend(&self, backend: ZKVMBackend) -> Result<(), anyhow::Error> {
+        if self.backend != backend {
+            return Err(anyhow::anyhow!(
+                "Proof generated with {:?} backend, expected {:?}",
+                self.backend,
+                backend
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Proof {
+    /// Decodes the envelope of the proof, rejecting unknown encoding versions.
+    pub fn envelope(&self) -> Result<ProofEnvelope, anyhow::Error> {
+        match self.0.first() {
+            Some(&PROOF_ENVELOPE_VERSION) => (),
+            Some(i) => return Err(anyhow::anyhow!("Unsupported proof encoding version {}", i)),
+            None => return Err(anyhow::anyhow!("Empty proof")),
+        }
+
+        ProofEnvelope::decode(&mut self.0.as_slice())
+            .map_err(|e| anyhow::anyhow!("Invalid proof envelope: {}", e))
+    }
+}
+
+impl From<ProofEnvelope> for Proof {
+    fn from(value: ProofEnvelope) -> Self {
+        Self(value.encode())
+    }
+}
+
 impl NexusRollupPI {
     pub fn check_consistency(&self, img_id: &StatementDigest) -> Result<(), anyhow::Error> {
         if img_id != &self.img_id {
@@ -465,19 +644,19 @@ impl NexusRollupPI {
 
 #[cfg(any(feature = "native-risc0"))]
 impl TryFrom<risc0_zkvm::Receipt> for Proof {
-    type Error = Error;
+    type Error = anyhow::Error;
 
     fn try_from(value: risc0_zkvm::Receipt) -> Result<Self, Self::Error> {
-        Ok(Self(to_vec(&value)?))
+        crate::zkvm::risczero::RiscZeroProof(value).try_into()
     }
 }
 
 #[cfg(any(feature = "native-risc0"))]
 impl TryInto<risc0_zkvm::Receipt> for Proof {
-    type Error = Error;
+    type Error = anyhow::Error;
 
     fn try_into(self) -> Result<risc0_zkvm::Receipt, Self::Error> {
-        Ok(from_slice(&self.0)?)
+        Ok(crate::zkvm::risczero::RiscZeroProof::try_from(self)?.0)
     }
 }
 
@@ -502,6 +681,43 @@ impl NexusTransaction for Transaction {
     }
 }
 
+/// Checks an ed25519 signature over a transaction hash.
+pub fn verify_tx_signature(
+    signer: &[u8; 32],
+    signature: &TxSignature,
+    tx_hash: &H256,
+) -> Result<(), anyhow::Error> {
+    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(signer)
+        .map_err(|_| anyhow::anyhow!("Invalid signer public key"))?;
+    let signature = ed25519_dalek::Signature::from_bytes(&signature.0);
+
+    verifying_key
+        .verify_strict(tx_hash.as_slice(), &signature)
+        .map_err(|_| anyhow::anyhow!("Invalid transaction signature"))
+}
+
+#[cfg(any(feature = "native"))]
+impl Transaction {
+    /// Checks the signature over the transaction hash against `signer`.
+    pub fn verify_signature(&self) -> Result<(), anyhow::Error> {
+        match &self.signer {
+            Some(signer) => verify_tx_signature(signer, &self.signature, &self.hash()),
+            None => Err(anyhow::anyhow!("Transaction signer not provided")),
+        }
+    }
+}
+
+#[cfg(any(feature = "native"))]
+impl From<&Transaction> for TransactionZKVM {
+    fn from(value: &Transaction) -> Self {
+        TransactionZKVM {
+            signature: value.signature.clone(),
+            params: value.params.clone(),
+            signer: value.signer.clone(),
+        }
+    }
+}
+
 impl NexusHeader {
     pub fn hash(&self) -> H256 {
         let serialized = self.encode();
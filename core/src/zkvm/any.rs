@@ -0,0 +1,343 @@
+use super::proving::ProvingProgress;
+use super::traits::{ZKVMProof, ZKVMProver};
+use super::{ProverMode, ProvingStats, ZKVMBackend};
+use crate::types::Proof;
+use anyhow::{anyhow, Error};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[cfg(feature = "native-mock")]
+use super::mock::{MockProof, MockProver};
+#[cfg(feature = "native-risc0")]
+use super::risczero::{RiscZeroProof, RiscZeroProver};
+#[cfg(feature = "native-sp1")]
+use super::sp1::{Sp1Proof, Sp1Prover};
+
+impl Default for ZKVMBackend {
+    #[cfg(feature = "native-risc0")]
+    fn default() -> Self {
+        ZKVMBackend::Risc0
+    }
+
+    #[cfg(all(feature = "native-sp1", not(feature = "native-risc0")))]
+    fn default() -> Self {
+        ZKVMBackend::Sp1
+    }
+
+    #[cfg(not(any(feature = "native-risc0", feature = "native-sp1")))]
+    fn default() -> Self {
+        ZKVMBackend::Mock
+    }
+}
+
+/// Prover for any of the zkVM backends compiled in, selected at runtime.
+pub enum AnyProver<'a> {
+    #[cfg(feature = "native-risc0")]
+    Risc0(RiscZeroProver<'a>),
+    #[cfg(feature = "native-sp1")]
+    Sp1(Sp1Prover, std::marker::PhantomData<&'a ()>),
+    #[cfg(feature = "native-mock")]
+    Mock(MockProver, std::marker::PhantomData<&'a ()>),
+}
+
+impl<'a> AnyProver<'a> {
+    /// Creates a prover for the given backend. The ELF must be built for that backend.
+    pub fn with_backend(
+        backend: ZKVMBackend,
+        elf: Vec<u8>,
+        prover_mode: ProverMode,
+    ) -> Result<Self, Error> {
+        match backend {
+            #[cfg(feature = "native-risc0")]
+            ZKVMBackend::Risc0 => Ok(Self::Risc0(RiscZeroProver::new(elf, prover_mode))),
+            #[cfg(feature = "native-sp1")]
+            ZKVMBackend::Sp1 => Ok(Self::Sp1(
+                Sp1Prover::new(elf, prover_mode),
+                std::marker::PhantomData,
+            )),
+            #[cfg(feature = "native-mock")]
+            ZKVMBackend::Mock => Ok(Self::Mock(
+                MockProver::new(elf, prover_mode),
+                std::marker::PhantomData,
+            )),
+            #[allow(unreachable_patterns)]
+            _ => Err(anyhow!("{:?} backend not enabled in this build", backend)),
+        }
+    }
+
+    pub fn backend(&self) -> ZKVMBackend {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(_) => ZKVMBackend::Risc0,
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(..) => ZKVMBackend::Sp1,
+            #[cfg(feature = "native-mock")]
+            Self::Mock(..) => ZKVMBackend::Mock,
+        }
+    }
+}
+
+impl<'a> ZKVMProver<AnyProof> for AnyProver<'a> {
+    /// Uses the default backend, `with_backend` should be used to select one.
+    fn new(elf: Vec<u8>, prover_mode: ProverMode) -> Self {
+        match Self::with_backend(ZKVMBackend::default(), elf, prover_mode) {
+            Ok(i) => i,
+            Err(_) => unreachable!("Default backend is always enabled"),
+        }
+    }
+
+    fn add_input<T: Serialize>(&mut self, input: &T) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(prover) => prover.add_input(input),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(prover, _) => prover.add_input(input),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(prover, _) => prover.add_input(input),
+        }
+    }
+
+    fn add_proof_for_recursion(&mut self, proof: AnyProof) -> Result<(), Error> {
+        #[allow(unreachable_patterns)]
+        match (self, proof) {
+            #[cfg(feature = "native-risc0")]
+            (Self::Risc0(prover), AnyProof::Risc0(proof)) => prover.add_proof_for_recursion(proof),
+            #[cfg(feature = "native-sp1")]
+            (Self::Sp1(prover, _), AnyProof::Sp1(proof)) => prover.add_proof_for_recursion(proof),
+            #[cfg(feature = "native-mock")]
+            (Self::Mock(prover, _), AnyProof::Mock(proof)) => prover.add_proof_for_recursion(proof),
+            (prover, proof) => Err(anyhow!(
+                "Cannot recurse {:?} proof in {:?} prover",
+                proof.backend(),
+                prover.backend()
+            )),
+        }
+    }
+
+    fn prove(&mut self) -> Result<AnyProof, Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(prover) => prover.prove().map(AnyProof::Risc0),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(prover, _) => prover.prove().map(AnyProof::Sp1),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(prover, _) => prover.prove().map(AnyProof::Mock),
+        }
+    }
+
+    fn prove_with_progress(
+        &mut self,
+        progress: &dyn Fn(ProvingProgress),
+    ) -> Result<AnyProof, Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(prover) => prover.prove_with_progress(progress).map(AnyProof::Risc0),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(prover, _) => prover.prove_with_progress(progress).map(AnyProof::Sp1),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(prover, _) => prover.prove_with_progress(progress).map(AnyProof::Mock),
+        }
+    }
+
+    fn stats(&self) -> Option<ProvingStats> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(prover) => prover.stats(),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(prover, _) => prover.stats(),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(prover, _) => prover.stats(),
+        }
+    }
+}
+
+/// Proof of any of the zkVM backends compiled in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AnyProof {
+    #[cfg(feature = "native-risc0")]
+    Risc0(RiscZeroProof),
+    #[cfg(feature = "native-sp1")]
+    Sp1(Sp1Proof),
+    #[cfg(feature = "native-mock")]
+    Mock(MockProof),
+}
+
+impl AnyProof {
+    pub fn backend(&self) -> ZKVMBackend {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(_) => ZKVMBackend::Risc0,
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(_) => ZKVMBackend::Sp1,
+            #[cfg(feature = "native-mock")]
+            Self::Mock(_) => ZKVMBackend::Mock,
+        }
+    }
+
+    /// Decodes a proof submitted to Nexus, produced by the given backend.
+    pub fn from_proof(backend: ZKVMBackend, proof: Proof) -> Result<Self, Error> {
+        match backend {
+            #[cfg(feature = "native-risc0")]
+            ZKVMBackend::Risc0 => RiscZeroProof::try_from(proof).map(Self::Risc0),
+            #[cfg(feature = "native-sp1")]
+            ZKVMBackend::Sp1 => Sp1Proof::try_from(proof).map(Self::Sp1),
+            #[cfg(feature = "native-mock")]
+            ZKVMBackend::Mock => MockProof::try_from(proof).map(Self::Mock),
+            #[allow(unreachable_patterns)]
+            _ => Err(anyhow!("{:?} backend not enabled in this build", backend)),
+        }
+    }
+}
+
+impl ZKVMProof for AnyProof {
+    fn verify(
+        &self,
+        img_id: Option<[u8; 32]>,
+        elf: Option<Vec<u8>>,
+        proof_mode: ProverMode,
+    ) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(proof) => proof.verify(img_id, elf, proof_mode),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(proof) => proof.verify(img_id, elf, proof_mode),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(proof) => proof.verify(img_id, elf, proof_mode),
+        }
+    }
+
+    fn public_inputs<V: Serialize + DeserializeOwned + Clone>(&mut self) -> Result<V, Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(proof) => proof.public_inputs(),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(proof) => proof.public_inputs(),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(proof) => proof.public_inputs(),
+        }
+    }
+
+    fn compress(&mut self) -> Result<Self, Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(proof) => proof.compress().map(Self::Risc0),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(proof) => proof.compress().map(Self::Sp1),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(proof) => proof.compress().map(Self::Mock),
+        }
+    }
+
+    fn wrap_groth16(&mut self) -> Result<Self, Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(proof) => proof.wrap_groth16().map(Self::Risc0),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(proof) => proof.wrap_groth16().map(Self::Sp1),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(proof) => proof.wrap_groth16().map(Self::Mock),
+        }
+    }
+
+    fn evm_seal(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(proof) => proof.evm_seal(),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(proof) => proof.evm_seal(),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(proof) => proof.evm_seal(),
+        }
+    }
+}
+
+impl TryInto<Proof> for AnyProof {
+    type Error = Error;
+
+    fn try_into(self) -> Result<Proof, Self::Error> {
+        match self {
+            #[cfg(feature = "native-risc0")]
+            Self::Risc0(proof) => proof.try_into(),
+            #[cfg(feature = "native-sp1")]
+            Self::Sp1(proof) => proof.try_into(),
+            #[cfg(feature = "native-mock")]
+            Self::Mock(proof) => proof.try_into(),
+        }
+    }
+}
+
+/// Decodes the proof with the backend recorded in its envelope.
+impl TryFrom<Proof> for AnyProof {
+    type Error = Error;
+
+    fn try_from(value: Proof) -> Result<Self, Self::Error> {
+        let backend = value.envelope()?.backend;
+
+        Self::from_proof(backend, value)
+    }
+}
+
+#[cfg(feature = "native-risc0")]
+impl From<RiscZeroProof> for AnyProof {
+    fn from(value: RiscZeroProof) -> Self {
+        Self::Risc0(value)
+    }
+}
+
+#[cfg(feature = "native-sp1")]
+impl From<Sp1Proof> for AnyProof {
+    fn from(value: Sp1Proof) -> Self {
+        Self::Sp1(value)
+    }
+}
+
+#[cfg(feature = "native-mock")]
+impl From<MockProof> for AnyProof {
+    fn from(value: MockProof) -> Self {
+        Self::Mock(value)
+    }
+}
+
+/// Proofs which can be taken out of an `AnyProof`, used to return the proof of a
+/// runtime selected prover as the proof type of the caller.
+pub trait ProofConversion: Sized {
+    fn from_any(proof: AnyProof) -> Result<Self, Error>;
+}
+
+impl ProofConversion for AnyProof {
+    fn from_any(proof: AnyProof) -> Result<Self, Error> {
+        Ok(proof)
+    }
+}
+
+#[cfg(feature = "native-risc0")]
+impl ProofConversion for RiscZeroProof {
+    fn from_any(proof: AnyProof) -> Result<Self, Error> {
+        match proof {
+            AnyProof::Risc0(proof) => Ok(proof),
+            #[allow(unreachable_patterns)]
+            other => Err(anyhow!("Expected Risc0 proof, got {:?}", other.backend())),
+        }
+    }
+}
+
+#[cfg(feature = "native-sp1")]
+impl ProofConversion for Sp1Proof {
+    fn from_any(proof: AnyProof) -> Result<Self, Error> {
+        match proof {
+            AnyProof::Sp1(proof) => Ok(proof),
+            #[allow(unreachable_patterns)]
+            other => Err(anyhow!("Expected Sp1 proof, got {:?}", other.backend())),
+        }
+    }
+}
+
+#[cfg(feature = "native-mock")]
+impl ProofConversion for MockProof {
+    fn from_any(proof: AnyProof) -> Result<Self, Error> {
+        match proof {
+            AnyProof::Mock(proof) => Ok(proof),
+            #[allow(unreachable_patterns)]
+            other => Err(anyhow!("Expected Mock proof, got {:?}", other.backend())),
+        }
+    }
+}
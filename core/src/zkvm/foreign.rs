@@ -0,0 +1,90 @@
+use super::{ReceiptKind, ZKVMBackend};
+use crate::types::{NexusRollupPI, Proof, StatementDigest};
+use anyhow::{anyhow, Error};
+#[cfg(any(feature = "risc0-zkvm"))]
+use risc0_zkvm::{serde::from_slice, Receipt};
+
+/// Kind of receipt of `backend` that the runtime proven with `runtime` verifies itself,
+/// as it cannot recurse them. `None` for the backend of the runtime, and for the ones it
+/// cannot verify.
+pub fn foreign_receipt_kind(runtime: ZKVMBackend, backend: ZKVMBackend) -> Option<ReceiptKind> {
+    match (runtime, backend) {
+        _ if runtime == backend => None,
+        //Mock proofs are only what the program committed, which any runtime can check.
+        (_, ZKVMBackend::Mock) => Some(ReceiptKind::Fake),
+        //The RISC Zero verifier relies on the RISC Zero platform once built for a guest,
+        //so only the mock runtime, which runs natively, verifies receipts itself.
+        (ZKVMBackend::Mock, ZKVMBackend::Risc0) => Some(ReceiptKind::Groth16),
+        //Groth16 proofs of SP1 are bound to another hash of the verifying key than the
+        //one rollups register as statement.
+        _ => None,
+    }
+}
+
+/// Verifies a rollup proof of another backend than the one of the runtime. The proof is
+/// part of the transaction, so the runtime verifies it while being proven, and the node
+/// the same way when executing the batch natively.
+pub fn verify_foreign_proof(
+    runtime: ZKVMBackend,
+    backend: ZKVMBackend,
+    proof: &Proof,
+    public_inputs: &NexusRollupPI,
+) -> Result<(), Error> {
+    let envelope = proof.envelope()?;
+    envelope.check_backend(backend)?;
+
+    match foreign_receipt_kind(runtime, backend) {
+        Some(kind) if kind == envelope.kind => (),
+        _ => {
+            return Err(anyhow!(
+                "{:?} receipts of {:?} proofs cannot be verified by the {:?} runtime",
+                envelope.kind,
+                backend,
+                runtime
+            ))
+        }
+    }
+
+    match backend {
+        ZKVMBackend::Mock => verify_mock(&envelope.data, public_inputs),
+        ZKVMBackend::Risc0 => verify_risc0(&envelope.data, public_inputs),
+        ZKVMBackend::Sp1 => Err(anyhow!("SP1 proofs can only be recursed")),
+    }
+}
+
+/// Mock proofs are encoded as `MockProof`, the image ID of the program followed by the
+/// outputs it committed.
+fn verify_mock(data: &[u8], public_inputs: &NexusRollupPI) -> Result<(), Error> {
+    let (img_id, journal): (StatementDigest, Vec<Vec<u8>>) = bincode::deserialize(data)?;
+
+    if img_id != public_inputs.img_id {
+        return Err(anyhow!("Proof not generated for the given image ID"));
+    }
+
+    if journal.first() != Some(&bincode::serialize(public_inputs)?) {
+        return Err(anyhow!("Proof not generated for the public inputs"));
+    }
+
+    Ok(())
+}
+
+#[cfg(any(feature = "risc0-zkvm"))]
+fn verify_risc0(data: &[u8], public_inputs: &NexusRollupPI) -> Result<(), Error> {
+    let receipt: Receipt = from_slice(data)?;
+
+    receipt
+        .verify(public_inputs.img_id.to_le_bytes())
+        .map_err(|e| anyhow!(e))?;
+
+    match receipt.journal.decode::<NexusRollupPI>() {
+        Ok(i) if &i == public_inputs => Ok(()),
+        _ => Err(anyhow!("Proof not generated for the public inputs")),
+    }
+}
+
+#[cfg(not(any(feature = "risc0-zkvm")))]
+fn verify_risc0(_: &[u8], _: &NexusRollupPI) -> Result<(), Error> {
+    Err(anyhow!(
+        "RISC Zero receipts cannot be verified by this build"
+    ))
+}
@@ -0,0 +1,252 @@
+use super::traits::{ZKVMEnv, ZKVMProof, ZKVMProver};
+use super::{ProverMode, ProvingStats, ReceiptKind, ZKVMBackend};
+use crate::types::{Proof, ProofEnvelope, StatementDigest};
+use crate::utils::hasher::{Digest, ShaHasher};
+use anyhow::{anyhow, Error};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::panic::catch_unwind;
+use std::sync::{Mutex, OnceLock};
+
+/// Stands in for the ELF of the Nexus runtime, which the mock prover runs in-process.
+pub const NEXUS_RUNTIME_MOCK_ELF: &[u8] = b"nexus-runtime-mock";
+
+/// Program run in place of a guest ELF.
+pub type MockProgram = fn();
+
+#[derive(Default)]
+struct Execution {
+    inputs: VecDeque<Vec<u8>>,
+    journal: Vec<Vec<u8>>,
+    assumptions: Vec<MockProof>,
+}
+
+thread_local! {
+    static EXECUTION: RefCell<Option<Execution>> = RefCell::new(None);
+}
+
+fn programs() -> &'static Mutex<HashMap<Vec<u8>, MockProgram>> {
+    static PROGRAMS: OnceLock<Mutex<HashMap<Vec<u8>, MockProgram>>> = OnceLock::new();
+
+    PROGRAMS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registers the program run when proving `elf`, so guests other than the Nexus
+/// runtime can be mocked.
+pub fn register_program(elf: &[u8], program: MockProgram) {
+    match programs().lock() {
+        Ok(mut i) => {
+            i.insert(elf.to_vec(), program);
+        }
+        Err(_) => panic!("Mock program registry poisoned"),
+    }
+}
+
+fn program(elf: &[u8]) -> Option<MockProgram> {
+    if elf == NEXUS_RUNTIME_MOCK_ELF {
+        return Some(crate::prover::run::<MockZKVM>);
+    }
+
+    programs().lock().ok()?.get(elf).cloned()
+}
+
+/// Image ID of a mocked program, the hash of its ELF.
+pub fn mock_img_id(elf: &[u8]) -> StatementDigest {
+    let mut hasher = ShaHasher::new();
+    hasher.0.update(elf);
+
+    let hash = hasher.finish();
+    let mut img_id = [0u32; 8];
+    for (i, chunk) in hash.as_slice().chunks(4).enumerate() {
+        img_id[i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    StatementDigest(img_id)
+}
+
+/// Runs programs in-process, without any proof system. Proofs are only the committed
+/// outputs of a program, so verification is a journal check.
+pub struct MockProver {
+    elf: Vec<u8>,
+    inputs: VecDeque<Vec<u8>>,
+    assumptions: Vec<MockProof>,
+    is_proven: bool,
+}
+
+impl ZKVMProver<MockProof> for MockProver {
+    fn new(elf: Vec<u8>, _: ProverMode) -> Self {
+        Self {
+            elf,
+            inputs: VecDeque::new(),
+            assumptions: vec![],
+            is_proven: false,
+        }
+    }
+
+    fn add_input<T: Serialize>(&mut self, input: &T) -> Result<(), Error> {
+        self.inputs.push_back(bincode::serialize(input)?);
+        Ok(())
+    }
+
+    fn add_proof_for_recursion(&mut self, proof: MockProof) -> Result<(), Error> {
+        self.assumptions.push(proof);
+        Ok(())
+    }
+
+    fn prove(&mut self) -> Result<MockProof, Error> {
+        let program = match program(&self.elf) {
+            Some(i) => i,
+            None => return Err(anyhow!("No mock program registered for the ELF")),
+        };
+
+        let execution = Execution {
+            inputs: std::mem::take(&mut self.inputs),
+            journal: vec![],
+            assumptions: std::mem::take(&mut self.assumptions),
+        };
+        //Restored after the run, so programs can be proven from within other programs.
+        let previous = EXECUTION.with(|e| e.replace(Some(execution)));
+        let result = catch_unwind(program);
+        let execution = EXECUTION.with(|e| e.replace(previous)).unwrap_or_default();
+
+        if result.is_err() {
+            return Err(anyhow!("Mock program panicked"));
+        }
+        self.is_proven = true;
+
+        Ok(MockProof {
+            img_id: mock_img_id(&self.elf),
+            journal: execution.journal,
+        })
+    }
+
+    fn stats(&self) -> Option<ProvingStats> {
+        //Programs run natively, so there are no cycles to count.
+        self.is_proven.then(ProvingStats::default)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockProof {
+    pub img_id: StatementDigest,
+    /// Outputs committed by the program, in order.
+    pub journal: Vec<Vec<u8>>,
+}
+
+impl ZKVMProof for MockProof {
+    fn verify(
+        &self,
+        img_id: Option<[u8; 32]>,
+        elf: Option<Vec<u8>>,
+        _: ProverMode,
+    ) -> Result<(), Error> {
+        let img_id = match (img_id, elf) {
+            (Some(i), _) => i,
+            (None, Some(elf)) => mock_img_id(&elf).to_le_bytes(),
+            (None, None) => return Err(anyhow!("Image ID or ELF is required")),
+        };
+
+        if self.img_id.to_le_bytes() != img_id {
+            return Err(anyhow!("Proof not generated for the given image ID"));
+        }
+
+        if self.journal.is_empty() {
+            return Err(anyhow!("Program did not commit any output"));
+        }
+
+        Ok(())
+    }
+
+    fn public_inputs<V: Serialize + DeserializeOwned + Clone>(&mut self) -> Result<V, Error> {
+        match self.journal.first() {
+            Some(i) => Ok(bincode::deserialize(i)?),
+            None => Err(anyhow!("Program did not commit any output")),
+        }
+    }
+
+    fn compress(&mut self) -> Result<MockProof, Error> {
+        Ok(self.clone())
+    }
+
+    fn wrap_groth16(&mut self) -> Result<MockProof, Error> {
+        Ok(self.clone())
+    }
+
+    fn evm_seal(&self) -> Result<Vec<u8>, Error> {
+        Err(anyhow!("Mock proofs cannot be verified on EVM"))
+    }
+}
+
+impl TryInto<Proof> for MockProof {
+    type Error = Error;
+
+    fn try_into(self) -> Result<Proof, Self::Error> {
+        let data = bincode::serialize(&self)?;
+
+        Ok(Proof::from(ProofEnvelope::new(
+            ZKVMBackend::Mock,
+            ReceiptKind::Fake,
+            self.img_id,
+            data,
+        )))
+    }
+}
+
+impl TryFrom<Proof> for MockProof {
+    type Error = Error;
+
+    fn try_from(value: Proof) -> Result<Self, Self::Error> {
+        let envelope = value.envelope()?;
+        envelope.check_backend(ZKVMBackend::Mock)?;
+
+        Ok(bincode::deserialize(&envelope.data)?)
+    }
+}
+
+/// Environment of programs run by `MockProver`.
+pub struct MockZKVM;
+
+impl ZKVMEnv for MockZKVM {
+    const BACKEND: ZKVMBackend = ZKVMBackend::Mock;
+
+    fn read_input<T: DeserializeOwned>() -> Result<T, Error> {
+        let input = EXECUTION.with(|e| e.borrow_mut().as_mut()?.inputs.pop_front());
+
+        match input {
+            Some(i) => Ok(bincode::deserialize(&i)?),
+            None => Err(anyhow!("No input left to read")),
+        }
+    }
+
+    fn verify<T: Serialize>(img_id: [u32; 8], public_inputs: &T) -> Result<(), Error> {
+        let encoded = bincode::serialize(public_inputs)?;
+        let is_proven = EXECUTION.with(|e| match e.borrow().as_ref() {
+            Some(execution) => execution
+                .assumptions
+                .iter()
+                .any(|proof| proof.img_id.0 == img_id && proof.journal.first() == Some(&encoded)),
+            None => false,
+        });
+
+        if !is_proven {
+            return Err(anyhow!("No recursed proof for the public inputs"));
+        }
+
+        Ok(())
+    }
+
+    fn is_proving() -> bool {
+        EXECUTION.with(|e| e.borrow().is_some())
+    }
+
+    fn commit<T: Serialize>(data: &T) {
+        let encoded = bincode::serialize(data).expect("Committed data should be serializable");
+
+        EXECUTION.with(|e| {
+            if let Some(execution) = e.borrow_mut().as_mut() {
+                execution.journal.push(encoded);
+            }
+        });
+    }
+}
@@ -1,7 +1,25 @@
+use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(any(feature = "native"))]
+use utoipa::ToSchema;
 
+pub mod foreign;
 pub mod traits;
 
+#[cfg(any(
+    feature = "native-risc0",
+    feature = "native-sp1",
+    feature = "native-mock"
+))]
+pub mod any;
+
+#[cfg(any(feature = "native-mock"))]
+pub mod mock;
+
+#[cfg(any(feature = "native"))]
+pub mod proving;
+
 #[cfg(any(feature = "native-risc0", feature = "zkvm-risc0"))]
 pub mod risczero;
 
@@ -15,3 +33,57 @@ pub enum ProverMode {
     NoAggregation,
     MockProof,
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "native", derive(ToSchema))]
+pub enum ZKVMBackend {
+    Risc0,
+    Sp1,
+    /// Proofs without cryptographic guarantees, for development only.
+    Mock,
+}
+
+/// Kind of receipt a proof is, which decides whether it can be recursed or verified on chain.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "native", derive(ToSchema))]
+pub enum ReceiptKind {
+    Composite,
+    Succinct,
+    Groth16,
+    Plonk,
+    /// Dev mode receipt, not backed by a real proof.
+    Fake,
+}
+
+/// Guest regions whose cycles are tracked while proving a Nexus batch.
+pub const PRE_STATE_REGION: &str = "pre_state";
+pub const STF_REGION: &str = "stf";
+pub const RECURSION_REGION: &str = "recursion";
+
+/// Cycles used by a proven program.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProvingStats {
+    pub total_cycles: u64,
+    pub segments: u64,
+    /// Cycles of the tracked guest regions, summed over every time a region was entered.
+    pub regions: BTreeMap<String, u64>,
+}
+
+impl ProvingStats {
+    pub fn region(&self, name: &str) -> u64 {
+        self.regions.get(name).cloned().unwrap_or(0)
+    }
+}
+
+impl std::str::FromStr for ZKVMBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "risc0" => Ok(ZKVMBackend::Risc0),
+            "sp1" => Ok(ZKVMBackend::Sp1),
+            "mock" => Ok(ZKVMBackend::Mock),
+            _ => Err(anyhow::anyhow!("Unknown zkVM backend {}", s)),
+        }
+    }
+}
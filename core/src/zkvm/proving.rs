@@ -0,0 +1,117 @@
+use anyhow::{anyhow, Error};
+use std::fmt;
+use std::future::pending;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::time::{sleep, Duration};
+
+/// Stage a prover is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingPhase {
+    /// Running the program to find its cycles and segments.
+    Executing,
+    Proving,
+    Done,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProvingProgress {
+    Phase(ProvingPhase),
+    Segments { done: u64, total: u64 },
+}
+
+pub type ProgressCallback = Arc<dyn Fn(ProvingProgress) + Send + Sync>;
+
+/// Controls of a proof generated with [`prove_async`].
+#[derive(Clone, Default)]
+pub struct ProvingOptions {
+    /// Proving is abandoned once the value turns true, e.g. the node's shutdown signal.
+    pub cancel: Option<watch::Receiver<bool>>,
+    pub timeout: Option<Duration>,
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl ProvingOptions {
+    pub fn report(&self, progress: ProvingProgress) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(progress);
+        }
+    }
+}
+
+/// Why a proof was not generated by [`prove_async`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingInterrupted {
+    Cancelled,
+    TimedOut(Duration),
+}
+
+impl fmt::Display for ProvingInterrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProvingInterrupted::Cancelled => write!(f, "Proving cancelled"),
+            ProvingInterrupted::TimedOut(timeout) => {
+                write!(f, "Proving timed out after {:?}", timeout)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProvingInterrupted {}
+
+/// Runs `prove` on the blocking pool, so async callers are not stalled while proving.
+/// Provers are built inside `prove`, as not all of them can be sent across threads.
+///
+/// Returns [`ProvingInterrupted`] as soon as the proof is cancelled or times out. Backends
+/// cannot be stopped mid-proof, so the proving thread runs to completion in the background
+/// and its result is dropped.
+pub async fn prove_async<R, F>(prove: F, options: ProvingOptions) -> Result<R, Error>
+where
+    R: Send + 'static,
+    F: FnOnce(&dyn Fn(ProvingProgress)) -> Result<R, Error> + Send + 'static,
+{
+    let reporter = options.clone();
+    let task = tokio::task::spawn_blocking(move || prove(&|progress| reporter.report(progress)));
+
+    interruptible(
+        async move {
+            match task.await {
+                Ok(i) => i,
+                Err(e) => Err(anyhow!("Proving task failed: {:?}", e)),
+            }
+        },
+        &options,
+    )
+    .await
+}
+
+/// Awaits `future` unless cancelled or timed out first, as configured in `options`.
+pub async fn interruptible<R>(
+    future: impl std::future::Future<Output = Result<R, Error>>,
+    options: &ProvingOptions,
+) -> Result<R, Error> {
+    let mut cancel = options.cancel.clone();
+    let cancelled = async move {
+        match cancel.as_mut() {
+            Some(cancel) => {
+                //A dropped sender can no longer cancel.
+                if cancel.wait_for(|cancelled| *cancelled).await.is_err() {
+                    pending::<()>().await;
+                }
+            }
+            None => pending::<()>().await,
+        }
+    };
+    let timed_out = async move {
+        match options.timeout {
+            Some(timeout) => sleep(timeout).await,
+            None => pending::<()>().await,
+        }
+    };
+
+    tokio::select! {
+        result = future => result,
+        _ = cancelled => Err(ProvingInterrupted::Cancelled.into()),
+        _ = timed_out => Err(ProvingInterrupted::TimedOut(options.timeout.unwrap_or_default()).into()),
+    }
+}
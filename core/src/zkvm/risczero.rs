@@ -1,9 +1,14 @@
 use crate::types::Proof;
+#[cfg(any(feature = "native-risc0"))]
+use crate::types::{ProofEnvelope, StatementDigest};
 
 use super::traits::ZKVMEnv;
 #[cfg(any(feature = "native-risc0"))]
 use super::traits::{ZKVMProof, ZKVMProver};
 use super::ProverMode;
+use super::ZKVMBackend;
+#[cfg(any(feature = "native-risc0"))]
+use super::{ProvingStats, ReceiptKind};
 use anyhow::anyhow;
 use anyhow::Error;
 use risc0_zkvm::guest::env;
@@ -11,15 +16,77 @@ use risc0_zkvm::serde::to_vec;
 #[cfg(any(feature = "native-risc0"))]
 use risc0_zkvm::ProverOpts;
 #[cfg(any(feature = "native-risc0"))]
-use risc0_zkvm::{default_prover, Executor, ExecutorEnv, ExecutorEnvBuilder, Prover};
+use risc0_zkvm::{
+    default_prover, sha, sha::Digestible, Executor, ExecutorEnv, ExecutorEnvBuilder, InnerReceipt,
+    Prover,
+};
 use risc0_zkvm::{serde::from_slice, Receipt};
 use serde::{Deserialize, Serialize};
+#[cfg(any(feature = "native-risc0"))]
+use std::collections::{BTreeMap, HashMap};
+#[cfg(any(feature = "native-risc0"))]
+use std::io::Write;
+#[cfg(any(feature = "native-risc0"))]
+use std::sync::{Arc, Mutex};
 
 #[cfg(any(feature = "native-risc0"))]
 pub struct RiscZeroProver<'a> {
     env_builder: ExecutorEnvBuilder<'a>,
     elf: Vec<u8>,
     prover_mode: ProverMode,
+    stats: Option<ProvingStats>,
+}
+
+/// Guest stdout, where cycle counts of tracked regions are written.
+#[cfg(any(feature = "native-risc0"))]
+#[derive(Clone, Default)]
+struct GuestOutput(Arc<Mutex<Vec<u8>>>);
+
+#[cfg(any(feature = "native-risc0"))]
+impl Write for GuestOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.0.lock() {
+            Ok(mut i) => i.write(buf),
+            Err(_) => Err(std::io::Error::other("Guest output poisoned")),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Sums the cycles of every region written by the guest, nested or repeated.
+#[cfg(any(feature = "native-risc0"))]
+fn region_cycles(output: &[u8]) -> BTreeMap<String, u64> {
+    let mut starts: HashMap<String, Vec<u64>> = HashMap::new();
+    let mut regions: BTreeMap<String, u64> = BTreeMap::new();
+
+    for line in String::from_utf8_lossy(output).lines() {
+        let (is_start, marker) = if let Some(i) = line.strip_prefix(REGION_START) {
+            (true, i)
+        } else if let Some(i) = line.strip_prefix(REGION_END) {
+            (false, i)
+        } else {
+            continue;
+        };
+
+        let (name, cycles) = match marker.rsplit_once(' ') {
+            Some((name, cycles)) => match cycles.parse::<u64>() {
+                Ok(i) => (name.to_string(), i),
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+
+        if is_start {
+            starts.entry(name).or_default().push(cycles);
+        } else if let Some(start) = starts.get_mut(&name).and_then(|i| i.pop()) {
+            *regions.entry(name).or_default() += cycles.saturating_sub(start);
+        }
+    }
+
+    regions
 }
 
 #[cfg(any(feature = "native-risc0"))]
@@ -30,6 +97,7 @@ impl<'a> ZKVMProver<RiscZeroProof> for RiscZeroProver<'a> {
             env_builder,
             elf,
             prover_mode,
+            stats: None,
         }
     }
 
@@ -47,7 +115,12 @@ impl<'a> ZKVMProver<RiscZeroProof> for RiscZeroProver<'a> {
         let start_time = std::time::Instant::now(); // Start time measurement
 
         //let env_1: ExecutorEnv = self.env_builder.clone().build().map_err(|e| anyhow!(e))?;
-        let env: ExecutorEnv = self.env_builder.build().map_err(|e| anyhow!(e))?;
+        let output = GuestOutput::default();
+        let env: ExecutorEnv = self
+            .env_builder
+            .stdout(output.clone())
+            .build()
+            .map_err(|e| anyhow!(e))?;
 
         let prover = default_prover();
 
@@ -73,8 +146,22 @@ impl<'a> ZKVMProver<RiscZeroProof> for RiscZeroProver<'a> {
         println!("Prover stats: {:?}", receipt.stats);
         println!("Proof generation completed in: {:?}", duration); // Log the elapsed time
 
+        let regions = match output.0.lock() {
+            Ok(i) => region_cycles(&i),
+            Err(_) => BTreeMap::new(),
+        };
+        self.stats = Some(ProvingStats {
+            total_cycles: receipt.stats.total_cycles,
+            segments: receipt.stats.segments as u64,
+            regions,
+        });
+
         Ok(RiscZeroProof(receipt.receipt))
     }
+
+    fn stats(&self) -> Option<ProvingStats> {
+        self.stats.clone()
+    }
 }
 
 #[cfg(any(feature = "native-risc0"))]
@@ -103,11 +190,55 @@ impl ZKVMProof for RiscZeroProof {
     }
 
     fn compress(&mut self) -> Result<RiscZeroProof, anyhow::Error> {
+        let prover = default_prover();
+        let prover_opts = ProverOpts::succinct();
+        let new_proof = prover.compress(&prover_opts, &self.0.clone())?;
+        Ok(RiscZeroProof(new_proof))
+    }
+
+    fn wrap_groth16(&mut self) -> Result<RiscZeroProof, anyhow::Error> {
         let prover = default_prover();
         let prover_opts = ProverOpts::groth16();
         let new_proof = prover.compress(&prover_opts, &self.0.clone())?;
         Ok(RiscZeroProof(new_proof))
     }
+
+    fn evm_seal(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let InnerReceipt::Groth16(receipt) = &self.0.inner else {
+            return Err(anyhow!("Only Groth16 receipts can be verified on EVM"));
+        };
+
+        //Selector of the verifier router, the first bytes of the verifier parameters.
+        Ok([
+            &receipt.verifier_parameters.as_bytes()[..4],
+            receipt.seal.as_slice(),
+        ]
+        .concat())
+    }
+}
+
+#[cfg(any(feature = "native-risc0"))]
+impl RiscZeroProof {
+    pub fn kind(&self) -> Result<ReceiptKind, anyhow::Error> {
+        match &self.0.inner {
+            InnerReceipt::Composite(_) => Ok(ReceiptKind::Composite),
+            InnerReceipt::Succinct(_) => Ok(ReceiptKind::Succinct),
+            InnerReceipt::Groth16(_) => Ok(ReceiptKind::Groth16),
+            InnerReceipt::Fake(_) => Ok(ReceiptKind::Fake),
+            _ => Err(anyhow!("Unsupported receipt kind")),
+        }
+    }
+
+    /// Image ID of the guest the receipt was generated for.
+    pub fn img_id(&self) -> Result<StatementDigest, anyhow::Error> {
+        let claim = self.0.claim()?.value()?;
+        let digest = claim.pre.digest::<sha::Impl>();
+        let mut img_id = [0u32; 8];
+
+        img_id.copy_from_slice(digest.as_words());
+
+        Ok(StatementDigest(img_id))
+    }
 }
 
 #[cfg(any(feature = "native-risc0"))]
@@ -123,7 +254,13 @@ impl TryInto<Proof> for RiscZeroProof {
             .into_iter()
             .flat_map(|x| x.to_ne_bytes().to_vec())
             .collect();
-        Ok(Proof(encoded_u8))
+
+        Ok(Proof::from(ProofEnvelope::new(
+            ZKVMBackend::Risc0,
+            self.kind()?,
+            self.img_id()?,
+            encoded_u8,
+        )))
     }
 }
 
@@ -132,15 +269,23 @@ impl TryFrom<Proof> for RiscZeroProof {
     type Error = anyhow::Error;
 
     fn try_from(value: Proof) -> Result<Self, Self::Error> {
-        let receipt: Receipt = from_slice(&value.0)?;
+        let envelope = value.envelope()?;
+        envelope.check_backend(ZKVMBackend::Risc0)?;
+
+        let receipt: Receipt = from_slice(&envelope.data)?;
 
         Ok(Self(receipt))
     }
 }
 
+const REGION_START: &str = "cycle-region-start: ";
+const REGION_END: &str = "cycle-region-end: ";
+
 pub struct ZKVM();
 
 impl ZKVMEnv for ZKVM {
+    const BACKEND: ZKVMBackend = ZKVMBackend::Risc0;
+
     fn read_input<T: serde::de::DeserializeOwned>() -> Result<T, anyhow::Error> {
         Ok(env::read())
     }
@@ -160,10 +305,17 @@ impl ZKVMEnv for ZKVM {
     fn commit<T: serde::Serialize>(data: &T) {
         env::commit(data);
     }
-}
 
-#[cfg(any(feature = "native-risc0"))]
-pub trait ProofConversion: std::convert::From<RiscZeroProof> {}
+    fn start_region(name: &str) {
+        //Also run natively by the node, where there are no cycles to count.
+        if cfg!(target_os = "zkvm") {
+            println!("{}{} {}", REGION_START, name, env::cycle_count());
+        }
+    }
 
-#[cfg(any(feature = "native-risc0"))]
-impl ProofConversion for RiscZeroProof {}
+    fn end_region(name: &str) {
+        if cfg!(target_os = "zkvm") {
+            println!("{}{} {}", REGION_END, name, env::cycle_count());
+        }
+    }
+}
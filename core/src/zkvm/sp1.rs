@@ -1,8 +1,15 @@
+#[cfg(any(feature = "native-sp1"))]
+use super::proving::{ProvingPhase, ProvingProgress};
 use super::traits::ZKVMEnv;
 #[cfg(any(feature = "native-sp1"))]
 use super::traits::{ZKVMProof, ZKVMProver};
 use super::ProverMode;
+use super::ZKVMBackend;
+#[cfg(any(feature = "native-sp1"))]
+use super::{ProvingStats, ReceiptKind};
 use crate::types::Proof;
+#[cfg(any(feature = "native-sp1"))]
+use crate::types::{ProofEnvelope, StatementDigest};
 use anyhow::anyhow;
 use anyhow::Error;
 use bincode;
@@ -13,11 +20,17 @@ use serde_json::{from_slice, to_vec};
 use sha2::Digest;
 use sha2::Sha256;
 #[cfg(any(feature = "native-sp1"))]
+use sp1_sdk::install::try_install_circuit_artifacts;
+#[cfg(any(feature = "native-sp1"))]
 use sp1_sdk::{
-    utils, HashableKey, ProverClient, SP1Proof, SP1ProofWithPublicValues, SP1Prover, SP1ProvingKey,
-    SP1PublicValues, SP1Stdin, SP1VerifyingKey,
+    utils, HashableKey, ProverClient, SP1Context, SP1Proof, SP1ProofWithPublicValues, SP1Prover,
+    SP1ProvingKey, SP1PublicValues, SP1Stdin, SP1VerifyingKey, SP1_CIRCUIT_VERSION,
 };
+#[cfg(any(feature = "native-sp1"))]
+use sp1_stark::{SP1CoreOpts, SP1ProverOpts};
 use std::borrow::Cow;
+#[cfg(any(feature = "native-sp1"))]
+use std::collections::BTreeMap;
 
 #[cfg(any(feature = "native-sp1"))]
 pub struct Sp1Prover {
@@ -27,6 +40,7 @@ pub struct Sp1Prover {
     prover_mode: ProverMode,
     pk: SP1ProvingKey,
     vk: SP1VerifyingKey,
+    stats: Option<ProvingStats>,
 }
 
 #[cfg(any(feature = "native-sp1"))]
@@ -34,6 +48,52 @@ impl Sp1Prover {
     pub fn vk(&self) -> [u32; 8] {
         self.vk.hash_u32()
     }
+
+    /// Proves with the local prover, taking the cycles and shards from the core proof. The
+    /// core prover does not report the cycles of guest regions, which are left empty.
+    fn prove_locally(
+        &self,
+        stdin: SP1Stdin,
+    ) -> Result<(SP1ProofWithPublicValues, ProvingStats), anyhow::Error> {
+        let prover = <SP1Prover>::new();
+        let opts = SP1ProverOpts::default();
+        let core_proof = prover.prove_core(&self.pk, &stdin, opts, SP1Context::default())?;
+        let stats = ProvingStats {
+            total_cycles: core_proof.cycles,
+            segments: core_proof.proof.0.len() as u64,
+            regions: BTreeMap::new(),
+        };
+        let public_values = core_proof.public_values.clone();
+
+        let proof = match &self.prover_mode {
+            ProverMode::NoAggregation => SP1Proof::Core(core_proof.proof.0),
+            _ => {
+                let deferred_proofs = stdin.proofs.iter().map(|(p, _)| p.clone()).collect();
+                let reduced_proof = prover.compress(&self.vk, core_proof, deferred_proofs, opts)?;
+
+                match &self.prover_mode {
+                    ProverMode::Groth16 => {
+                        let shrunk_proof = prover.shrink(reduced_proof, opts)?;
+                        let outer_proof = prover.wrap_bn254(shrunk_proof, opts)?;
+                        let artifacts = try_install_circuit_artifacts("groth16");
+
+                        SP1Proof::Groth16(prover.wrap_groth16_bn254(outer_proof, &artifacts))
+                    }
+                    _ => SP1Proof::Compressed(Box::new(reduced_proof)),
+                }
+            }
+        };
+
+        Ok((
+            SP1ProofWithPublicValues {
+                proof,
+                stdin,
+                public_values,
+                sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+            },
+            stats,
+        ))
+    }
 }
 
 #[cfg(any(feature = "native-sp1"))]
@@ -52,6 +112,7 @@ impl ZKVMProver<Sp1Proof> for Sp1Prover {
             prover_mode,
             pk,
             vk,
+            stats: None,
         }
     }
 
@@ -72,46 +133,89 @@ impl ZKVMProver<Sp1Proof> for Sp1Prover {
     }
 
     fn prove(&mut self) -> Result<Sp1Proof, anyhow::Error> {
-        let mut sp1_input = self.sp1_standard_input.clone();
-
-        let proof = match &self.prover_mode {
-            // ProverMode::MockProof => {
-            //     let (output, stats) = self.sp1_client.execute(&self.elf, sp1_input).run().unwrap();
+        self.prove_with_progress(&|_| {})
+    }
 
-            //     Sp1Proof::Mock(output)
-            // }
-            ProverMode::Compressed => Sp1Proof(
-                self.sp1_client
-                    .prove(&self.pk, sp1_input)
-                    .compressed()
-                    .run()
-                    .expect("proof generation failed"),
-            ),
-            ProverMode::MockProof => Sp1Proof(
-                self.sp1_client
+    fn prove_with_progress(
+        &mut self,
+        progress: &dyn Fn(ProvingProgress),
+    ) -> Result<Sp1Proof, anyhow::Error> {
+        let sp1_input = self.sp1_standard_input.clone();
+        let img_id = StatementDigest(self.vk());
+
+        let (proof, stats) = match &self.prover_mode {
+            //The mock prover only executes the program, but does not report its cycles.
+            ProverMode::MockProof => {
+                progress(ProvingProgress::Phase(ProvingPhase::Executing));
+                let (_, report) = self
+                    .sp1_client
+                    .execute(&self.elf, sp1_input.clone())
+                    .run()?;
+                let total_cycles = report.total_instruction_count();
+                let stats = ProvingStats {
+                    total_cycles,
+                    segments: total_cycles.div_ceil(SP1CoreOpts::default().shard_size as u64),
+                    regions: report.cycle_tracker.into_iter().collect(),
+                };
+
+                progress(ProvingProgress::Phase(ProvingPhase::Proving));
+                let proof = self
+                    .sp1_client
                     .prove(&self.pk, sp1_input)
                     .compressed()
-                    .run()
-                    .expect("proof generation failed"),
-            ),
-            _ => Sp1Proof(
-                self.sp1_client
-                    .prove(&self.pk, sp1_input)
-                    .run()
-                    .expect("proof generation failed"),
-            ),
+                    .run()?;
+
+                (proof, stats)
+            }
+            //Regions are tracked by executing the program once before proving it.
+            _ => {
+                progress(ProvingProgress::Phase(ProvingPhase::Executing));
+                let (_, report) = self
+                    .sp1_client
+                    .execute(&self.elf, sp1_input.clone())
+                    .run()?;
+
+                progress(ProvingProgress::Phase(ProvingPhase::Proving));
+                let (proof, mut stats) = self.prove_locally(sp1_input)?;
+                stats.regions = report.cycle_tracker.into_iter().collect();
+
+                (proof, stats)
+            }
         };
+        progress(ProvingProgress::Segments {
+            done: stats.segments,
+            total: stats.segments,
+        });
+        progress(ProvingProgress::Phase(ProvingPhase::Done));
+        self.stats = Some(stats);
+
+        Ok(Sp1Proof(proof, img_id))
+    }
 
-        Ok(proof)
+    fn stats(&self) -> Option<ProvingStats> {
+        self.stats.clone()
     }
 }
 // #[cfg(any(feature = "native-sp1"))]
 // #[derive(Debug, Clone, Serialize, Deserialize)]
 // pub struct Sp1Proof(pub SP1ProofWithPublicValues);
 
+/// SP1 proof along with the verifying key hash of the program it was generated for.
 #[cfg(any(feature = "native-sp1"))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Sp1Proof(SP1ProofWithPublicValues);
+pub struct Sp1Proof(SP1ProofWithPublicValues, StatementDigest);
+
+#[cfg(any(feature = "native-sp1"))]
+impl Sp1Proof {
+    pub fn kind(&self) -> ReceiptKind {
+        match &self.0.proof {
+            SP1Proof::Core(_) => ReceiptKind::Composite,
+            SP1Proof::Compressed(_) => ReceiptKind::Succinct,
+            SP1Proof::Groth16(_) => ReceiptKind::Groth16,
+            SP1Proof::Plonk(_) => ReceiptKind::Plonk,
+        }
+    }
+}
 
 #[cfg(any(feature = "native-sp1"))]
 impl ZKVMProof for Sp1Proof {
@@ -131,31 +235,65 @@ impl ZKVMProof for Sp1Proof {
         elf: Option<Vec<u8>>,
         proof_mode: ProverMode,
     ) -> Result<(), anyhow::Error> {
-        let elf = match elf {
-            Some(elf) => elf,
-            None => return Err(anyhow!("ELF is required")),
-        };
         let sp1_client = match proof_mode {
             ProverMode::MockProof => ProverClient::mock(),
             _ => ProverClient::local(),
         };
-        //TODO: Change this to also accept vk instead of the elf file.
-        let (_, vk) = sp1_client.setup(&elf);
+        let vk = match (elf, img_id, &self.0.proof) {
+            (Some(elf), _, _) => sp1_client.setup(&elf).1,
+            //Compressed proofs carry their verifying key, which has to hash to the image ID.
+            (None, Some(img_id), SP1Proof::Compressed(p)) => {
+                let vk = SP1VerifyingKey { vk: p.vk.clone() };
+
+                if StatementDigest(vk.hash_u32()).to_le_bytes() != img_id {
+                    return Err(anyhow!("Proof not generated for the given image ID"));
+                }
+
+                vk
+            }
+            _ => return Err(anyhow!("ELF is required")),
+        };
         sp1_client.verify(&self.0, &vk)?;
         Ok(())
     }
 
     fn compress(&mut self) -> Result<Sp1Proof, anyhow::Error> {
-        let mut new_proof = self.clone();
-
-        if let Some(groth16_proof) = &self.0.proof.clone().try_as_groth_16() {
-            new_proof.0.proof = SP1Proof::Groth16(groth16_proof.clone());
-        } else {
-            return Err(anyhow::anyhow!("Failed to create groth16 proof"));
+        match &self.0.proof {
+            SP1Proof::Core(_) => Err(anyhow!(
+                "Core proofs need the proving key to be compressed, prove in compressed mode"
+            )),
+            _ => Ok(self.clone()),
         }
+    }
+
+    fn wrap_groth16(&mut self) -> Result<Sp1Proof, anyhow::Error> {
+        let reduced_proof = match &self.0.proof {
+            SP1Proof::Compressed(p) => *p.clone(),
+            SP1Proof::Groth16(_) => return Ok(self.clone()),
+            _ => return Err(anyhow!("Only compressed proofs can be wrapped in Groth16")),
+        };
+
+        let prover = <SP1Prover>::new();
+        let opts = SP1ProverOpts::default();
+        let shrunk_proof = prover.shrink(reduced_proof, opts)?;
+        let outer_proof = prover.wrap_bn254(shrunk_proof, opts)?;
+        let artifacts = try_install_circuit_artifacts("groth16");
+
+        let mut new_proof = self.clone();
+        new_proof.0.proof = SP1Proof::Groth16(prover.wrap_groth16_bn254(outer_proof, &artifacts));
 
         Ok(new_proof)
     }
+
+    fn evm_seal(&self) -> Result<Vec<u8>, anyhow::Error> {
+        match &self.0.proof {
+            //Prefixed with the selector of the verifier gateway.
+            SP1Proof::Groth16(_) | SP1Proof::Plonk(_) => Ok(self.0.bytes()),
+            _ => Err(anyhow!(
+                "Only Groth16 and Plonk proofs can be verified on EVM"
+            )),
+        }
+    }
 }
 
 #[cfg(any(feature = "native-sp1"))]
@@ -163,8 +301,11 @@ impl TryFrom<Proof> for Sp1Proof {
     type Error = anyhow::Error;
 
     fn try_from(value: Proof) -> Result<Self, Self::Error> {
-        let receipt: Sp1Proof = from_slice(&value.0)?;
-        Ok(receipt)
+        let envelope = value.envelope()?;
+        envelope.check_backend(ZKVMBackend::Sp1)?;
+
+        let receipt: SP1ProofWithPublicValues = from_slice(&envelope.data)?;
+        Ok(Sp1Proof(receipt, envelope.img_id))
     }
 }
 
@@ -185,8 +326,14 @@ impl TryInto<Proof> for Sp1Proof {
 
     fn try_into(self) -> Result<Proof, Self::Error> {
         let encoded_u8: Vec<u8> =
-            to_vec(&self).map_err(|e| anyhow::anyhow!("Serialization error: {}", e))?;
-        Ok(Proof(encoded_u8))
+            to_vec(&self.0).map_err(|e| anyhow::anyhow!("Serialization error: {}", e))?;
+
+        Ok(Proof::from(ProofEnvelope::new(
+            ZKVMBackend::Sp1,
+            self.kind(),
+            self.1,
+            encoded_u8,
+        )))
     }
 }
 
@@ -206,6 +353,8 @@ fn serialize_to_data<T: Serialize>(input: &T) -> Result<SerializedData, Error> {
 pub struct SP1ZKVM();
 
 impl ZKVMEnv for SP1ZKVM {
+    const BACKEND: ZKVMBackend = ZKVMBackend::Sp1;
+
     fn read_input<T: DeserializeOwned>() -> Result<T, anyhow::Error> {
         Ok(sp1_zkvm::io::read::<T>())
     }
@@ -226,10 +375,17 @@ impl ZKVMEnv for SP1ZKVM {
         let byte_slice: &[u8] = serialized_data.as_ref();
         sp1_zkvm::io::commit_slice(byte_slice);
     }
-}
 
-#[cfg(any(feature = "native-sp1"))]
-pub trait ProofConversion: std::convert::From<Sp1Proof> {}
+    //Picked up by the executor, which sums the cycles of each region in its report.
+    fn start_region(name: &str) {
+        if cfg!(target_os = "zkvm") {
+            println!("cycle-tracker-report-start: {}", name);
+        }
+    }
 
-#[cfg(any(feature = "native-sp1"))]
-impl ProofConversion for Sp1Proof {}
+    fn end_region(name: &str) {
+        if cfg!(target_os = "zkvm") {
+            println!("cycle-tracker-report-end: {}", name);
+        }
+    }
+}
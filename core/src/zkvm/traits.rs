@@ -1,14 +1,39 @@
 use crate::types::Proof as NexusProof;
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::ProverMode;
+#[cfg(any(feature = "native"))]
+use super::proving::{ProvingPhase, ProvingProgress};
+use super::{ProverMode, ProvingStats, ZKVMBackend};
 
+/// Proving is blocking on purpose, as not every prover can be sent across threads. Async
+/// callers build the prover inside [`prove_async`](super::proving::prove_async), which runs
+/// it on the blocking pool with cancellation, a timeout and progress reporting.
 #[cfg(any(feature = "native"))]
 pub trait ZKVMProver<R: ZKVMProof> {
     fn new(elf: Vec<u8>, prover_mode: ProverMode) -> Self;
     fn add_input<T: Serialize>(&mut self, input: &T) -> Result<(), anyhow::Error>;
     fn add_proof_for_recursion(&mut self, proof: R) -> Result<(), anyhow::Error>;
     fn prove(&mut self) -> Result<R, anyhow::Error>;
+    /// Same as `prove`, reporting progress on the way. Backends that can tell more
+    /// than start and end override it.
+    fn prove_with_progress(
+        &mut self,
+        progress: &dyn Fn(ProvingProgress),
+    ) -> Result<R, anyhow::Error> {
+        progress(ProvingProgress::Phase(ProvingPhase::Proving));
+        let proof = self.prove()?;
+        if let Some(stats) = self.stats() {
+            progress(ProvingProgress::Segments {
+                done: stats.segments,
+                total: stats.segments,
+            });
+        }
+        progress(ProvingProgress::Phase(ProvingPhase::Done));
+
+        Ok(proof)
+    }
+    /// Cycles used by the last proven program.
+    fn stats(&self) -> Option<ProvingStats>;
 }
 
 #[cfg(any(feature = "native"))]
@@ -22,7 +47,12 @@ pub trait ZKVMProof: Sized {
     fn public_inputs<V: Serialize + DeserializeOwned + Clone>(
         &mut self,
     ) -> Result<V, anyhow::Error>;
+    /// Compresses the proof into one that can be recursed.
     fn compress(&mut self) -> Result<Self, anyhow::Error>;
+    /// Wraps a compressed proof in Groth16, so it can be verified on EVM.
+    fn wrap_groth16(&mut self) -> Result<Self, anyhow::Error>;
+    /// Seal of a Groth16 proof, as expected by the EVM verifier of the backend.
+    fn evm_seal(&self) -> Result<Vec<u8>, anyhow::Error>;
 }
 
 // pub trait ZKProof {
@@ -31,7 +61,17 @@ pub trait ZKVMProof: Sized {
 // }
 
 pub trait ZKVMEnv {
+    const BACKEND: ZKVMBackend;
+
     fn verify<T: Serialize>(img_id: [u32; 8], public_inputs: &T) -> Result<(), anyhow::Error>;
     fn read_input<T: DeserializeOwned>() -> Result<T, anyhow::Error>;
     fn commit<T: Serialize>(data: &T);
+    /// Whether the runtime is being proven, so recursed proofs can be verified. Native
+    /// execution of a guest is not.
+    fn is_proving() -> bool {
+        !cfg!(feature = "native")
+    }
+    /// Marks the start of a guest region whose cycles are reported to the prover.
+    fn start_region(_name: &str) {}
+    fn end_region(_name: &str) {}
 }
@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 
+use crate::runtime::NEXUS_RUNTIME_VERSION;
 use crate::state::types::AccountState;
 use crate::stf::StateTransitionFunction;
 use crate::types::{
     AvailHeader, HeaderStore, NexusHeader, Sha256, StateUpdate, TransactionZKVM, H256,
 };
 use crate::utils::hasher::{Digest, ShaHasher};
-use crate::zkvm::traits::ZKVMEnv;
+use crate::zkvm::{traits::ZKVMEnv, PRE_STATE_REGION, STF_REGION};
 use jmt::{KeyHash, RootHash};
 use serde::Serialize;
 
@@ -28,13 +29,19 @@ impl<Z: ZKVMEnv> ZKVMStateMachine<Z> {
         txs: &Vec<TransactionZKVM>,
         state_update: StateUpdate,
     ) -> Result<NexusHeader, anyhow::Error> {
-        let number: u32 = if let Some(first_header) = old_headers.first() {
-            first_header.number + 1
-        } else {
-            0
-        };
+        //Blocks of a newer runtime cannot be followed by ones of an older runtime.
+        if let Some(first_header) = old_headers.first() {
+            if first_header.runtime_version > NEXUS_RUNTIME_VERSION {
+                return Err(anyhow::anyhow!(
+                    "Runtime version {} cannot follow version {}",
+                    NEXUS_RUNTIME_VERSION,
+                    first_header.runtime_version
+                ));
+            }
+        }
 
         let mut pre_state: HashMap<[u8; 32], AccountState> = HashMap::new();
+        Z::start_region(PRE_STATE_REGION);
         if !txs.is_empty() {
             //TODO: Implement multiproof to avoid verifying each leaf.
             state_update
@@ -63,29 +70,53 @@ impl<Z: ZKVMEnv> ZKVMStateMachine<Z> {
                     },
                 )?
         }
+        Z::end_region(PRE_STATE_REGION);
 
+        Z::start_region(STF_REGION);
         let result = self
             .stf
             .execute_batch(new_avail_header, old_headers, txs, &pre_state)?;
+        Z::end_region(STF_REGION);
 
         //TODO verify post state root.
 
-        let txs_encoded: Vec<u8> = parity_scale_codec::Encode::encode(&txs);
+        Ok(batch_header(
+            new_avail_header,
+            old_headers,
+            txs,
+            &state_update,
+            NEXUS_RUNTIME_VERSION,
+        ))
+    }
+}
+
+/// Header of the block built from the batch by the given runtime version.
+pub fn batch_header(
+    new_avail_header: &AvailHeader,
+    old_headers: &HeaderStore,
+    txs: &Vec<TransactionZKVM>,
+    state_update: &StateUpdate,
+    runtime_version: u32,
+) -> NexusHeader {
+    let txs_encoded: Vec<u8> = parity_scale_codec::Encode::encode(&txs);
 
-        let mut hasher = ShaHasher::new();
-        hasher.0.update(&txs_encoded);
-        let tx_root = hasher.finish();
+    let mut hasher = ShaHasher::new();
+    hasher.0.update(&txs_encoded);
+    let tx_root = hasher.finish();
 
-        Ok(NexusHeader {
-            parent_hash: match old_headers.first() {
-                Some(i) => i.hash(),
-                None => H256::zero(),
-            },
-            number,
-            tx_root,
-            state_root: state_update.post_state_root,
-            prev_state_root: state_update.pre_state_root,
-            avail_header_hash: H256::from(new_avail_header.hash().as_fixed_slice().clone()),
-        })
+    NexusHeader {
+        parent_hash: match old_headers.first() {
+            Some(i) => i.hash(),
+            None => H256::zero(),
+        },
+        number: match old_headers.first() {
+            Some(i) => i.number + 1,
+            None => 0,
+        },
+        tx_root,
+        state_root: state_update.post_state_root,
+        prev_state_root: state_update.pre_state_root,
+        avail_header_hash: H256::from(new_avail_header.hash().as_fixed_slice().clone()),
+        runtime_version,
     }
 }
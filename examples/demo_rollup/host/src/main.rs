@@ -1,6 +1,9 @@
 use std::fmt::Debug;
 
-use adapter_sdk::{state::AdapterState, types::AdapterConfig};
+use adapter_sdk::{
+    state::AdapterState,
+    types::{AdapterConfig, SigningKeySource},
+};
 use demo_rollup_core::DemoProof;
 use methods::{ADAPTER_ELF, ADAPTER_ID};
 use nexus_core::{
@@ -8,7 +11,7 @@ use nexus_core::{
     zkvm::{
         risczero::{RiscZeroProof, ZKVM},
         traits::{ZKVMEnv, ZKVMProof, ZKVMProver},
-        ProverMode,
+        ProverMode, ZKVMBackend,
     },
 };
 
@@ -23,8 +26,16 @@ fn main() {
             vk: [0u8; 32],
             rollup_start_height: 606460,
             prover_mode: ProverMode::MockProof,
+            proving_system: Some(ZKVMBackend::Risc0),
             //TODO: Replace with configurable value.
             avail_url: String::from("wss://turing-rpc.avail.so:443/ws"),
+            nexus_url: String::from("http://127.0.0.1:7000"),
+            signing_key: SigningKeySource::DevSeed(String::from(
+                "0101010101010101010101010101010101010101010101010101010101010101",
+            )),
+            start_nexus_hash: None,
+            api_addr: String::from("127.0.0.1:3031"),
+            avail_signing_key: None,
         },
     );
     let rt = tokio::runtime::Runtime::new().unwrap();
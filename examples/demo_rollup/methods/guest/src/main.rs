@@ -8,6 +8,7 @@ use adapter_sdk::types::AdapterPublicInputs;
 use adapter_sdk::types::RollupProofWithPublicInputs;
 use demo_rollup_core::DemoProof;
 use nexus_core::types::StatementDigest;
+use nexus_core::zkvm::risczero::ZKVM;
 use risc0_zkvm::guest::env;
 use risc0_zkvm::sha::Digest;
 
@@ -20,7 +21,7 @@ fn main() {
     let img_id: StatementDigest = env::read();
     let vk: [u8; 32] = env::read();
 
-    let result = verify_proof(
+    let result = verify_proof::<DemoProof, ZKVM>(
         proof,
         prev_adapter_public_inputs,
         private_inputs,
@@ -1,4 +1,7 @@
-use adapter_sdk::{api::NexusAPI, types::AdapterConfig};
+use adapter_sdk::{
+    api::NexusAPI,
+    types::{AdapterConfig, SigningKeySource},
+};
 use anyhow::{Context, Error};
 use geth_methods::{ADAPTER_ELF, ADAPTER_ID};
 use nexus_core::db::NodeDB;
@@ -7,7 +10,7 @@ use nexus_core::types::{
     StatementDigest, SubmitProof, Transaction, TxParams, TxSignature, H256,
 };
 use nexus_core::zkvm::risczero::RiscZeroProof;
-use nexus_core::zkvm::ProverMode;
+use nexus_core::zkvm::{ProverMode, ZKVMBackend};
 use risc0_zkvm::guest::env;
 use risc0_zkvm::serde::to_vec;
 use risc0_zkvm::{default_prover, ExecutorEnv};
@@ -51,7 +54,6 @@ async fn main() -> Result<(), Error> {
     } else {
         ProverMode::Compressed
     };
-    let nexus_api = NexusAPI::new(&"http://127.0.0.1:7000");
 
     // Create or open the database
     let db_path = "db";
@@ -69,7 +71,15 @@ async fn main() -> Result<(), Error> {
             vk: [0u8; 32],
             rollup_start_height: 606460,
             prover_mode,
+            proving_system: Some(ZKVMBackend::Risc0),
             avail_url: String::from("wss://turing-rpc.avail.so:443/ws"),
+            nexus_url: String::from("http://127.0.0.1:7000"),
+            signing_key: SigningKeySource::DevSeed(String::from(
+                "0202020202020202020202020202020202020202020202020202020202020202",
+            )),
+            start_nexus_hash: None,
+            api_addr: String::from("127.0.0.1:3031"),
+            avail_signing_key: None,
         };
         AdapterStateData {
             last_height: 0,
@@ -77,6 +87,8 @@ async fn main() -> Result<(), Error> {
         }
     };
 
+    let nexus_api = NexusAPI::new(&adapter_state_data.adapter_config.nexus_url);
+
     // Main loop to fetch headers and run adapter
     let mut last_height = adapter_state_data.last_height;
     let mut start_nexus_hash = None;
@@ -125,7 +137,9 @@ async fn main() -> Result<(), Error> {
                             app_id: app_account_id.clone(),
                             statement: StatementDigest(ADAPTER_ID),
                             start_nexus_hash: range[0],
+                            proving_system: ZKVMBackend::Risc0,
                         }),
+                        signer: None,
                     };
                     match nexus_api.send_tx(tx).await {
                         Ok(i) => {
@@ -229,6 +243,7 @@ async fn main() -> Result<(), Error> {
                             height: public_inputs.height,
                             data: None,
                         }),
+                        signer: None,
                     };
 
                     match nexus_api.send_tx(tx).await {
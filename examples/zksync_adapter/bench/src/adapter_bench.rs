@@ -3,7 +3,7 @@ use nexus_core::types::{AccountState, AppAccountId, StatementDigest, H256 as Nex
 use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
 #[cfg(feature = "sp1")]
 use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
-use nexus_core::zkvm::ProverMode;
+use nexus_core::zkvm::{ProverMode, ZKVMBackend};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -42,6 +42,8 @@ fn create_mock_data() -> (
             start_nexus_hash: [2u8; 32],
             last_proof_height: 0,
             height: 0,
+            proving_system: ZKVMBackend::Risc0,
+            owner: [0; 32],
         },
     ));
     let new_rollup_proof = get_mock_proof();
@@ -252,6 +252,38 @@ impl STF {
     >(
         &self,
         //previous_adapter_pi: AdapterPublicInputs,
+        prev_adapter_proof: Option<P>,
+        init_account: Option<(AppAccountId, AccountState)>,
+        new_rollup_proof: Vec<String>,
+        new_rollup_pi: L1BatchWithMetadata,
+        pubdata_commitments: Vec<u8>,
+        versioned_hashes: Vec<[u8; 32]>,
+        nexus_hash: NexusH256,
+    ) -> Result<P, anyhow::Error>
+    where
+        <P as TryFrom<NexusProof>>::Error: std::fmt::Debug,
+    {
+        self.create_recursive_proof_with(
+            Z::new(self.elf.clone(), self.prover_mode.clone()),
+            prev_adapter_proof,
+            init_account,
+            new_rollup_proof,
+            new_rollup_pi,
+            pubdata_commitments,
+            versioned_hashes,
+            nexus_hash,
+        )
+    }
+
+    /// Same as `create_recursive_proof`, proving with the given prover, so the zkVM
+    /// backend can be selected at runtime.
+    #[cfg(any(feature = "native", feature = "risc0", feature = "sp1"))]
+    pub fn create_recursive_proof_with<
+        Z: ZKVMProver<P>,
+        P: ZKVMProof + Serialize + Clone + TryFrom<NexusProof>,
+    >(
+        &self,
+        mut prover: Z,
         mut prev_adapter_proof: Option<P>,
         init_account: Option<(AppAccountId, AccountState)>,
         new_rollup_proof: Vec<String>,
@@ -308,8 +340,6 @@ impl STF {
             self.prover_mode.clone(),
         )?;
 
-        let mut prover = Z::new(self.elf.clone(), self.prover_mode.clone());
-
         prover.add_input(&prev_adapter_pi)?;
         prover.add_input(&new_rollup_proof)?;
         prover.add_input(&new_rollup_pi)?;
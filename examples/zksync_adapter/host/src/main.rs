@@ -1,4 +1,7 @@
-use adapter_sdk::{api::NexusAPI, types::AdapterConfig};
+use adapter_sdk::{
+    api::NexusAPI,
+    types::{AdapterConfig, SigningKeySource},
+};
 use anyhow::{anyhow, Context, Error};
 use nexus_core::db::NodeDB;
 use nexus_core::types::{
@@ -6,14 +9,11 @@ use nexus_core::types::{
     StatementDigest, SubmitProof, Transaction, TxParams, TxSignature, H256,
 };
 
-#[cfg(feature = "risc0")]
-use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
-
+use nexus_core::zkvm::any::{AnyProof as Proof, AnyProver as Prover};
 #[cfg(feature = "sp1")]
-use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
-
+use nexus_core::zkvm::sp1::Sp1Prover;
 use nexus_core::zkvm::traits::{ZKVMProof, ZKVMProver};
-use nexus_core::zkvm::ProverMode;
+use nexus_core::zkvm::{ProverMode, ZKVMBackend};
 use proof_api::ProofAPIResponse;
 #[cfg(feature = "risc0")]
 use risc0_zkvm::guest::env;
@@ -41,6 +41,9 @@ use zksync_methods::{ZKSYNC_ADAPTER_ELF, ZKSYNC_ADAPTER_ID};
 mod proof_api;
 // Your NodeDB struct and methods implementation here
 
+const USAGE: &str =
+    "Usage: cargo run -- <zksync_proof_api_url> [--dev] [--app_id <value>] [--zkvm <risc0|sp1>]";
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 struct AdapterStateData {
     last_height: u32,
@@ -56,12 +59,12 @@ async fn main() -> Result<(), Error> {
     let args: Vec<String> = args().collect();
     if args.len() <= 2 {
         if args.len() == 2 && args[1] == "--dev" {
-            eprintln!("Usage: cargo run -- <zksync_proof_api_url> [--dev] [--app_id <value>]");
+            eprintln!("{}", USAGE);
             return Ok(());
         }
 
         if args.len() < 2 {
-            eprintln!("Usage: cargo run -- <zksync_proof_api_url> [--dev] [--app_id <value>]");
+            eprintln!("{}", USAGE);
             return Ok(());
         }
     }
@@ -88,23 +91,28 @@ async fn main() -> Result<(), Error> {
                 }
             }
         } else {
-            eprintln!("Usage: cargo run -- <zksync_proof_api_url> [--dev] [--app_id <value>]");
+            eprintln!("{}", USAGE);
             return Ok(());
         }
     }
 
-    let nexus_api = NexusAPI::new(&"http://dev.nexus.avail.tools");
+    // Parse the --zkvm argument if provided, defaults to the first backend compiled in
+    let mut backend = ZKVMBackend::default();
+    if let Some(zkvm_index) = args.iter().position(|arg| arg == "--zkvm") {
+        match args.get(zkvm_index + 1).map(|i| i.parse::<ZKVMBackend>()) {
+            Some(Ok(i)) => backend = i,
+            _ => {
+                eprintln!("{}", USAGE);
+                return Ok(());
+            }
+        }
+    }
 
     // Create or open the database
     let db_path = format!("db/{:?}", app_id);
     let db = NodeDB::from_path(&db_path);
 
-    #[cfg(feature = "sp1")]
-    let ZKSYNC_ADAPTER_ELF: &[u8] =
-        include_bytes!("../../methods/sp1-guest/elf/riscv32im-succinct-zkvm-elf");
-
-    #[cfg(feature = "sp1")]
-    let ZKSYNC_ADAPTER_ID = Prover::new(ZKSYNC_ADAPTER_ELF.to_vec(), prover_mode.clone()).vk(); // since sp1 doesn't implements verify method on proof object
+    let (adapter_elf, adapter_id) = adapter_program(backend, &prover_mode)?;
 
     // Retrieve or initialize the adapter state data from the database
     let adapter_state_data =
@@ -114,12 +122,18 @@ async fn main() -> Result<(), Error> {
             // Initialize with default values if no data found in the database
             let adapter_config = AdapterConfig {
                 app_id: AppId(app_id),
-                elf: ZKSYNC_ADAPTER_ELF.to_vec(),
-                adapter_elf_id: StatementDigest(ZKSYNC_ADAPTER_ID),
+                elf: adapter_elf.clone(),
+                adapter_elf_id: StatementDigest(adapter_id),
                 vk: [0u8; 32],
                 rollup_start_height: 606460,
                 prover_mode: prover_mode.clone(),
+                proving_system: Some(backend),
                 avail_url: String::from("wss://turing-rpc.avail.so:443/ws"),
+                nexus_url: String::from("http://dev.nexus.avail.tools"),
+                signing_key: SigningKeySource::DevSeed(format!("{:064x}", app_id)),
+                start_nexus_hash: None,
+                api_addr: String::from("127.0.0.1:3031"),
+                avail_signing_key: None,
             };
             AdapterStateData {
                 last_height: 0,
@@ -127,14 +141,12 @@ async fn main() -> Result<(), Error> {
             }
         };
 
+    let nexus_api = NexusAPI::new(&adapter_state_data.adapter_config.nexus_url);
+
     // Main loop to fetch headers and run adapter
     let mut last_height = adapter_state_data.last_height;
     let mut start_nexus_hash: Option<H256> = None;
-    let stf = STF::new(
-        ZKSYNC_ADAPTER_ID,
-        ZKSYNC_ADAPTER_ELF.to_vec(),
-        prover_mode.clone(),
-    );
+    let stf = STF::new(adapter_id, adapter_elf.clone(), prover_mode.clone());
 
     println!(
         "Starting nexus with AppAccountId: {:?} \n, and start height {last_height}",
@@ -169,9 +181,11 @@ async fn main() -> Result<(), Error> {
             signature: TxSignature([0u8; 64]),
             params: TxParams::InitAccount(InitAccount {
                 app_id: app_account_id.clone(),
-                statement: StatementDigest(ZKSYNC_ADAPTER_ID),
+                statement: StatementDigest(adapter_id),
                 start_nexus_hash: account_with_proof.nexus_header.hash(),
+                proving_system: backend,
             }),
+            signer: None,
         };
 
         // fs::write("./init_tx.json", serde_json::to_string(&tx).unwrap()).await;
@@ -262,7 +276,8 @@ async fn main() -> Result<(), Error> {
                     continue;
                 }
 
-                let mut recursive_proof = stf.create_recursive_proof::<Prover, Proof, ZKVM>(
+                let mut recursive_proof = stf.create_recursive_proof_with(
+                    Prover::with_backend(backend, adapter_elf.clone(), prover_mode.clone())?,
                     prev_proof_with_pi,
                     account_state,
                     proof,
@@ -289,19 +304,9 @@ async fn main() -> Result<(), Error> {
                     .rollup_hash
                     .unwrap();
 
-                #[cfg(feature = "risc0")]
-                match recursive_proof.0.verify(ZKSYNC_ADAPTER_ID) {
-                    Ok(()) => {
-                        println!("Proof verification successful");
-                        ()
-                    }
-                    Err(e) => return Err(anyhow!("Proof generated is invalid.")),
-                }
-
-                #[cfg(feature = "sp1")]
                 match recursive_proof.verify(
-                    None,
-                    Some(ZKSYNC_ADAPTER_ELF.to_vec()),
+                    Some(StatementDigest(adapter_id).to_le_bytes()),
+                    Some(adapter_elf.clone()),
                     prover_mode.clone(),
                 ) {
                     Ok(()) => {
@@ -323,7 +328,7 @@ async fn main() -> Result<(), Error> {
                             H256::from(account_with_proof.account.start_nexus_hash)
                         }),
                         app_id: app_account_id.clone(),
-                        img_id: StatementDigest(ZKSYNC_ADAPTER_ID),
+                        img_id: StatementDigest(adapter_id),
                         rollup_hash: Some(rollup_hash),
                     };
 
@@ -344,6 +349,7 @@ async fn main() -> Result<(), Error> {
                             height: public_inputs.height,
                             data: public_inputs.rollup_hash.clone(),
                         }),
+                        signer: None,
                     };
 
                     // fs::write(
@@ -401,3 +407,25 @@ async fn main() -> Result<(), Error> {
         tokio::time::sleep(Duration::from_secs(10)).await;
     }
 }
+
+/// ELF and image ID of the zksync adapter program for the given backend.
+fn adapter_program(
+    backend: ZKVMBackend,
+    prover_mode: &ProverMode,
+) -> Result<(Vec<u8>, [u32; 8]), Error> {
+    match backend {
+        #[cfg(feature = "risc0")]
+        ZKVMBackend::Risc0 => Ok((ZKSYNC_ADAPTER_ELF.to_vec(), ZKSYNC_ADAPTER_ID)),
+        #[cfg(feature = "sp1")]
+        ZKVMBackend::Sp1 => {
+            let elf =
+                include_bytes!("../../methods/sp1-guest/elf/riscv32im-succinct-zkvm-elf").to_vec();
+            //Sp1 has no image ID, the hash of the verifying key is used instead.
+            let id = Sp1Prover::new(elf.clone(), prover_mode.clone()).vk();
+
+            Ok((elf, id))
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(anyhow!("{:?} backend not enabled in this build", backend)),
+    }
+}
@@ -8,9 +8,9 @@ use nexus_core::{
         AppAccountId, AppId, AvailHeader, HeaderStore, InitAccount, NexusHeader, StatementDigest,
         Transaction, TxParams, TxSignature,
     },
-    zkvm::ProverMode,
+    zkvm::{ProverMode, ZKVMBackend},
 };
-use nexus_host::execute_batch;
+use nexus_host::{execute_batch, new_prover, BatchProfile};
 use rocksdb::Options;
 use serde_json::from_reader;
 use std::env::args;
@@ -23,10 +23,11 @@ use std::time::Instant;
 use std::{any, env};
 use tokio::sync::Mutex;
 
+use nexus_core::zkvm::any::{AnyProof as Proof, AnyProver as Prover};
 #[cfg(feature = "risc0")]
-use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
-#[cfg(feature = "sp1")]
-use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
+use nexus_core::zkvm::risczero::ZKVM;
+#[cfg(all(feature = "sp1", not(feature = "risc0")))]
+use nexus_core::zkvm::sp1::SP1ZKVM as ZKVM;
 
 #[cfg(any(feature = "sp1"))]
 use env_logger;
@@ -73,21 +74,21 @@ async fn bench_init_account_transactions(
     state_machine: &mut StateMachine<ZKVM, Proof>,
     avail_headers: Vec<AvailHeader>,
     header_store: &mut HeaderStore,
-) -> Proof {
+) -> (Proof, BatchProfile) {
     let file_content = fs::read_to_string("mock_data/init_account_txns.json").unwrap();
     let init_account_transactions: Vec<Transaction> = serde_json::from_str(&file_content).unwrap();
 
-    let (proof, header, _, _) = execute_batch::<Prover, Proof, ZKVM>(
+    let (proof, header, _, _, profile) = execute_batch::<Prover, Proof, ZKVM>(
         &init_account_transactions,
         state_machine,
         &avail_headers[1],
         header_store,
-        prover_mode.clone(),
+        new_prover(ZKVMBackend::default(), prover_mode.clone()).unwrap(),
     )
     .await
     .unwrap();
 
-    proof
+    (proof, profile)
 }
 
 async fn bench_submit_proof_transactions(
@@ -95,21 +96,34 @@ async fn bench_submit_proof_transactions(
     state_machine: &mut StateMachine<ZKVM, Proof>,
     avail_headers: Vec<AvailHeader>,
     header_store: &mut HeaderStore,
-) -> Proof {
+) -> (Proof, BatchProfile) {
     let file_content = fs::read_to_string("mock_data/submit_proof_txns.json").unwrap();
     let submit_proof_transactions: Vec<Transaction> = serde_json::from_str(&file_content).unwrap();
 
-    let (proof, _, _, _) = execute_batch::<Prover, Proof, ZKVM>(
+    let (proof, _, _, _, profile) = execute_batch::<Prover, Proof, ZKVM>(
         &submit_proof_transactions,
         state_machine,
         &avail_headers[2],
         header_store,
-        prover_mode.clone(),
+        new_prover(ZKVMBackend::default(), prover_mode.clone()).unwrap(),
     )
     .await
     .unwrap();
 
-    proof
+    (proof, profile)
+}
+
+fn print_profile(profile: &BatchProfile) {
+    println!(
+        "Cycles for {} transactions: {} total in {} segments, {} pre-state verification, {} STF ({} recursion), proven in {} ms",
+        profile.tx_count,
+        profile.total_cycles,
+        profile.segments,
+        profile.pre_state_cycles,
+        profile.stf_cycles,
+        profile.recursion_cycles,
+        profile.proving_time_ms
+    );
 }
 
 fn get_proof_size(proof: Proof) -> u64 {
@@ -156,19 +170,19 @@ async fn main() -> Result<(), anyhow::Error> {
         create_mock_data(prover_mode.clone());
     let mock_txs: Vec<Transaction> = Vec::new();
 
-    let (_, header, _, _) = execute_batch::<Prover, Proof, ZKVM>(
+    let (_, header, _, _, _) = execute_batch::<Prover, Proof, ZKVM>(
         &mock_txs,
         &mut state_machine,
         &avail_headers[0],
         &mut header_store,
-        prover_mode.clone(),
+        new_prover(ZKVMBackend::default(), prover_mode.clone()).unwrap(),
     )
     .await
     .unwrap();
 
     let init_account_time_start = Instant::now();
 
-    let mut proof = bench_init_account_transactions(
+    let (proof, profile) = bench_init_account_transactions(
         prover_mode.clone(),
         &mut state_machine,
         avail_headers.clone(),
@@ -182,12 +196,14 @@ async fn main() -> Result<(), anyhow::Error> {
         prover_mode_param, init_account_transactions_duration
     );
 
+    print_profile(&profile);
+
     let mut file_size = get_proof_size(proof);
     println!("Size of the Proof Binary: {} bytes", file_size);
 
     let submit_account_time_start = Instant::now();
 
-    proof = bench_submit_proof_transactions(
+    let (proof, profile) = bench_submit_proof_transactions(
         prover_mode.clone(),
         &mut state_machine,
         avail_headers.clone(),
@@ -201,6 +217,8 @@ async fn main() -> Result<(), anyhow::Error> {
         prover_mode_param, submit_account_transactions_duration
     );
 
+    print_profile(&profile);
+
     file_size = get_proof_size(proof);
     println!("Size of the Proof Binary: {} bytes", file_size);
 
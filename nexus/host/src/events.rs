@@ -0,0 +1,180 @@
+use futures_util::{SinkExt, StreamExt};
+use nexus_core::types::{AccountState, NexusHeader, TransactionStatus, TxError, H256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+use warp::ws::{Message, WebSocket};
+
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+pub type EventSender = broadcast::Sender<NexusEvent>;
+
+pub fn event_channel() -> EventSender {
+    let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+    sender
+}
+
+/// Events pushed to websocket subscribers once a batch is committed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NexusEvent {
+    NewHeader {
+        header: NexusHeader,
+    },
+    TxStatus {
+        tx_hash: H256,
+        status: TransactionStatus,
+        block_hash: Option<H256>,
+        error: Option<TxError>,
+    },
+    AccountUpdate {
+        app_account_id: H256,
+        account: AccountState,
+        nexus_hash: H256,
+    },
+}
+
+/// Requests sent by clients over the websocket. Hashes are hex encoded.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SubscriptionRequest {
+    SubscribeHeaders,
+    UnsubscribeHeaders,
+    SubscribeTxStatus { tx_hashes: Vec<String> },
+    UnsubscribeTxStatus { tx_hashes: Vec<String> },
+    SubscribeAccounts { app_account_ids: Vec<String> },
+    UnsubscribeAccounts { app_account_ids: Vec<String> },
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    headers: bool,
+    tx_hashes: HashSet<H256>,
+    app_account_ids: HashSet<H256>,
+}
+
+impl Subscriptions {
+    fn apply(&mut self, request: SubscriptionRequest) -> Result<(), String> {
+        match request {
+            SubscriptionRequest::SubscribeHeaders => self.headers = true,
+            SubscriptionRequest::UnsubscribeHeaders => self.headers = false,
+            SubscriptionRequest::SubscribeTxStatus { tx_hashes } => {
+                self.tx_hashes.extend(parse_hashes(&tx_hashes)?)
+            }
+            SubscriptionRequest::UnsubscribeTxStatus { tx_hashes } => {
+                for hash in parse_hashes(&tx_hashes)? {
+                    self.tx_hashes.remove(&hash);
+                }
+            }
+            SubscriptionRequest::SubscribeAccounts { app_account_ids } => {
+                self.app_account_ids.extend(parse_hashes(&app_account_ids)?)
+            }
+            SubscriptionRequest::UnsubscribeAccounts { app_account_ids } => {
+                for hash in parse_hashes(&app_account_ids)? {
+                    self.app_account_ids.remove(&hash);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn matches(&self, event: &NexusEvent) -> bool {
+        match event {
+            NexusEvent::NewHeader { .. } => self.headers,
+            NexusEvent::TxStatus { tx_hash, .. } => self.tx_hashes.contains(tx_hash),
+            NexusEvent::AccountUpdate { app_account_id, .. } => {
+                self.app_account_ids.contains(app_account_id)
+            }
+        }
+    }
+}
+
+fn parse_hashes(hashes: &[String]) -> Result<Vec<H256>, String> {
+    hashes
+        .iter()
+        .map(|hash| {
+            let bytes: [u8; 32] = match hex::decode(hash) {
+                Ok(i) => i.try_into().map_err(|_| format!("Invalid hash {}", hash))?,
+                Err(_) => return Err(format!("Invalid hash {}", hash)),
+            };
+
+            Ok(H256::from(bytes))
+        })
+        .collect()
+}
+
+pub async fn handle_ws(ws: WebSocket, events: EventSender) {
+    let (mut ws_tx, mut ws_rx) = ws.split();
+    let mut event_rx = events.subscribe();
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        tokio::select! {
+            msg = ws_rx.next() => {
+                let msg = match msg {
+                    Some(Ok(i)) => i,
+                    Some(Err(e)) => {
+                        debug!(error = ?e, "Websocket receive error");
+                        break;
+                    }
+                    None => break,
+                };
+
+                if msg.is_close() {
+                    break;
+                }
+
+                //Ignoring ping, pong and binary messages.
+                let text = match msg.to_str() {
+                    Ok(i) => i,
+                    Err(_) => continue,
+                };
+
+                let response = match serde_json::from_str::<SubscriptionRequest>(text) {
+                    Ok(request) => match subscriptions.apply(request) {
+                        Ok(()) => serde_json::json!({ "result": "ok" }),
+                        Err(e) => serde_json::json!({ "error": e }),
+                    },
+                    Err(e) => serde_json::json!({ "error": format!("Invalid request: {}", e) }),
+                };
+
+                if ws_tx.send(Message::text(response.to_string())).await.is_err() {
+                    break;
+                }
+            }
+            event = event_rx.recv() => {
+                let event = match event {
+                    Ok(i) => i,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Websocket subscriber lagging behind, events dropped");
+                        let response = serde_json::json!({ "error": "lagged", "skipped": skipped });
+
+                        if ws_tx.send(Message::text(response.to_string())).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                if !subscriptions.matches(&event) {
+                    continue;
+                }
+
+                let serialized_event = match serde_json::to_string(&event) {
+                    Ok(i) => i,
+                    Err(_) => continue,
+                };
+
+                if ws_tx.send(Message::text(serialized_event)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    debug!("Websocket connection closed");
+}
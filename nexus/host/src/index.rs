@@ -0,0 +1,235 @@
+//Secondary indexes over node db, written on every batch commit and used by
+//the listing APIs.
+use anyhow::{anyhow, Error};
+use nexus_core::db::{BatchTransaction, NodeDB};
+use nexus_core::errors::NexusTxError;
+use nexus_core::genesis::GenesisAccount;
+use nexus_core::state::VmState;
+use nexus_core::traits::NexusTransaction;
+use nexus_core::types::{
+    AccountState, AppAccountId, HeaderStore, NexusBlock, NexusBlockWithPointers, Transaction,
+    TransactionWithStatus, TxParams, H256,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 20;
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PageRequest {
+    pub offset: u64,
+    pub limit: u64,
+}
+
+impl PageRequest {
+    pub fn new(offset: Option<u64>, limit: Option<u64>) -> Self {
+        Self {
+            offset: offset.unwrap_or(0),
+            limit: limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT),
+        }
+    }
+
+    fn range(&self, total: u64) -> std::ops::Range<u64> {
+        let start = self.offset.min(total);
+        let end = self.offset.saturating_add(self.limit).min(total);
+
+        start..end
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AccountListItem {
+    pub app_account_id: AppAccountId,
+    pub account: AccountState,
+}
+
+fn account_owner_key(app_account_id: &AppAccountId) -> Vec<u8> {
+    [b"account-owner-".as_slice(), app_account_id.0.as_slice()].concat()
+}
+
+fn app_tx_count_key(app_account_id: &AppAccountId) -> Vec<u8> {
+    [b"app-tx-count-".as_slice(), app_account_id.0.as_slice()].concat()
+}
+
+fn app_tx_key(app_account_id: &AppAccountId, index: u64) -> Vec<u8> {
+    [
+        b"app-tx-".as_slice(),
+        app_account_id.0.as_slice(),
+        index.to_be_bytes().as_slice(),
+    ]
+    .concat()
+}
+
+/// Adds index entries for the transactions of a batch to the given db batch.
+pub fn index_batch(
+    db: &NodeDB,
+    batch_transaction: &mut BatchTransaction,
+    txs: &Vec<Transaction>,
+    txs_result: &HashMap<H256, Result<(), NexusTxError>>,
+) -> Result<(), Error> {
+    let mut app_tx_counts: HashMap<[u8; 32], u64> = HashMap::new();
+
+    for tx in txs.iter() {
+        let tx_hash = tx.hash();
+        let success = match txs_result.get(&tx_hash) {
+            Some(i) => i.is_ok(),
+            //Not executed in this batch.
+            None => continue,
+        };
+
+        let app_account_id = match &tx.params {
+            TxParams::SubmitProof(i) => i.app_id.clone(),
+            TxParams::InitAccount(i) => i.app_id.clone(),
+        };
+
+        let count = match app_tx_counts.get(&app_account_id.0) {
+            Some(i) => i.clone(),
+            None => db.get(&app_tx_count_key(&app_account_id))?.unwrap_or(0),
+        };
+        batch_transaction.put(&app_tx_key(&app_account_id, count), &tx_hash)?;
+        app_tx_counts.insert(app_account_id.0.clone(), count + 1);
+
+        if success {
+            if let TxParams::InitAccount(_) = &tx.params {
+                if let Some(signer) = &tx.signer {
+                    batch_transaction.put(&account_owner_key(&app_account_id), signer)?;
+                }
+            }
+        }
+    }
+
+    for (app_account_id, count) in app_tx_counts.iter() {
+        batch_transaction.put(
+            &app_tx_count_key(&AppAccountId(app_account_id.clone())),
+            count,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Adds index entries for the accounts registered at genesis to the given db batch.
+pub fn index_genesis(
+    batch_transaction: &mut BatchTransaction,
+    accounts: &Vec<GenesisAccount>,
+) -> Result<(), Error> {
+    for account in accounts.iter() {
+        if let Some(owner) = &account.owner {
+            batch_transaction.put(&account_owner_key(&account.app_id), owner.as_fixed_slice())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the key which signed the registration of the account, if it was signed.
+pub fn get_account_owner(
+    db: &NodeDB,
+    app_account_id: &AppAccountId,
+) -> Result<Option<[u8; 32]>, Error> {
+    db.get(&account_owner_key(app_account_id))
+}
+
+/// Lists blocks from the latest one backwards.
+pub fn list_blocks(db: &NodeDB, page: &PageRequest) -> Result<Page<NexusBlock>, Error> {
+    let total: u64 = match db.get::<HeaderStore>(b"previous_headers")? {
+        Some(i) => match i.first() {
+            Some(header) => header.number as u64 + 1,
+            None => 0,
+        },
+        None => 0,
+    };
+
+    let mut items = vec![];
+    for position in page.range(total) {
+        let number = (total - 1 - position) as u32;
+        let hash: H256 = match db.get(&[number.to_be_bytes().as_slice(), b"-block"].concat())? {
+            Some(i) => i,
+            None => {
+                return Err(anyhow!(
+                    "Block number to hash mapping missing for {}",
+                    number
+                ))
+            }
+        };
+
+        match db.get::<NexusBlockWithPointers>(&[hash.as_slice(), b"-block"].concat())? {
+            Some(i) => items.push(i.block),
+            None => return Err(anyhow!("Block missing for hash {:?}", hash)),
+        }
+    }
+
+    Ok(Page {
+        items,
+        total,
+        offset: page.offset,
+        limit: page.limit,
+    })
+}
+
+/// Lists transactions of an app, latest first.
+pub fn list_app_txs(
+    db: &NodeDB,
+    app_account_id: &AppAccountId,
+    page: &PageRequest,
+) -> Result<Page<TransactionWithStatus>, Error> {
+    let total: u64 = db.get(&app_tx_count_key(app_account_id))?.unwrap_or(0);
+
+    let mut items = vec![];
+    for position in page.range(total) {
+        let index = total - 1 - position;
+        let tx_hash: H256 = match db.get(&app_tx_key(app_account_id, index))? {
+            Some(i) => i,
+            None => return Err(anyhow!("App tx index missing for {}", index)),
+        };
+
+        match db.get::<TransactionWithStatus>(tx_hash.as_slice())? {
+            Some(i) => items.push(i),
+            None => return Err(anyhow!("Tx missing for hash {:?}", tx_hash)),
+        }
+    }
+
+    Ok(Page {
+        items,
+        total,
+        offset: page.offset,
+        limit: page.limit,
+    })
+}
+
+/// Lists accounts in the state at the given version, sorted by account ID.
+pub fn list_accounts(
+    state: &VmState,
+    version: u64,
+    page: &PageRequest,
+) -> Result<Page<AccountListItem>, Error> {
+    let accounts = state.accounts(version)?;
+    let total = accounts.len() as u64;
+    let range = page.range(total);
+
+    let items = accounts
+        .into_iter()
+        .skip(range.start as usize)
+        .take((range.end - range.start) as usize)
+        .map(|(app_account_id, account)| AccountListItem {
+            app_account_id: AppAccountId(app_account_id.into()),
+            account,
+        })
+        .collect();
+
+    Ok(Page {
+        items,
+        total,
+        offset: page.offset,
+        limit: page.limit,
+    })
+}
@@ -0,0 +1,363 @@
+use nexus_core::db::NodeDB;
+use nexus_core::mempool::Mempool;
+use nexus_core::runtime::RuntimeSchedule;
+use nexus_core::state::VmState;
+use nexus_core::types::{Transaction, H256};
+use serde::de::{DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use warp::hyper::body::Bytes;
+use warp::reply::Response;
+use warp::{http::StatusCode, Rejection, Reply};
+
+use crate::index::PageRequest;
+use crate::rpc::{
+    add_tx, fetch_account, fetch_account_owner, fetch_accounts, fetch_app_txs, fetch_block,
+    fetch_block_profile, fetch_block_proof, fetch_blocks, fetch_header, fetch_range,
+    fetch_tx_status, AccountWithProofHex, ApiError, INVALID_PARAMS_CODE,
+};
+
+pub const PARSE_ERROR_CODE: i64 = -32700;
+pub const INVALID_REQUEST_CODE: i64 = -32600;
+pub const METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+#[derive(Clone, Debug, Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+    /// `None` only when the id is missing, which makes the request a notification.
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Value>,
+}
+
+/// Keeps an explicit `"id": null` apart from a missing id.
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+    pub id: Value,
+}
+
+impl JsonRpcError {
+    fn new(code: i64, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<ApiError> for JsonRpcError {
+    fn from(value: ApiError) -> Self {
+        Self {
+            code: value.code,
+            message: value.message,
+        }
+    }
+}
+
+impl JsonRpcResponse {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: String::from("2.0"),
+            result: Some(result),
+            error: None,
+            id,
+        }
+    }
+
+    fn error(id: Value, error: JsonRpcError) -> Self {
+        Self {
+            jsonrpc: String::from("2.0"),
+            result: None,
+            error: Some(error),
+            id,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct JsonRpcContext {
+    pub mempool: Mempool,
+    pub db: Arc<Mutex<NodeDB>>,
+    pub vm_state: Arc<Mutex<VmState>>,
+    pub schedule: RuntimeSchedule,
+}
+
+/// Params can be given either positionally or by name.
+struct Params(Option<Value>);
+
+impl Params {
+    fn get<T: DeserializeOwned>(
+        &self,
+        index: usize,
+        name: &str,
+    ) -> Result<Option<T>, JsonRpcError> {
+        let value = match &self.0 {
+            Some(Value::Array(values)) => values.get(index),
+            Some(Value::Object(values)) => values.get(name),
+            Some(Value::Null) | None => None,
+            Some(_) => {
+                return Err(JsonRpcError::new(
+                    INVALID_PARAMS_CODE,
+                    "Params must be an array or an object",
+                ))
+            }
+        };
+
+        match value {
+            None | Some(Value::Null) => Ok(None),
+            Some(i) => serde_json::from_value(i.clone()).map(Some).map_err(|e| {
+                JsonRpcError::new(
+                    INVALID_PARAMS_CODE,
+                    &format!("Invalid param {}: {}", name, e),
+                )
+            }),
+        }
+    }
+
+    fn required<T: DeserializeOwned>(&self, index: usize, name: &str) -> Result<T, JsonRpcError> {
+        match self.get(index, name)? {
+            Some(i) => Ok(i),
+            None => Err(JsonRpcError::new(
+                INVALID_PARAMS_CODE,
+                &format!("Missing param {}", name),
+            )),
+        }
+    }
+
+    fn hash(&self, index: usize, name: &str) -> Result<Option<H256>, JsonRpcError> {
+        match self.get::<String>(index, name)? {
+            Some(i) => match H256::try_from(i.trim_start_matches("0x")) {
+                Ok(hash) => Ok(Some(hash)),
+                Err(_) => Err(JsonRpcError::new(INVALID_PARAMS_CODE, "Invalid hash")),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn required_hash(&self, index: usize, name: &str) -> Result<H256, JsonRpcError> {
+        match self.hash(index, name)? {
+            Some(i) => Ok(i),
+            None => Err(JsonRpcError::new(
+                INVALID_PARAMS_CODE,
+                &format!("Missing param {}", name),
+            )),
+        }
+    }
+}
+
+fn to_value<T: Serialize>(result: Result<T, ApiError>) -> Result<Value, JsonRpcError> {
+    match result {
+        Ok(i) => serde_json::to_value(i)
+            .map_err(|_| JsonRpcError::from(ApiError::internal("Internal encoding error"))),
+        Err(e) => Err(JsonRpcError::from(e)),
+    }
+}
+
+async fn dispatch(
+    context: &JsonRpcContext,
+    method: &str,
+    params: Params,
+) -> Result<Value, JsonRpcError> {
+    match method {
+        "nexus_health" => Ok(serde_json::json!({"status": "Alive ser."})),
+        "nexus_sendTransaction" => {
+            let tx: Transaction = params.required(0, "transaction")?;
+
+            to_value(
+                add_tx(&context.mempool, &context.vm_state, tx)
+                    .await
+                    .map(|hash| hex::encode(hash.as_slice())),
+            )
+        }
+        "nexus_getTransactionStatus" => {
+            let tx_hash = params.required_hash(0, "tx_hash")?;
+
+            to_value(fetch_tx_status(&context.db, &tx_hash).await)
+        }
+        "nexus_getBlockByNumber" => {
+            let block_number: u32 = params.required(0, "block_number")?;
+
+            to_value(fetch_block(&context.db, None, Some(block_number)).await)
+        }
+        "nexus_getBlockByHash" => {
+            let block_hash = params.required_hash(0, "block_hash")?;
+
+            to_value(fetch_block(&context.db, Some(block_hash), None).await)
+        }
+        "nexus_getLatestBlock" => to_value(fetch_block(&context.db, None, None).await),
+        "nexus_getBlockProof" => {
+            let block_number: Option<u32> = params.get(0, "block_number")?;
+
+            to_value(fetch_block_proof(&context.db, &context.schedule, None, block_number).await)
+        }
+        "nexus_getBlockProfile" => {
+            let block_number: Option<u32> = params.get(0, "block_number")?;
+
+            to_value(fetch_block_profile(&context.db, None, block_number).await)
+        }
+        "nexus_getAccount" => {
+            let app_account_id = params.required_hash(0, "app_account_id")?;
+            let block_hash = params.hash(1, "block_hash")?;
+
+            to_value(
+                fetch_account(&context.db, &context.vm_state, &app_account_id, block_hash).await,
+            )
+        }
+        "nexus_getAccountHex" => {
+            let app_account_id = params.required_hash(0, "app_account_id")?;
+
+            to_value(
+                fetch_account(&context.db, &context.vm_state, &app_account_id, None)
+                    .await
+                    .map(AccountWithProofHex::from),
+            )
+        }
+        "nexus_getAccountOwner" => {
+            let app_account_id = params.required_hash(0, "app_account_id")?;
+
+            to_value(fetch_account_owner(&context.db, &app_account_id).await)
+        }
+        "nexus_getHeader" => {
+            let avail_hash = params.required_hash(0, "hash")?;
+
+            to_value(fetch_header(&context.db, &avail_hash).await)
+        }
+        "nexus_getRange" => to_value(fetch_range(&context.db).await),
+        "nexus_getBlocks" => {
+            let page = PageRequest::new(params.get(0, "offset")?, params.get(1, "limit")?);
+
+            to_value(fetch_blocks(&context.db, &page).await)
+        }
+        "nexus_getAppTransactions" => {
+            let app_account_id = params.required_hash(0, "app_account_id")?;
+            let page = PageRequest::new(params.get(1, "offset")?, params.get(2, "limit")?);
+
+            to_value(fetch_app_txs(&context.db, &app_account_id, &page).await)
+        }
+        "nexus_getAccounts" => {
+            let block_hash = params.hash(0, "block_hash")?;
+            let page = PageRequest::new(params.get(1, "offset")?, params.get(2, "limit")?);
+
+            to_value(fetch_accounts(&context.db, &context.vm_state, block_hash, &page).await)
+        }
+        _ => Err(JsonRpcError::new(
+            METHOD_NOT_FOUND_CODE,
+            &format!("Method not found: {}", method),
+        )),
+    }
+}
+
+/// Handles a single request object. Returns `None` for notifications.
+async fn handle_request(context: &JsonRpcContext, request: Value) -> Option<JsonRpcResponse> {
+    let request: JsonRpcRequest = match serde_json::from_value(request) {
+        Ok(i) => i,
+        Err(_) => {
+            return Some(JsonRpcResponse::error(
+                Value::Null,
+                JsonRpcError::new(INVALID_REQUEST_CODE, "Invalid request"),
+            ))
+        }
+    };
+
+    if request.jsonrpc != "2.0" {
+        return Some(JsonRpcResponse::error(
+            request.id.unwrap_or(Value::Null),
+            JsonRpcError::new(INVALID_REQUEST_CODE, "Only JSON-RPC 2.0 is supported"),
+        ));
+    }
+
+    let result = dispatch(context, &request.method, Params(request.params)).await;
+    let id = request.id?;
+
+    Some(match result {
+        Ok(i) => JsonRpcResponse::result(id, i),
+        Err(e) => JsonRpcResponse::error(id, e),
+    })
+}
+
+fn json_reply(body: String, status: StatusCode) -> Response {
+    warp::reply::with_header(
+        warp::reply::with_status(body, status),
+        "content-type",
+        "application/json",
+    )
+    .into_response()
+}
+
+fn no_content() -> Response {
+    warp::reply::with_status(warp::reply(), StatusCode::NO_CONTENT).into_response()
+}
+
+pub async fn handle_json_rpc(context: JsonRpcContext, body: Bytes) -> Result<Response, Rejection> {
+    let request: Value = match serde_json::from_slice(&body) {
+        Ok(i) => i,
+        Err(_) => {
+            let response = JsonRpcResponse::error(
+                Value::Null,
+                JsonRpcError::new(PARSE_ERROR_CODE, "Parse error"),
+            );
+
+            return Ok(json_reply(
+                serde_json::to_string(&response).unwrap_or_default(),
+                StatusCode::OK,
+            ));
+        }
+    };
+
+    let serialized_response = match request {
+        Value::Array(requests) => {
+            if requests.is_empty() {
+                serde_json::to_string(&JsonRpcResponse::error(
+                    Value::Null,
+                    JsonRpcError::new(INVALID_REQUEST_CODE, "Empty batch"),
+                ))
+            } else {
+                let mut responses: Vec<JsonRpcResponse> = vec![];
+                for request in requests {
+                    if let Some(response) = handle_request(&context, request).await {
+                        responses.push(response);
+                    }
+                }
+
+                //Batch made only of notifications, nothing to respond with.
+                if responses.is_empty() {
+                    return Ok(no_content());
+                }
+
+                serde_json::to_string(&responses)
+            }
+        }
+        request => match handle_request(&context, request).await {
+            Some(response) => serde_json::to_string(&response),
+            None => return Ok(no_content()),
+        },
+    };
+
+    match serialized_response {
+        Ok(i) => Ok(json_reply(i, StatusCode::OK)),
+        Err(_) => Ok(warp::reply::with_status(
+            "Internal encoding error".to_string(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response()),
+    }
+}
@@ -1,35 +1,58 @@
 use anyhow::{anyhow, Context, Error};
 pub use avail_subxt::Header;
+use ethabi::{encode, short_signature, ParamType, Token, U256};
 use jmt::storage::TreeUpdateBatch;
 use nexus_core::{
     db::{BatchTransaction, NodeDB},
+    errors::NexusTxError,
+    genesis::Genesis,
     mempool::Mempool,
+    runtime::{RuntimeSchedule, RuntimeUpgrade, NEXUS_RUNTIME_VERSION},
     state::VmState,
     state_machine::StateMachine,
     traits::NexusTransaction,
     types::{
         AvailHeader, HeaderStore, NexusBlock, NexusBlockWithPointers, NexusHeader,
-        Proof as NexusProof, Transaction, TransactionResult, TransactionStatus,
-        TransactionWithStatus, TransactionZKVM, TxParams, H256,
+        Proof as NexusProof, StateUpdate, StatementDigest, Transaction, TransactionResult,
+        TransactionStatus, TransactionWithStatus, TransactionZKVM, TxError, TxParams, H256,
     },
     zkvm::{
+        proving::{
+            interruptible, prove_async, ProvingInterrupted, ProvingOptions, ProvingPhase,
+            ProvingProgress,
+        },
         traits::{ZKVMEnv, ZKVMProof, ZKVMProver},
-        ProverMode,
+        ProverMode, ProvingStats, ReceiptKind, ZKVMBackend, PRE_STATE_REGION, RECURSION_REGION,
+        STF_REGION,
     },
+    zkvm_state_machine::batch_header,
 };
+use parity_scale_codec::Encode;
 use serde_json;
-use std::{collections::HashMap, mem, thread};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt, mem, thread,
+};
 use tokio::fs;
 use tracing::{debug, error, info, instrument};
 
+use crate::events::{event_channel, EventSender, NexusEvent};
+use crate::index::{index_batch, index_genesis};
 use crate::rpc::routes;
+use crate::worker::{ProverWorkers, WorkerRequest};
 use avail_subxt::config::Header as HeaderTrait;
+use nexus_core::zkvm::any::{AnyProof as Proof, AnyProver as Prover};
+#[cfg(all(feature = "mock", not(any(feature = "risc0", feature = "sp1"))))]
+use nexus_core::zkvm::mock::MockZKVM as ZKVM;
 #[cfg(any(feature = "risc0"))]
-use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
+use nexus_core::zkvm::risczero::ZKVM;
+#[cfg(all(feature = "sp1", not(feature = "risc0")))]
+use nexus_core::zkvm::sp1::SP1ZKVM as ZKVM;
 
+#[cfg(any(feature = "mock"))]
+use nexus_core::zkvm::mock::{mock_img_id, NEXUS_RUNTIME_MOCK_ELF};
 #[cfg(any(feature = "sp1"))]
-use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
-
+use nexus_core::zkvm::sp1::Sp1Prover;
 #[cfg(any(feature = "risc0"))]
 use prover::{NEXUS_RUNTIME_ELF, NEXUS_RUNTIME_ID};
 pub use relayer::{Relayer, SimpleRelayer};
@@ -39,16 +62,105 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::{env::args, fmt::Debug as DebugTrait};
 use tokio::sync::{mpsc::UnboundedReceiver, watch, Mutex};
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
+use utoipa::ToSchema;
 use warp::Filter;
 
+pub mod events;
+pub mod index;
+pub mod jsonrpc;
 pub mod rpc;
+pub mod worker;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AvailToNexusPointer {
     number: u32,
     nexus_hash: H256,
 }
 
+/// Proof of a block, stored next to it. Carries the calldata of
+/// `NexusProofManager.updateNexusBlock` once wrapped in Groth16.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NexusBlockProof {
+    pub proof: NexusProof,
+    pub calldata: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct NexusBlockProofHex {
+    pub backend: ZKVMBackend,
+    pub kind: ReceiptKind,
+    pub proof: String,
+    pub calldata: Option<String>,
+}
+
+const MAX_PROVING_BACKOFF_SECS: u64 = 600;
+
+/// How the execution engine proves batches.
+#[derive(Clone, Default)]
+pub struct ProvingConfig {
+    /// Prover workers batches are shipped to, proven in-process when not set.
+    pub workers: Option<ProverWorkers>,
+    /// Batches taking longer are proven again, after a delay growing with each attempt.
+    pub timeout: Option<Duration>,
+    /// Runtimes blocks are proven with, the one built into the node when not set.
+    pub schedule: Option<RuntimeSchedule>,
+}
+
+impl ProvingConfig {
+    /// Runtimes the blocks of the backend are proven with.
+    pub fn schedule(&self, backend: ZKVMBackend) -> Result<RuntimeSchedule, Error> {
+        match &self.schedule {
+            Some(i) => Ok(i.clone()),
+            None => builtin_schedule(backend),
+        }
+    }
+}
+
+/// Resources used to prove a batch, stored next to its block.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct BatchProfile {
+    pub tx_count: usize,
+    pub total_cycles: u64,
+    /// Segments for risc0, shards for sp1.
+    pub segments: u64,
+    /// Cycles spent verifying pre-state inclusion proofs.
+    pub pre_state_cycles: u64,
+    /// Cycles spent in the STF, recursion included.
+    pub stf_cycles: u64,
+    /// Cycles spent verifying recursed proofs.
+    pub recursion_cycles: u64,
+    pub proving_time_ms: u64,
+}
+
+impl BatchProfile {
+    pub fn new(tx_count: usize, stats: &ProvingStats, proving_time: Duration) -> Self {
+        Self {
+            tx_count,
+            total_cycles: stats.total_cycles,
+            segments: stats.segments,
+            pre_state_cycles: stats.region(PRE_STATE_REGION),
+            stf_cycles: stats.region(STF_REGION),
+            recursion_cycles: stats.region(RECURSION_REGION),
+            proving_time_ms: proving_time.as_millis() as u64,
+        }
+    }
+}
+
+impl TryFrom<&NexusBlockProof> for NexusBlockProofHex {
+    type Error = Error;
+
+    fn try_from(value: &NexusBlockProof) -> Result<Self, Self::Error> {
+        let envelope = value.proof.envelope()?;
+
+        Ok(Self {
+            backend: envelope.backend,
+            kind: envelope.kind,
+            proof: hex::encode(&value.proof.0),
+            calldata: value.calldata.as_ref().map(hex::encode),
+        })
+    }
+}
+
 pub fn setup_components(db_path: &str) -> (Arc<Mutex<NodeDB>>, Arc<Mutex<VmState>>) {
     // Construct the node_db path directly as a string
     let node_db_path = format!("{}/node_db", db_path);
@@ -61,6 +173,105 @@ pub fn setup_components(db_path: &str) -> (Arc<Mutex<NodeDB>>, Arc<Mutex<VmState
     (Arc::new(Mutex::new(node_db)), state)
 }
 
+/// Commits block 0 of the chain from `genesis` on a new node db, checking the chain in the
+/// node db was started from it otherwise. `runtime` is the runtime active at height 0.
+pub async fn apply_genesis(
+    node_db: &Arc<Mutex<NodeDB>>,
+    state: &Arc<Mutex<VmState>>,
+    genesis: &Genesis,
+    runtime: &RuntimeUpgrade,
+) -> Result<NexusHeader, Error> {
+    genesis.validate()?;
+    if genesis.runtime_version != runtime.version {
+        return Err(anyhow!(
+            "Genesis runtime version {}, version {} is active at height 0",
+            genesis.runtime_version,
+            runtime.version
+        ));
+    }
+
+    let db_lock = node_db.lock().await;
+    if db_lock.get::<HeaderStore>(b"previous_headers")?.is_some() {
+        let nexus_hash: H256 =
+            match db_lock.get(&[0u32.to_be_bytes().as_slice(), b"-block"].concat())? {
+                Some(i) => i,
+                None => return Err(anyhow!("Node DB has blocks, but no block 0")),
+            };
+        let header: NexusHeader = match db_lock.get(nexus_hash.as_slice())? {
+            Some(i) => i,
+            None => return Err(anyhow!("Block 0 {} not found in node DB", nexus_hash)),
+        };
+        genesis.check_header(&header)?;
+
+        return Ok(header);
+    }
+
+    let mut state_lock = state.lock().await;
+    //Left over by a genesis that failed before block 0 was stored.
+    if state_lock.get_version(true)?.is_some() {
+        return Err(anyhow!(
+            "Runtime DB has state but node DB has no blocks, clean the DB to apply the genesis"
+        ));
+    }
+
+    let (state_root, jmt_version) = if genesis.accounts.is_empty() {
+        (H256::zero(), 0)
+    } else {
+        let (tree_update_batch, state_update) =
+            state_lock.update_set(genesis.account_states(), 1)?;
+        state_lock.update_version(1)?;
+        state_lock.commit(&tree_update_batch.node_batch)?;
+
+        (state_update.post_state_root, 1)
+    };
+    drop(state_lock);
+
+    let header = genesis.header(state_root);
+    let nexus_hash = header.hash();
+    let mut header_store = HeaderStore::new(32);
+    header_store.push_front(&header);
+
+    let mut batch_transaction = BatchTransaction::new();
+    batch_transaction.put(b"previous_headers", &header_store)?;
+    //Relayer starts from the Avail block after the one pointed to by the latest block.
+    batch_transaction.put(
+        genesis.start_avail_hash.as_slice(),
+        &AvailToNexusPointer {
+            number: genesis.start_avail_block,
+            nexus_hash: nexus_hash.clone(),
+        },
+    )?;
+    index_genesis(&mut batch_transaction, &genesis.accounts)?;
+    batch_transaction.put(nexus_hash.as_slice(), &header)?;
+    batch_transaction.put(
+        &[nexus_hash.as_slice(), b"-block"].concat(),
+        &NexusBlockWithPointers {
+            block: NexusBlock {
+                header: header.clone(),
+                transactions: vec![],
+            },
+            jmt_version,
+        },
+    )?;
+    batch_transaction.put(
+        &[header.number.to_be_bytes().as_slice(), b"-block"].concat(),
+        &nexus_hash,
+    )?;
+    db_lock.put_batch(batch_transaction)?;
+    db_lock.set_current_root(&header.state_root)?;
+
+    info!(
+        network_id = %genesis.network_id,
+        nexus_hash = %hex::encode(nexus_hash.as_slice()),
+        state_root = %hex::encode(header.state_root.as_slice()),
+        start_avail_block = genesis.start_avail_block,
+        accounts = genesis.accounts.len(),
+        "🌱 Genesis block committed"
+    );
+
+    Ok(header)
+}
+
 pub async fn relayer_handle(
     relayer_mutex: Arc<Mutex<impl Relayer + Send + 'static>>,
     node_db_mutex: Arc<Mutex<NodeDB>>,
@@ -108,6 +319,267 @@ pub async fn relayer_handle(
     info!("Exited relayer handle");
 }
 
+/// Returns the ELF of the Nexus runtime built for the given backend.
+pub fn runtime_elf(backend: ZKVMBackend) -> Result<Vec<u8>, Error> {
+    match backend {
+        #[cfg(any(feature = "risc0"))]
+        ZKVMBackend::Risc0 => Ok(NEXUS_RUNTIME_ELF.to_vec()),
+        #[cfg(any(feature = "sp1"))]
+        ZKVMBackend::Sp1 => {
+            Ok(include_bytes!("../../prover/sp1-guest/elf/riscv32im-succinct-zkvm-elf").to_vec())
+        }
+        #[cfg(any(feature = "mock"))]
+        ZKVMBackend::Mock => Ok(NEXUS_RUNTIME_MOCK_ELF.to_vec()),
+        #[allow(unreachable_patterns)]
+        _ => Err(anyhow!("{:?} backend not enabled in this build", backend)),
+    }
+}
+
+/// Compresses a block proof, and wraps it in Groth16 when the node runs in Groth16 mode.
+pub fn post_process_proof<P: ZKVMProof>(
+    mut proof: P,
+    prover_mode: &ProverMode,
+) -> Result<P, Error> {
+    match prover_mode {
+        ProverMode::MockProof => Ok(proof),
+        ProverMode::Groth16 => proof.compress()?.wrap_groth16(),
+        _ => proof.compress(),
+    }
+}
+
+/// Calldata of `NexusProofManager.updateNexusBlock`, proving the block with the given seal.
+pub fn update_nexus_block_calldata(header: &NexusHeader, seal: Vec<u8>) -> Vec<u8> {
+    let selector = short_signature(
+        "updateNexusBlock",
+        &[
+            ParamType::Uint(256),
+            ParamType::Tuple(vec![ParamType::FixedBytes(32), ParamType::FixedBytes(32)]),
+            ParamType::Bytes,
+            ParamType::Bytes,
+        ],
+    );
+    let params = encode(&[
+        Token::Uint(U256::from(header.number)),
+        Token::Tuple(vec![
+            Token::FixedBytes(header.state_root.as_slice().to_vec()),
+            Token::FixedBytes(header.hash().as_slice().to_vec()),
+        ]),
+        Token::Bytes(header.encode()),
+        Token::Bytes(seal),
+    ]);
+
+    [selector.as_slice(), params.as_slice()].concat()
+}
+
+/// Block proof that is not of its block, by the runtime scheduled at its height. Proving the
+/// block again gives the same proof, so the engine stops on it.
+#[derive(Debug)]
+pub struct InvalidBlockProof(Error);
+
+impl fmt::Display for InvalidBlockProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid block proof: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidBlockProof {}
+
+fn block_proof(
+    proof: Proof,
+    header: &NexusHeader,
+    prover_mode: &ProverMode,
+    schedule: &RuntimeSchedule,
+) -> Result<NexusBlockProof, Error> {
+    let proof = post_process_proof(proof, prover_mode)?;
+    let calldata = match prover_mode {
+        ProverMode::Groth16 => Some(update_nexus_block_calldata(header, proof.evm_seal()?)),
+        _ => None,
+    };
+    let proof: NexusProof = proof.try_into()?;
+
+    //Only proofs of the block, by the runtime active at its height, are stored.
+    let verified = schedule
+        .active(proof.envelope()?.backend, header.number)
+        .and_then(runtime_upgrade_elf)
+        .and_then(|elf| schedule.verify_block_proof(&proof, Some(elf), prover_mode.clone()));
+    let proven_header = match verified {
+        Ok(i) => i,
+        Err(e) => return Err(InvalidBlockProof(e).into()),
+    };
+    if &proven_header != header {
+        return Err(InvalidBlockProof(anyhow!(
+            "Proof is of a different block, expected {:?}, got {:?}",
+            header,
+            proven_header
+        ))
+        .into());
+    }
+
+    Ok(NexusBlockProof { proof, calldata })
+}
+
+/// Creates a prover of the Nexus runtime for the given backend.
+pub fn new_prover(backend: ZKVMBackend, prover_mode: ProverMode) -> Result<Prover<'static>, Error> {
+    Prover::with_backend(backend, runtime_elf(backend)?, prover_mode)
+}
+
+/// Image ID of the Nexus runtime built for the given backend.
+pub fn runtime_img_id(backend: ZKVMBackend) -> Result<StatementDigest, Error> {
+    match backend {
+        #[cfg(any(feature = "risc0"))]
+        ZKVMBackend::Risc0 => Ok(StatementDigest(NEXUS_RUNTIME_ID)),
+        #[cfg(any(feature = "sp1"))]
+        ZKVMBackend::Sp1 => Ok(StatementDigest(
+            Sp1Prover::new(runtime_elf(backend)?, ProverMode::MockProof).vk(),
+        )),
+        #[cfg(any(feature = "mock"))]
+        ZKVMBackend::Mock => Ok(mock_img_id(NEXUS_RUNTIME_MOCK_ELF)),
+        #[allow(unreachable_patterns)]
+        _ => Err(anyhow!("{:?} backend not enabled in this build", backend)),
+    }
+}
+
+/// Schedule proving every block with the runtime built into the node.
+pub fn builtin_schedule(backend: ZKVMBackend) -> Result<RuntimeSchedule, Error> {
+    RuntimeSchedule::new(vec![RuntimeUpgrade {
+        version: NEXUS_RUNTIME_VERSION,
+        activation_height: 0,
+        backend,
+        img_id: runtime_img_id(backend)?,
+        elf: None,
+    }])
+}
+
+/// ELF of a scheduled runtime, `None` when it is the one built into the node.
+pub fn scheduled_elf(runtime: &RuntimeUpgrade) -> Result<Option<Vec<u8>>, Error> {
+    match &runtime.elf {
+        Some(path) => Ok(Some(std::fs::read(path)?)),
+        None if runtime.version == NEXUS_RUNTIME_VERSION => Ok(None),
+        None => Err(anyhow!(
+            "No ELF given for runtime version {}, built in version is {}",
+            runtime.version,
+            NEXUS_RUNTIME_VERSION
+        )),
+    }
+}
+
+/// ELF of a scheduled runtime, including the one built into the node.
+pub fn runtime_upgrade_elf(runtime: &RuntimeUpgrade) -> Result<Vec<u8>, Error> {
+    match scheduled_elf(runtime)? {
+        Some(i) => Ok(i),
+        None => runtime_elf(runtime.backend),
+    }
+}
+
+/// Creates a prover of a scheduled runtime.
+pub fn scheduled_prover(
+    runtime: &RuntimeUpgrade,
+    prover_mode: ProverMode,
+) -> Result<Prover<'static>, Error> {
+    match scheduled_elf(runtime)? {
+        Some(elf) => Prover::with_backend(runtime.backend, elf, prover_mode),
+        None => new_prover(runtime.backend, prover_mode),
+    }
+}
+
+/// Inputs of the Nexus runtime for a single batch. Serializable so the proof can be
+/// generated by a prover worker, see [`worker`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProvingJob {
+    pub txs: Vec<TransactionZKVM>,
+    pub state_update: StateUpdate,
+    pub header: AvailHeader,
+    pub header_store: HeaderStore,
+    pub recursion_proofs: Vec<NexusProof>,
+}
+
+impl ProvingJob {
+    pub fn prove<Z: ZKVMProver<P>, P: ZKVMProof + TryFrom<NexusProof>>(
+        &self,
+        mut zkvm_prover: Z,
+        progress: &dyn Fn(ProvingProgress),
+    ) -> Result<(P, Option<ProvingStats>), Error>
+    where
+        <P as TryFrom<NexusProof>>::Error: std::fmt::Debug,
+    {
+        for proof in self.recursion_proofs.iter() {
+            let receipt: P = match P::try_from(proof.clone()) {
+                Ok(i) => i,
+                Err(e) => return Err(anyhow!("Invalid recursion proof: {:?}", e)),
+            };
+            zkvm_prover.add_proof_for_recursion(receipt)?;
+        }
+
+        zkvm_prover.add_input(&self.txs)?;
+        zkvm_prover.add_input(&self.state_update)?;
+        zkvm_prover.add_input(&self.header)?;
+        zkvm_prover.add_input(&self.header_store)?;
+        let proof = zkvm_prover.prove_with_progress(progress)?;
+
+        Ok((proof, zkvm_prover.stats()))
+    }
+
+    /// Header the given runtime version builds for the batch.
+    pub fn header(&self, runtime_version: u32) -> NexusHeader {
+        batch_header(
+            &self.header,
+            &self.header_store,
+            &self.txs,
+            &self.state_update,
+            runtime_version,
+        )
+    }
+}
+
+/// Executes the batch on the state machine and collects what the runtime needs to prove it.
+pub async fn prepare_batch<P: ZKVMProof + Serialize + Clone + DebugTrait, E: ZKVMEnv>(
+    txs: &Vec<Transaction>,
+    state_machine: &mut StateMachine<E, P>,
+    header: &AvailHeader,
+    header_store: &HeaderStore,
+) -> Result<
+    (
+        ProvingJob,
+        HashMap<H256, Result<(), NexusTxError>>,
+        Option<TreeUpdateBatch>,
+    ),
+    Error,
+> {
+    let (tree_update_batch, state_update, tx_result): (
+        Option<jmt::storage::TreeUpdateBatch>,
+        StateUpdate,
+        HashMap<H256, Result<(), NexusTxError>>,
+    ) = state_machine
+        .execute_batch(&header, header_store, &txs)
+        .await?;
+
+    let backend = state_machine.backend();
+    let mut recursion_proofs: Vec<NexusProof> = vec![];
+    let mut zkvm_txs: Vec<TransactionZKVM> = Vec::with_capacity(txs.len());
+
+    for tx in txs.iter() {
+        if let TxParams::SubmitProof(submit_proof_tx) = &tx.params {
+            //Proofs of other backends are verified by the runtime from the transaction.
+            if submit_proof_tx.proof.envelope()?.backend == backend {
+                //TODO: Remove transactions that error out from mempool
+                recursion_proofs.push(submit_proof_tx.proof.clone());
+            }
+        }
+
+        zkvm_txs.push(TransactionZKVM::from(tx));
+    }
+
+    let job = ProvingJob {
+        txs: zkvm_txs,
+        state_update,
+        header: header.clone(),
+        header_store: header_store.clone(),
+        recursion_proofs,
+    };
+
+    Ok((job, tx_result, tree_update_batch))
+}
+
 pub async fn execute_batch<
     Z: ZKVMProver<P>,
     P: ZKVMProof + Serialize + Clone + DebugTrait + TryFrom<NexusProof>,
@@ -117,58 +589,162 @@ pub async fn execute_batch<
     state_machine: &mut StateMachine<E, P>,
     header: &AvailHeader,
     header_store: &mut HeaderStore,
-    prover_mode: ProverMode,
-) -> Result<(P, NexusHeader, HashMap<H256, bool>, Option<TreeUpdateBatch>), Error>
+    zkvm_prover: Z,
+) -> Result<
+    (
+        P,
+        NexusHeader,
+        HashMap<H256, Result<(), NexusTxError>>,
+        Option<TreeUpdateBatch>,
+        BatchProfile,
+    ),
+    Error,
+>
 where
     <P as TryFrom<NexusProof>>::Error: std::fmt::Debug,
 {
-    let (tree_update_batch, state_update, tx_result): (
-        Option<jmt::storage::TreeUpdateBatch>,
-        nexus_core::types::StateUpdate,
-        HashMap<H256, bool>,
-    ) = state_machine
-        .execute_batch(&header, header_store, &txs)
-        .await?;
+    let (job, tx_result, tree_update_batch) =
+        prepare_batch(txs, state_machine, header, header_store).await?;
 
-    let (proof, result) = {
-        #[cfg(any(feature = "sp1"))]
-        let NEXUS_RUNTIME_ELF: &[u8] =
-            include_bytes!("../../prover/sp1-guest/elf/riscv32im-succinct-zkvm-elf");
-
-        let mut zkvm_prover = Z::new(NEXUS_RUNTIME_ELF.to_vec(), prover_mode);
-
-        let zkvm_txs: Result<Vec<TransactionZKVM>, anyhow::Error> = txs
-            .iter()
-            .map(|tx| {
-                if let TxParams::SubmitProof(submit_proof_tx) = &tx.params {
-                    //TODO: Remove transactions that error out from mempool
-                    let proof = submit_proof_tx.proof.clone();
-                    let receipt: P = P::try_from(proof).unwrap();
-                    zkvm_prover.add_proof_for_recursion(receipt).unwrap();
-                }
+    let proving_start = Instant::now();
+    let (mut proof, stats) = job.prove::<Z, P>(zkvm_prover, &|_| {})?;
+    let profile = BatchProfile::new(
+        txs.len(),
+        &stats.unwrap_or_default(),
+        proving_start.elapsed(),
+    );
+
+    let result: NexusHeader = proof.public_inputs()?;
+    header_store.push_front(&result);
+
+    Ok((proof, result, tx_result, tree_update_batch, profile))
+}
 
-                Ok(TransactionZKVM {
-                    signature: tx.signature.clone(),
-                    params: tx.params.clone(),
-                })
-            })
-            .collect();
+/// Same as [`execute_batch`], without blocking the async runtime while proving. The proof is
+/// generated by one of the prover workers when given, on the blocking pool otherwise.
+pub async fn execute_batch_async<E: ZKVMEnv>(
+    txs: &Vec<Transaction>,
+    state_machine: &mut StateMachine<E, Proof>,
+    header: &AvailHeader,
+    header_store: &mut HeaderStore,
+    runtime: &RuntimeUpgrade,
+    workers: Option<&ProverWorkers>,
+    prover_mode: &ProverMode,
+    options: &ProvingOptions,
+) -> Result<
+    (
+        Proof,
+        NexusHeader,
+        HashMap<H256, Result<(), NexusTxError>>,
+        Option<TreeUpdateBatch>,
+        BatchProfile,
+    ),
+    Error,
+> {
+    let backend = state_machine.backend();
+    if runtime.backend != backend {
+        return Err(anyhow!(
+            "{:?} runtime scheduled for a {:?} chain",
+            runtime.backend,
+            backend
+        ));
+    }
+    runtime.check_native()?;
+
+    let (job, tx_result, tree_update_batch) =
+        prepare_batch(txs, state_machine, header, header_store).await?;
 
-        let zkvm_txs = zkvm_txs?;
+    let proving_start = Instant::now();
+    let (proof, result, stats) = prove_batch(
+        job,
+        runtime.clone(),
+        backend,
+        workers.cloned(),
+        prover_mode.clone(),
+        options.clone(),
+    )
+    .await?;
+    let profile = BatchProfile::new(
+        txs.len(),
+        &stats.unwrap_or_default(),
+        proving_start.elapsed(),
+    );
+    header_store.push_front(&result);
+
+    Ok((proof, result, tx_result, tree_update_batch, profile))
+}
 
-        zkvm_prover.add_input(&zkvm_txs).unwrap();
-        zkvm_prover.add_input(&state_update).unwrap();
-        zkvm_prover.add_input(&header).unwrap();
-        zkvm_prover.add_input(&header_store).unwrap();
-        let mut proof = zkvm_prover.prove()?;
+/// Proves a batch executed by [`prepare_batch`], checking the proof is of the block built
+/// from the batch by the runtime.
+pub async fn prove_batch(
+    job: ProvingJob,
+    runtime: RuntimeUpgrade,
+    backend: ZKVMBackend,
+    workers: Option<ProverWorkers>,
+    prover_mode: ProverMode,
+    options: ProvingOptions,
+) -> Result<(Proof, NexusHeader, Option<ProvingStats>), Error> {
+    let expected_header = job.header(runtime.version);
+    let (mut proof, stats) = match workers {
+        Some(workers) => {
+            let elf = scheduled_elf(&runtime)?;
+            let request = WorkerRequest {
+                backend,
+                prover_mode: prover_mode.clone(),
+                elf: elf.clone(),
+                job,
+            };
+            let response = interruptible(workers.prove(&request), &options).await?;
+            let proof = Proof::try_from(response.proof)?;
+            if proof.backend() != backend {
+                return Err(anyhow!(
+                    "Prover worker returned a {:?} proof, expected {:?}",
+                    proof.backend(),
+                    backend
+                ));
+            }
+            let elf = match elf {
+                Some(i) => i,
+                None => runtime_elf(backend)?,
+            };
+            if let Err(e) = proof.verify(Some(runtime.img_id.to_le_bytes()), Some(elf), prover_mode)
+            {
+                return Err(anyhow!("Prover worker returned an invalid proof: {:?}", e));
+            }
+
+            (proof, response.stats)
+        }
+        None => {
+            let runtime = runtime.clone();
 
-        let result: NexusHeader = proof.public_inputs()?;
-        (proof, result)
+            prove_async(
+                move |progress| {
+                    job.prove::<Prover, Proof>(scheduled_prover(&runtime, prover_mode)?, progress)
+                },
+                options,
+            )
+            .await?
+        }
     };
 
-    header_store.push_front(&result);
+    let result: NexusHeader = proof.public_inputs()?;
+    if result.runtime_version != runtime.version {
+        return Err(anyhow!(
+            "Batch proven by runtime version {}, version {} is active",
+            result.runtime_version,
+            runtime.version
+        ));
+    }
+    //Proofs of a worker are valid, but have to be of the batch executed here.
+    if result != expected_header {
+        return Err(anyhow!(
+            "Proof is of a different batch, expected {:?}, got {:?}",
+            expected_header,
+            result
+        ));
+    }
 
-    Ok((proof, result, tx_result, tree_update_batch))
+    Ok((proof, result, stats))
 }
 
 #[instrument(
@@ -178,9 +754,11 @@ where
         mempool,
         state_machine,
         prover_mode,
+        proving_config,
         shutdown_rx,
         state,
-        receiver
+        receiver,
+        events
     )
 )]
 pub async fn execution_engine_handle(
@@ -189,19 +767,154 @@ pub async fn execution_engine_handle(
     mempool: Mempool,
     mut state_machine: StateMachine<ZKVM, Proof>,
     prover_mode: ProverMode,
+    backend: ZKVMBackend,
+    proving_config: ProvingConfig,
     mut shutdown_rx: watch::Receiver<bool>,
     state: Arc<Mutex<VmState>>,
+    events: EventSender,
 ) -> Result<(), anyhow::Error> {
-    info!("Starting execution engine in {:?} mode", prover_mode);
+    info!(
+        "Starting execution engine in {:?} mode with {:?} prover",
+        prover_mode, backend
+    );
+    if let Some(workers) = &proving_config.workers {
+        info!("👷 Proving on prover workers: {:?}", workers.urls());
+    }
+    let schedule = proving_config.schedule(backend)?;
+    for runtime in schedule.upgrades().iter().filter(|i| i.backend == backend) {
+        info!(
+            runtime_version = runtime.version,
+            activation_height = runtime.activation_height,
+            img_id = %hex::encode(runtime.img_id.to_le_bytes()),
+            "📜 Runtime scheduled"
+        );
+    }
+    let proving_options = ProvingOptions {
+        cancel: Some(shutdown_rx.clone()),
+        timeout: proving_config.timeout,
+        on_progress: Some(Arc::new(|progress: ProvingProgress| match progress {
+            ProvingProgress::Phase(ProvingPhase::Done) => debug!("Proving done"),
+            ProvingProgress::Phase(phase) => info!(?phase, "⚙️ Proving phase"),
+            ProvingProgress::Segments { done, total } => {
+                debug!(done, total, "Proving segments")
+            }
+        })),
+    };
     const MAX_HEADERS: usize = 5;
     let mut header_array: Vec<Header> = Vec::new();
 
+    //Blocks are committed once executed and proven in the background, one batch per prover
+    //worker at a time. Execution pauses while as many blocks wait for a prover.
+    let max_in_flight = match &proving_config.workers {
+        Some(workers) => workers.urls().len(),
+        None => 1,
+    };
+    let mut queue: VecDeque<PendingProof> = pending_proofs(&node_db).await?;
+    let mut in_flight: VecDeque<(NexusHeader, usize, ProofTask)> = VecDeque::new();
+    let mut failed_attempts: HashMap<u32, u32> = HashMap::new();
+    let spawn_proof = |pending: PendingProof, delay: Duration| {
+        let runtime = schedule.active(backend, pending.header.number)?.clone();
+        let tx_count = pending.job.txs.len();
+        let task = tokio::spawn(proof_task(
+            pending.job,
+            pending.header.clone(),
+            runtime,
+            schedule.clone(),
+            proving_config.workers.clone(),
+            prover_mode.clone(),
+            proving_options.clone(),
+            delay,
+        ));
+
+        Ok::<_, Error>((pending.header, tx_count, task))
+    };
+    if !queue.is_empty() {
+        info!(
+            pending_blocks = queue.len(),
+            "🔁 Resuming proofs of committed blocks"
+        );
+    }
+
     loop {
         if *shutdown_rx.borrow() {
             info!("Shutdown signal received, stopping execution engine");
             break;
         }
 
+        while in_flight.len() < max_in_flight {
+            let pending = match queue.pop_front() {
+                Some(i) => i,
+                None => break,
+            };
+
+            in_flight.push_back(spawn_proof(pending, Duration::ZERO)?);
+        }
+
+        //Proofs are stored in block order.
+        let oldest_done = match in_flight.front() {
+            Some((_, _, task)) => task.is_finished() || queue.len() >= max_in_flight,
+            None => false,
+        };
+        if oldest_done {
+            let (header, tx_count, task) = match in_flight.pop_front() {
+                Some(i) => i,
+                None => continue,
+            };
+            let result = match task.await {
+                Ok(i) => i,
+                Err(e) => Err(anyhow!("Proving task failed: {:?}", e)),
+            };
+
+            match result {
+                Ok((proof, stats, proving_time)) => {
+                    failed_attempts.remove(&header.number);
+                    store_block_proof(&node_db, &header, tx_count, proof, stats, proving_time)
+                        .await?;
+                }
+                Err(e) => {
+                    //The block stays pending, and is proven again on restart.
+                    if let Some(ProvingInterrupted::Cancelled) = e.downcast_ref() {
+                        info!(
+                            nexus_block = header.number,
+                            "🛑 Shutdown signal received while proving, proof resumes on restart"
+                        );
+                        break;
+                    }
+
+                    //Stops like on runtimes the node does not run, the block stays pending.
+                    if e.downcast_ref::<InvalidBlockProof>().is_some() {
+                        error!(
+                            nexus_block = header.number,
+                            error = ?e,
+                            "❌ Block proof does not match the block"
+                        );
+                        return Err(e);
+                    }
+
+                    //Timeouts, failed workers and proofs failing post-processing are retried,
+                    //later blocks wait behind.
+                    let attempts = failed_attempts.entry(header.number).or_insert(0);
+                    *attempts += 1;
+                    let delay = proving_backoff(*attempts);
+                    error!(
+                        nexus_block = header.number,
+                        attempts = *attempts,
+                        retry_in_secs = delay.as_secs(),
+                        error = ?e,
+                        "❌ Block proving failed, retrying"
+                    );
+
+                    let pending = {
+                        let db_lock = node_db.lock().await;
+                        get_pending_proof(&db_lock, &header.hash())?
+                    };
+                    in_flight.push_front(spawn_proof(pending, delay)?);
+                }
+            }
+
+            continue;
+        }
+
         let header_opt = {
             let mut lock = receiver.lock().await;
             lock.try_recv().ok()
@@ -246,67 +959,93 @@ pub async fn execution_engine_handle(
                 "📦 Starting batch processing"
             );
 
+            let number = match old_headers.first() {
+                Some(i) => i.number + 1,
+                None => 0,
+            };
+            let runtime = schedule.active(backend, number)?;
+            if runtime.activation_height == number && number != 0 {
+                info!(
+                    nexus_block = number,
+                    runtime_version = runtime.version,
+                    "⬆️ Runtime upgrade activated"
+                );
+            }
+            //Stops at the first block of another runtime, resumed once the node runs it.
+            if let Err(e) = runtime.check_native() {
+                error!(error = ?e, nexus_block = number, "❌ Node does not run the active runtime");
+                return Err(e);
+            }
+
             debug!("🔄 Beginning batch execution");
-            match execute_batch::<Prover, Proof, ZKVM>(
+            let (job, tx_result, tree_update_batch) = match prepare_batch(
                 &txs,
                 &mut state_machine,
                 &AvailHeader::from(&header),
-                &mut old_headers,
-                prover_mode.clone(),
+                &old_headers,
+            )
+            .await
+            {
+                Ok(i) => i,
+                Err(e) => {
+                    error!(error = ?e, "❌ Batch execution failed");
+                    return Err(e);
+                }
+            };
+            let pending = PendingProof {
+                header: job.header(runtime.version),
+                job,
+            };
+            let result = &pending.header;
+            old_headers.push_front(result);
+
+            let updated_version = state.lock().await.get_version(false)?;
+            info!(
+                nexus_block = result.number,
+                batch_hash = %hex::encode(result.hash().as_slice()),
+                state_root = %hex::encode(result.state_root.as_slice()),
+                state_version = ?updated_version,
+                "✨ Batch execution completed"
+            );
+
+            info!("💾 Starting batch commit");
+            match save_batch_information(
+                &node_db,
+                &mempool,
+                &mut state_machine,
+                &state,
+                &events,
+                ProcessedBatchInfo {
+                    avail_header: &header,
+                    header: result,
+                    pending: &pending,
+                    txs_result: &tx_result,
+                    tree_update_batch,
+                    txs: &txs,
+                    mempool_index: &index,
+                    updated_header_store: &old_headers,
+                    jmt_version: match updated_version {
+                        Some(i) => i,
+                        None => 0,
+                    },
+                },
             )
             .await
             {
-                Ok((_, result, tx_result, tree_update_batch)) => {
-                    let updated_version = state.lock().await.get_version(false)?;
+                Ok(_) => {
+                    let successful_txs = tx_result.values().filter(|result| result.is_ok()).count();
                     info!(
                         nexus_block = result.number,
                         batch_hash = %hex::encode(result.hash().as_slice()),
                         state_root = %hex::encode(result.state_root.as_slice()),
-                        state_version = ?updated_version,
-                        "✨ Batch execution completed"
+                        total_txs = txs.len(),
+                        successful_txs = successful_txs,
+                        failed_txs = txs.len() - successful_txs,
+                        "✅ Batch processing completed successfully"
                     );
-
-                    info!("💾 Starting batch commit");
-                    match save_batch_information(
-                        &node_db,
-                        &mempool,
-                        &mut state_machine,
-                        ProcessedBatchInfo {
-                            avail_header: &header,
-                            header: &result,
-                            txs_result: &tx_result,
-                            tree_update_batch,
-                            txs: &txs,
-                            mempool_index: &index,
-                            updated_header_store: &old_headers,
-                            jmt_version: match updated_version {
-                                Some(i) => i,
-                                None => 0,
-                            },
-                        },
-                    )
-                    .await
-                    {
-                        Ok(_) => {
-                            let successful_txs =
-                                tx_result.values().filter(|&&success| success).count();
-                            info!(
-                                nexus_block = result.number,
-                                batch_hash = %hex::encode(result.hash().as_slice()),
-                                state_root = %hex::encode(result.state_root.as_slice()),
-                                total_txs = txs.len(),
-                                successful_txs = successful_txs,
-                                failed_txs = txs.len() - successful_txs,
-                                "✅ Batch processing completed successfully"
-                            );
-                        }
-                        Err(e) => error!(error = ?e, "❌ Failed to commit batch"),
-                    }
-                }
-                Err(e) => {
-                    error!(error = ?e, "❌ Batch execution failed");
-                    return Err(e);
+                    queue.push_back(pending);
                 }
+                Err(e) => error!(error = ?e, "❌ Failed to commit batch"),
             }
             info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ \n");
         } else {
@@ -315,18 +1054,150 @@ pub async fn execution_engine_handle(
         }
     }
 
+    //Proofs still running are resumed on restart.
+    for (_, _, task) in in_flight {
+        task.abort();
+    }
+
     info!("Execution engine stopped");
     Ok(())
 }
 
+/// Block committed before its proof, kept in the node DB until proven so proving resumes
+/// after a restart.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingProof {
+    pub header: NexusHeader,
+    pub job: ProvingJob,
+}
+
+type ProofTask =
+    tokio::task::JoinHandle<Result<(NexusBlockProof, Option<ProvingStats>, Duration), Error>>;
+
+/// Proves a committed block, then post-processes and verifies its proof. The proving time
+/// and timeout only cover the proof of the batch.
+async fn proof_task(
+    job: ProvingJob,
+    header: NexusHeader,
+    runtime: RuntimeUpgrade,
+    schedule: RuntimeSchedule,
+    workers: Option<ProverWorkers>,
+    prover_mode: ProverMode,
+    options: ProvingOptions,
+    delay: Duration,
+) -> Result<(NexusBlockProof, Option<ProvingStats>, Duration), Error> {
+    sleep(delay).await;
+
+    //Block proofs are kept recursable, Groth16 wrapping happens once proven.
+    let proving_mode = match &prover_mode {
+        ProverMode::Groth16 => ProverMode::Compressed,
+        _ => prover_mode.clone(),
+    };
+    let backend = runtime.backend;
+    let proving_start = Instant::now();
+    let (proof, _, stats) = prove_batch(
+        job,
+        runtime,
+        backend,
+        workers,
+        proving_mode,
+        options.clone(),
+    )
+    .await?;
+    let proving_time = proving_start.elapsed();
+
+    //Compression, Groth16 wrapping and verification are proving work too, kept off the
+    //runtime. They can only be cancelled.
+    let post_processing_options = ProvingOptions {
+        cancel: options.cancel,
+        ..ProvingOptions::default()
+    };
+    let block_proof = prove_async(
+        move |_| block_proof(proof, &header, &prover_mode, &schedule),
+        post_processing_options,
+    )
+    .await?;
+
+    Ok((block_proof, stats, proving_time))
+}
+
+/// Blocks committed but not proven yet, oldest first.
+async fn pending_proofs(node_db: &Arc<Mutex<NodeDB>>) -> Result<VecDeque<PendingProof>, Error> {
+    let db_lock = node_db.lock().await;
+    let hashes: Vec<H256> = db_lock.get(b"pending_proofs")?.unwrap_or_default();
+
+    hashes
+        .iter()
+        .map(|nexus_hash| get_pending_proof(&db_lock, nexus_hash))
+        .collect()
+}
+
+fn get_pending_proof(db_lock: &NodeDB, nexus_hash: &H256) -> Result<PendingProof, Error> {
+    match db_lock.get::<PendingProof>(&[nexus_hash.as_slice(), b"-pending"].concat())? {
+        Some(i) => Ok(i),
+        None => Err(anyhow!(
+            "Pending proof of block {} not found",
+            hex::encode(nexus_hash.as_slice())
+        )),
+    }
+}
+
+/// Delay before proving a block again, doubling with each failed attempt up to
+/// `MAX_PROVING_BACKOFF_SECS`.
+pub fn proving_backoff(failed_attempts: u32) -> Duration {
+    let secs = 2u64.saturating_pow(failed_attempts.min(32));
+
+    Duration::from_secs(secs.min(MAX_PROVING_BACKOFF_SECS))
+}
+
+/// Stores the proof of a committed block along with its profile, so it is no longer pending.
+async fn store_block_proof(
+    node_db: &Arc<Mutex<NodeDB>>,
+    header: &NexusHeader,
+    tx_count: usize,
+    proof: NexusBlockProof,
+    stats: Option<ProvingStats>,
+    proving_time: Duration,
+) -> Result<(), Error> {
+    let profile = BatchProfile::new(tx_count, &stats.unwrap_or_default(), proving_time);
+    info!(
+        nexus_block = header.number,
+        total_cycles = profile.total_cycles,
+        segments = profile.segments,
+        pre_state_cycles = profile.pre_state_cycles,
+        stf_cycles = profile.stf_cycles,
+        recursion_cycles = profile.recursion_cycles,
+        proving_time_ms = profile.proving_time_ms,
+        "⏱️ Batch proving profile"
+    );
+
+    let nexus_hash = header.hash();
+    let db_lock = node_db.lock().await;
+    let mut pending_proofs: Vec<H256> = db_lock.get(b"pending_proofs")?.unwrap_or_default();
+    pending_proofs.retain(|i| i != &nexus_hash);
+
+    let mut batch_transaction = BatchTransaction::new();
+    batch_transaction.put(&[nexus_hash.as_slice(), b"-proof"].concat(), &proof)?;
+    batch_transaction.put(&[nexus_hash.as_slice(), b"-profile"].concat(), &profile)?;
+    batch_transaction.put(b"pending_proofs", &pending_proofs)?;
+    batch_transaction.delete(&[nexus_hash.as_slice(), b"-pending"].concat());
+    db_lock.put_batch(batch_transaction)?;
+
+    info!(nexus_block = header.number, "🔏 Block proof stored");
+
+    Ok(())
+}
+
 #[instrument(
     level = "debug",
-    skip(node_db, mempool, state_machine, processed_batch_info)
+    skip(node_db, mempool, state_machine, state, events, processed_batch_info)
 )]
 pub async fn save_batch_information<'a>(
     node_db: &Arc<Mutex<NodeDB>>,
     mempool: &Mempool,
     state_machine: &mut StateMachine<ZKVM, Proof>,
+    state: &Arc<Mutex<VmState>>,
+    events: &EventSender,
     processed_batch_info: ProcessedBatchInfo<'a>,
 ) -> Result<(), Error> {
     debug!(
@@ -367,8 +1238,9 @@ pub async fn save_batch_information<'a>(
     );
 
     let mut txs_result_vec: Vec<TransactionResult> = vec![];
+    let mut tx_events: Vec<NexusEvent> = vec![];
 
-    for (tx_hash, success) in processed_batch_info.txs_result.iter() {
+    for (tx_hash, result) in processed_batch_info.txs_result.iter() {
         let db_lock = node_db.lock().await;
         let mut tx: TransactionWithStatus =
             match db_lock.get::<TransactionWithStatus>(tx_hash.as_slice())? {
@@ -377,18 +1249,34 @@ pub async fn save_batch_information<'a>(
             };
 
         tx.block_hash = Some(nexus_hash.clone());
-        tx.status = if success.clone() {
-            TransactionStatus::Successful
-        } else {
-            TransactionStatus::Failed
+        tx.status = match result {
+            Ok(()) => TransactionStatus::Successful,
+            Err(_) => TransactionStatus::Failed,
         };
+        tx.error = result.as_ref().err().map(TxError::from);
 
         batch_transaction.put(tx_hash.as_slice(), &tx);
+        tx_events.push(NexusEvent::TxStatus {
+            tx_hash: tx_hash.clone(),
+            status: tx.status.clone(),
+            block_hash: tx.block_hash.clone(),
+            error: tx.error.clone(),
+        });
         txs_result_vec.push(TransactionResult {
             hash: tx_hash.clone(),
-            result: success.clone(),
+            result: result.is_ok(),
+            error: tx.error.clone(),
         });
     }
+    {
+        let db_lock = node_db.lock().await;
+        index_batch(
+            &db_lock,
+            &mut batch_transaction,
+            processed_batch_info.txs,
+            processed_batch_info.txs_result,
+        )?;
+    }
     batch_transaction.put(nexus_hash.as_slice(), &processed_batch_info.header);
     batch_transaction.put(
         &[nexus_hash.as_slice(), b"-block"].concat(),
@@ -409,22 +1297,84 @@ pub async fn save_batch_information<'a>(
         &nexus_hash,
     );
     let db_lock = node_db.lock().await;
+    //Removed again once the block is proven, see `store_block_proof`.
+    let mut pending_proofs: Vec<H256> = db_lock.get(b"pending_proofs")?.unwrap_or_default();
+    pending_proofs.push(nexus_hash.clone());
+    batch_transaction.put(b"pending_proofs", &pending_proofs)?;
+    batch_transaction.put(
+        &[nexus_hash.as_slice(), b"-pending"].concat(),
+        processed_batch_info.pending,
+    )?;
     db_lock.put_batch(batch_transaction)?;
 
     db_lock
         .set_current_root(&processed_batch_info.header.state_root)
         .unwrap();
+    drop(db_lock);
     if let Some(i) = processed_batch_info.mempool_index {
         mempool.clear_upto_tx(i.clone()).await;
     };
 
+    publish_batch_events(state, events, &processed_batch_info, tx_events).await;
+
     Ok(())
 }
 
+/// Pushes events for a committed batch to websocket subscribers.
+async fn publish_batch_events<'a>(
+    state: &Arc<Mutex<VmState>>,
+    events: &EventSender,
+    processed_batch_info: &ProcessedBatchInfo<'a>,
+    tx_events: Vec<NexusEvent>,
+) {
+    //No subscribers connected, nothing to publish.
+    if events.receiver_count() == 0 {
+        return;
+    }
+
+    let nexus_hash = processed_batch_info.header.hash();
+    let _ = events.send(NexusEvent::NewHeader {
+        header: processed_batch_info.header.clone(),
+    });
+
+    for event in tx_events {
+        let _ = events.send(event);
+    }
+
+    let state_lock = state.lock().await;
+    for tx in processed_batch_info.txs.iter() {
+        let success = match processed_batch_info.txs_result.get(&tx.hash()) {
+            Some(i) => i.is_ok(),
+            None => false,
+        };
+        if !success {
+            continue;
+        }
+
+        let app_account_id = match &tx.params {
+            TxParams::SubmitProof(i) => H256::from(i.app_id.0),
+            TxParams::InitAccount(i) => H256::from(i.app_id.0),
+        };
+
+        match state_lock.get(&app_account_id, processed_batch_info.jmt_version) {
+            Ok(Some(account)) => {
+                let _ = events.send(NexusEvent::AccountUpdate {
+                    app_account_id,
+                    account,
+                    nexus_hash: nexus_hash.clone(),
+                });
+            }
+            Ok(None) => (),
+            Err(e) => error!(error = ?e, "Failed to read account state for event"),
+        }
+    }
+}
+
 pub struct ProcessedBatchInfo<'a> {
     avail_header: &'a Header,
     header: &'a NexusHeader,
-    txs_result: &'a HashMap<H256, bool>,
+    pending: &'a PendingProof,
+    txs_result: &'a HashMap<H256, Result<(), NexusTxError>>,
     tree_update_batch: Option<TreeUpdateBatch>,
     txs: &'a Vec<Transaction>,
     mempool_index: &'a Option<usize>,
@@ -438,8 +1388,10 @@ pub fn run_server(
     state: Arc<Mutex<VmState>>,
     mut shutdown_rx: watch::Receiver<bool>,
     port: u32,
+    events: EventSender,
+    schedule: RuntimeSchedule,
 ) -> tokio::task::JoinHandle<()> {
-    let routes = routes(mempool, node_db, state.clone());
+    let routes = routes(mempool, node_db, state.clone(), events, schedule);
     let cors = warp::cors()
         .allow_any_origin()
         .allow_methods(vec!["POST"])
@@ -469,7 +1421,8 @@ pub async fn run_nexus(
     relayer_mutex: Arc<Mutex<impl Relayer + Send + 'static>>,
     node_db: Arc<Mutex<NodeDB>>,
     mut state_machine: StateMachine<ZKVM, Proof>,
-    (prover_mode, server_port): (ProverMode, u32),
+    (prover_mode, backend, server_port): (ProverMode, ZKVMBackend, u32),
+    proving_config: ProvingConfig,
     state: Arc<Mutex<VmState>>,
     mut shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Error> {
@@ -484,8 +1437,11 @@ pub async fn run_nexus(
 
         relayer.receiver()
     };
-    let mempool = Mempool::new(node_db.clone());
+    let mempool = Mempool::new(node_db.clone(), backend);
     let mempool_clone = mempool.clone();
+    let events = event_channel();
+    let events_clone = events.clone();
+    let schedule = proving_config.schedule(backend)?;
     let relayer_handle = tokio::spawn(async move {
         relayer_handle(relayer_mutex, db_clone_2, shutdown_rx_1.clone()).await
     });
@@ -497,13 +1453,24 @@ pub async fn run_nexus(
             mempool_clone,
             state_machine,
             prover_mode,
+            backend,
+            proving_config,
             shutdown_rx_2.clone(),
             state_2.clone(),
+            events_clone,
         )
         .await
     });
 
-    let server_handle = run_server(mempool, db_clone, state, shutdown_rx, server_port);
+    let server_handle = run_server(
+        mempool,
+        db_clone,
+        state,
+        shutdown_rx,
+        server_port,
+        events,
+        schedule,
+    );
 
     let result = tokio::try_join!(server_handle, execution_engine, relayer_handle);
 
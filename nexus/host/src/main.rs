@@ -1,16 +1,26 @@
 pub use avail_subxt::Header;
-use nexus_core::{state_machine::StateMachine, zkvm::ProverMode};
+use nexus_core::{
+    genesis::Genesis,
+    runtime::RuntimeSchedule,
+    state_machine::StateMachine,
+    zkvm::{ProverMode, ZKVMBackend},
+};
 
+use nexus_core::zkvm::any::AnyProof as Proof;
+#[cfg(all(feature = "mock", not(any(feature = "risc0", feature = "sp1"))))]
+use nexus_core::zkvm::mock::MockZKVM as ZKVM;
 #[cfg(any(feature = "risc0"))]
-use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
+use nexus_core::zkvm::risczero::ZKVM;
 
-use host::{run_nexus, setup_components};
-#[cfg(any(feature = "sp1"))]
-use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
+use host::worker::{run_worker, ProverWorkers, WorkerConfig, DEFAULT_MAX_JOB_SIZE};
+use host::{apply_genesis, builtin_schedule, run_nexus, setup_components, ProvingConfig};
+#[cfg(all(feature = "sp1", not(feature = "risc0")))]
+use nexus_core::zkvm::sp1::SP1ZKVM as ZKVM;
 pub use relayer::{Relayer, SimpleRelayer};
 use std::env::args;
 use std::io::Write;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{watch, Mutex};
 use tracing::{error, info};
 use tracing_subscriber::{fmt, EnvFilter};
@@ -38,16 +48,138 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("⚠️  Running in dev mode - proofs are not valid");
     }
 
+    let groth16_flag = args.iter().any(|arg| arg == "--groth16");
+
     let prover_mode = if dev_flag {
         ProverMode::MockProof
+    } else if groth16_flag {
+        ProverMode::Groth16
     } else {
         ProverMode::Compressed
     };
 
+    let backend = match args
+        .iter()
+        .find(|arg| arg.starts_with("--zkvm="))
+        .map(|arg| arg.trim_start_matches("--zkvm=").parse::<ZKVMBackend>())
+    {
+        Some(Ok(i)) => i,
+        Some(Err(e)) => return Err(e.into()),
+        None => ZKVMBackend::default(),
+    };
+
+    //Shared by workers and the nodes shipping jobs to them.
+    let worker_token = args
+        .iter()
+        .find(|arg| arg.starts_with("--worker-token="))
+        .map(|arg| arg.trim_start_matches("--worker-token=").to_string());
+
+    //Worker mode only proves jobs shipped by nodes, backend and mode come with each job.
+    if args.iter().any(|arg| arg == "--worker") {
+        let port = match args
+            .iter()
+            .find(|arg| arg.starts_with("--worker-port="))
+            .map(|arg| arg.trim_start_matches("--worker-port=").parse::<u32>())
+        {
+            Some(Ok(i)) => i,
+            Some(Err(e)) => return Err(e.into()),
+            None => 7100,
+        };
+        let max_job_mb = match args
+            .iter()
+            .find(|arg| arg.starts_with("--max-job-mb="))
+            .map(|arg| arg.trim_start_matches("--max-job-mb=").parse::<u64>())
+        {
+            Some(Ok(i)) => i,
+            Some(Err(e)) => return Err(e.into()),
+            None => DEFAULT_MAX_JOB_SIZE / (1024 * 1024),
+        };
+        let mut config = WorkerConfig::new(port);
+        config.token = worker_token.clone();
+        config.max_job_size = max_job_mb * 1024 * 1024;
+        //Only local nodes can reach the worker unless told otherwise.
+        if let Some(host) = args
+            .iter()
+            .find(|arg| arg.starts_with("--worker-host="))
+            .map(|arg| arg.trim_start_matches("--worker-host="))
+        {
+            config.host = host.to_string();
+        }
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        rt.block_on(async {
+            let shutdown_task = tokio::spawn(async move {
+                if let Err(err) = tokio::signal::ctrl_c().await {
+                    error!("Failed to listen for shutdown signal: {:?}", err);
+                } else {
+                    info!("Received shutdown signal, initiating graceful shutdown");
+                    let _ = shutdown_tx.send(true);
+                }
+            });
+
+            if let Err(e) = tokio::try_join!(shutdown_task, run_worker(config, shutdown_rx)) {
+                error!("Error during execution: {:?}", e);
+            }
+        });
+
+        info!("Prover worker stopped");
+        return Ok(());
+    }
+
+    let workers = match args
+        .iter()
+        .find(|arg| arg.starts_with("--prover-workers="))
+        .map(|arg| arg.trim_start_matches("--prover-workers="))
+    {
+        Some(urls) => Some(
+            ProverWorkers::new(urls.split(',').map(|url| url.to_string()).collect())?
+                .with_token(worker_token),
+        ),
+        None => None,
+    };
+
+    let schedule = match args
+        .iter()
+        .find(|arg| arg.starts_with("--runtime-schedule="))
+        .map(|arg| arg.trim_start_matches("--runtime-schedule="))
+    {
+        Some(path) => Some(RuntimeSchedule::from_file(path)?),
+        None => None,
+    };
+
+    let timeout = match args
+        .iter()
+        .find(|arg| arg.starts_with("--proving-timeout="))
+        .map(|arg| arg.trim_start_matches("--proving-timeout=").parse::<u64>())
+    {
+        Some(Ok(i)) => Some(Duration::from_secs(i)),
+        Some(Err(e)) => return Err(e.into()),
+        None => None,
+    };
+
+    //Checked against the runtime active at height 0, before any block is processed.
+    let genesis = match args
+        .iter()
+        .find(|arg| arg.starts_with("--genesis="))
+        .map(|arg| arg.trim_start_matches("--genesis="))
+    {
+        Some(path) => {
+            let runtime = match &schedule {
+                Some(i) => i.active(backend, 0)?.clone(),
+                None => builtin_schedule(backend)?.active(backend, 0)?.clone(),
+            };
+
+            Some((Genesis::from_file(path)?, runtime))
+        }
+        None => None,
+    };
+
     print_animated_logo(&prover_mode);
+    info!("Using {:?} prover", backend);
 
     let (node_db, state) = setup_components("./db");
-    let mut state_machine = StateMachine::<ZKVM, Proof>::new(state.clone());
+    let mut state_machine = StateMachine::<ZKVM, Proof>::with_backend(state.clone(), backend);
 
     let avail_rpc = args
         .iter()
@@ -75,6 +207,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         });
 
+        if let Some((genesis, runtime)) = &genesis {
+            if let Err(e) = apply_genesis(&node_db, &state, genesis, runtime).await {
+                error!("Failed to apply genesis: {:?}", e);
+                return;
+            }
+        }
+
         // Spawn the main Nexus logic
         info!("Starting execution engine");
         let nexus_task = tokio::spawn(async move {
@@ -82,7 +221,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 relayer_mutex,
                 node_db,
                 state_machine,
-                (prover_mode, 7000),
+                (prover_mode, backend, 7000),
+                ProvingConfig {
+                    workers,
+                    timeout,
+                    schedule,
+                },
                 state,
                 shutdown_rx,
             )
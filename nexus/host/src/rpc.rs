@@ -2,13 +2,16 @@ use core::convert::Infallible;
 use jmt::ValueHash;
 use nexus_core::db::NodeDB;
 use nexus_core::mempool::Mempool;
+use nexus_core::runtime::RuntimeSchedule;
 use nexus_core::state::VmState;
 use nexus_core::state_machine::StateMachine;
+use nexus_core::traits::NexusTransaction;
 use nexus_core::types::{
-    AccountState, AccountWithProof, AvailHeader, HeaderStore, NexusBlockWithPointers,
-    NexusBlockWithTransactions, NexusHeader, StatementDigest, Transaction, TransactionWithStatus,
-    H256,
+    AccountOwner, AccountState, AccountWithProof, AppAccountId, AvailHeader, HeaderStore,
+    NexusBlock, NexusBlockWithPointers, NexusBlockWithTransactions, NexusHeader, StatementDigest,
+    Transaction, TransactionWithStatus, TxParams, H256,
 };
+pub use nexus_core::types::{AccountStateHex, AccountWithProofHex, NexusHeaderHex};
 use nexus_core::utils::hasher::Sha256;
 use serde::{Deserialize, Serialize};
 use serde_json;
@@ -22,72 +25,16 @@ use warp::{
     Rejection,
 };
 
-use crate::AvailToNexusPointer;
+use crate::events::{handle_ws, EventSender};
+use crate::index::{
+    get_account_owner, list_accounts, list_app_txs, list_blocks, AccountListItem, Page, PageRequest,
+};
+use crate::jsonrpc::{handle_json_rpc, JsonRpcContext};
+use crate::{AvailToNexusPointer, BatchProfile, NexusBlockProof, NexusBlockProofHex};
 
 use utoipa::OpenApi;
 use utoipa_swagger_ui::Config;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-pub struct AccountStateHex {
-    pub statement: String,
-    pub state_root: String,
-    pub start_nexus_hash: String,
-    pub last_proof_height: u32,
-    pub height: u32,
-}
-
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-pub struct NexusHeaderHex {
-    pub parent_hash: String,
-    pub prev_state_root: String,
-    pub state_root: String,
-    pub avail_header_hash: String,
-    pub number: u32,
-}
-
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-pub struct AccountWithProofHex {
-    pub account: AccountStateHex,
-    pub proof: Vec<String>,
-    pub value_hash: String,
-    pub nexus_header: NexusHeaderHex,
-}
-
-impl From<NexusHeader> for NexusHeaderHex {
-    fn from(value: NexusHeader) -> Self {
-        Self {
-            parent_hash: hex::encode(value.parent_hash.as_fixed_slice()),
-            prev_state_root: hex::encode(value.prev_state_root.as_fixed_slice()),
-            state_root: hex::encode(value.state_root.as_fixed_slice()),
-            avail_header_hash: hex::encode(value.avail_header_hash.as_fixed_slice()),
-            number: value.number,
-        }
-    }
-}
-
-impl From<AccountState> for AccountStateHex {
-    fn from(value: AccountState) -> Self {
-        Self {
-            statement: value.statement.encode().to_string(),
-            state_root: hex::encode(value.state_root),
-            start_nexus_hash: hex::encode(value.start_nexus_hash),
-            last_proof_height: value.last_proof_height,
-            height: value.height,
-        }
-    }
-}
-
-impl From<AccountWithProof> for AccountWithProofHex {
-    fn from(value: AccountWithProof) -> Self {
-        Self {
-            account: AccountStateHex::from(value.account),
-            proof: value.proof_hex,
-            value_hash: value.value_hash_hex,
-            nexus_header: NexusHeaderHex::from(value.nexus_header),
-        }
-    }
-}
-
 #[derive(OpenApi)]
 #[openapi(
     paths(
@@ -95,10 +42,16 @@ impl From<AccountWithProof> for AccountWithProofHex {
         submit_tx,
         tx_status,
         get_block,
+        get_block_proof,
+        get_block_profile,
         get_state,
         get_state_hex,
         get_header,
-        range
+        range,
+        get_blocks,
+        get_app_txs,
+        get_accounts,
+        get_owner
     ),
     components(
         schemas(
@@ -111,8 +64,14 @@ impl From<AccountWithProof> for AccountWithProofHex {
             nexus_core::types::InitAccount,
             nexus_core::types::NexusHeader,
             nexus_core::types::TransactionStatus,
+            nexus_core::types::TxError,
+            nexus_core::types::AccountOwner,
             nexus_core::state::types::AccountState,
-            nexus_core::state::types::StatementDigest
+            nexus_core::state::types::StatementDigest,
+            nexus_core::zkvm::ZKVMBackend,
+            nexus_core::zkvm::ReceiptKind,
+            crate::NexusBlockProofHex,
+            crate::BatchProfile
         )
     ),
     tags(
@@ -134,100 +93,148 @@ async fn health_check() -> impl Reply {
     warp::reply::json(&serde_json::json!({"status": "Alive ser."}))
 }
 
-/// Submit a new transaction to the mempool
-#[utoipa::path(
-    post,
-    path = "/tx",
-    tag = "nexus",
-    request_body = Transaction,
-    responses(
-        (status = 200, description = "Transaction added successfully", body = String),
-        (status = 500, description = "Internal mempool error", body = String)
-    )
-)]
-async fn submit_tx(mempool: Mempool, tx: Transaction) -> Result<WithStatus<String>, Rejection> {
+/// Error returned by the shared handlers, carrying both the REST status code
+/// and the JSON-RPC error code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: i64,
+    pub message: String,
+}
+
+pub const INVALID_PARAMS_CODE: i64 = -32602;
+pub const INTERNAL_ERROR_CODE: i64 = -32603;
+pub const NOT_FOUND_CODE: i64 = -32001;
+pub const MEMPOOL_ERROR_CODE: i64 = -32002;
+
+impl ApiError {
+    pub fn invalid_params(message: &str) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            code: INVALID_PARAMS_CODE,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn not_found(message: &str) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            code: NOT_FOUND_CODE,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn internal(message: &str) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            code: INTERNAL_ERROR_CODE,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+
+        self
+    }
+}
+
+fn reply<T: Serialize>(result: Result<T, ApiError>) -> Result<WithStatus<String>, Rejection> {
+    match result {
+        Ok(i) => match serde_json::to_string(&i) {
+            Ok(serialized_response) => Ok(warp::reply::with_status(
+                serialized_response,
+                warp::http::StatusCode::OK,
+            )),
+            Err(_) => Ok(warp::reply::with_status(
+                "Internal encoding error".to_string(),
+                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        },
+        Err(e) => Ok(warp::reply::with_status(e.message, e.status)),
+    }
+}
+
+pub async fn add_tx(
+    mempool: &Mempool,
+    state: &Arc<Mutex<VmState>>,
+    tx: Transaction,
+) -> Result<H256, ApiError> {
+    let tx_hash = tx.hash();
+
+    //Unsigned transactions are still accepted, but a provided signature must be valid.
+    if tx.signer.is_some() && tx.verify_signature().is_err() {
+        return Err(ApiError::invalid_params("Invalid signature"));
+    }
+
+    let account = match &tx.params {
+        TxParams::SubmitProof(submit_proof) => {
+            latest_account(state, &submit_proof.app_id.as_h256()).await?
+        }
+        _ => None,
+    };
+
+    if let Err(e) = mempool.check_tx(&tx, account.as_ref()) {
+        return Err(ApiError::invalid_params(&e.to_string()));
+    }
+
     match mempool.add_tx(tx).await {
-        Ok(()) => Ok(warp::reply::with_status(
-            "Added tx".to_string(),
-            warp::http::StatusCode::OK,
-        )),
-        Err(_) => Ok(warp::reply::with_status(
-            "Internal Mempool error".to_string(),
-            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-        )),
+        Ok(()) => Ok(tx_hash),
+        Err(_) => Err(ApiError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            code: MEMPOOL_ERROR_CODE,
+            message: "Internal Mempool error".to_string(),
+        }),
     }
 }
 
-/// Get transaction and its status by hash
-#[utoipa::path(
-    get,
-    path = "/tx_status",
-    tag = "nexus",
-    params(
-        ("tx_hash" = String, Query, description = "Transaction hash in hex format")
-    ),
-    responses(
-        (status = 200, description = "Transaction status found", body = TransactionWithStatus),
-        (status = 404, description = "Transaction not found", body = String),
-        (status = 400, description = "Invalid hash format", body = String),
-        (status = 500, description = "Internal error", body = String)
-    )
-)]
-async fn tx_status(db: Arc<Mutex<NodeDB>>, tx_hash: H256) -> Result<WithStatus<String>, Rejection> {
+/// Account state at the latest committed version, if the account exists.
+async fn latest_account(
+    state: &Arc<Mutex<VmState>>,
+    app_account_id: &H256,
+) -> Result<Option<AccountState>, ApiError> {
+    let state_lock = state.lock().await;
+
+    let version = match state_lock.get_version(true) {
+        Ok(Some(i)) => i,
+        Ok(None) => return Ok(None),
+        Err(_) => return Err(ApiError::internal("Internal db error")),
+    };
+
+    match state_lock.get(app_account_id, version) {
+        Ok(i) => Ok(i),
+        Err(_) => Err(ApiError::internal("Internal db error")),
+    }
+}
+
+pub async fn fetch_tx_status(
+    db: &Arc<Mutex<NodeDB>>,
+    tx_hash: &H256,
+) -> Result<TransactionWithStatus, ApiError> {
     let db_lock = db.lock().await;
     match db_lock.get::<TransactionWithStatus>(tx_hash.as_slice()) {
-        Ok(Some(i)) => Ok(warp::reply::with_status(
-            serde_json::to_string(&i).expect("Failed to serialize Account to JSON"),
-            warp::http::StatusCode::OK,
-        )),
-        Ok(None) => Ok(warp::reply::with_status(
-            "Transaction not found".to_string(),
-            warp::http::StatusCode::NOT_FOUND,
-        )),
-        Err(_) => Ok(warp::reply::with_status(
-            "Internal error".to_string(),
-            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-        )),
+        Ok(Some(i)) => Ok(i),
+        Ok(None) => Err(ApiError::not_found("Transaction not found")),
+        Err(_) => Err(ApiError::internal("Internal error")),
     }
 }
 
-/// Get block by hash, number or latest
-#[utoipa::path(
-    get,
-    path = "/block",
-    tag = "nexus",
-    params(
-        ("block_hash" = Option<String>, Query, description = "Block hash in hex format. If not provided, returns the latest block"),
-        ("block_number" = Option<u32>, Query, description = "Block number to query. If not provided, returns the latest block")
-    ),
-    responses(
-        (status = 200, description = "Block found", body = NexusBlockWithTransactions),
-        (status = 404, description = "Block not found", body = String),
-        (status = 400, description = "Invalid hash format", body = String),
-        (status = 500, description = "Internal error", body = String)
-    )
-)]
-async fn get_block(
-    db: Arc<Mutex<NodeDB>>,
+/// Resolves the hash of the block at the given number or hash, or of the latest block.
+fn resolve_block_hash(
+    db_lock: &NodeDB,
     block_hash_opt: Option<H256>,
     block_number_opt: Option<u32>,
-) -> Result<WithStatus<String>, Rejection> {
-    let db_lock = db.lock().await;
-
-    let nexus_hash = if block_number_opt.is_some() {
-        let block_number = block_number_opt.unwrap();
+) -> Result<H256, ApiError> {
+    let nexus_hash = if let Some(block_number) = block_number_opt {
         match db_lock.get::<H256>(&[block_number.to_be_bytes().as_slice(), b"-block"].concat()) {
             Ok(Some(hash)) => hash,
             Ok(None) => {
-                return Ok(warp::reply::with_status(
-                    "Nexus height does not exist".to_string(),
-                    warp::http::StatusCode::BAD_REQUEST,
-                ))
+                return Err(ApiError::not_found("Nexus height does not exist")
+                    .with_status(StatusCode::BAD_REQUEST))
             }
             Err(_) => {
-                return Ok(warp::reply::with_status(
-                    "Internal error when retrieving block number to hash mapping".to_string(),
-                    warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+                return Err(ApiError::internal(
+                    "Internal error when retrieving block number to hash mapping",
                 ))
             }
         }
@@ -237,39 +244,33 @@ async fn get_block(
             None => match db_lock.get::<HeaderStore>(b"previous_headers") {
                 Ok(Some(headers)) => match headers.first().map(|h| h.hash()) {
                     Some(hash) => hash,
-                    None => {
-                        return Ok(warp::reply::with_status(
-                            "Latest headers not retrievable".to_string(),
-                            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-                        ))
-                    }
+                    None => return Err(ApiError::internal("Latest headers not retrievable")),
                 },
-                _ => {
-                    return Ok(warp::reply::with_status(
-                        "Latest headers not retrievable".to_string(),
-                        warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-                    ))
-                }
+                _ => return Err(ApiError::internal("Latest headers not retrievable")),
             },
         }
     };
 
-    let block =
-        match db_lock.get::<NexusBlockWithPointers>(&[nexus_hash.as_slice(), b"-block"].concat()) {
-            Ok(Some(b)) => b,
-            Ok(None) => {
-                return Ok(warp::reply::with_status(
-                    "Block not found".to_string(),
-                    warp::http::StatusCode::BAD_REQUEST,
-                ))
-            }
-            Err(_) => {
-                return Ok(warp::reply::with_status(
-                    "Error retrieving block".to_string(),
-                    warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-                ))
-            }
-        };
+    Ok(nexus_hash)
+}
+
+pub async fn fetch_block(
+    db: &Arc<Mutex<NodeDB>>,
+    block_hash_opt: Option<H256>,
+    block_number_opt: Option<u32>,
+) -> Result<NexusBlockWithTransactions, ApiError> {
+    let db_lock = db.lock().await;
+    let nexus_hash = resolve_block_hash(&db_lock, block_hash_opt, block_number_opt)?;
+
+    let block = match db_lock
+        .get::<NexusBlockWithPointers>(&[nexus_hash.as_slice(), b"-block"].concat())
+    {
+        Ok(Some(b)) => b,
+        Ok(None) => {
+            return Err(ApiError::not_found("Block not found").with_status(StatusCode::BAD_REQUEST))
+        }
+        Err(_) => return Err(ApiError::internal("Error retrieving block")),
+    };
 
     let txs = block
         .block
@@ -284,112 +285,112 @@ async fn get_block(
         .collect::<Vec<_>>();
 
     if txs.len() != block.block.transactions.len() {
-        return Ok(warp::reply::with_status(
-            "Some transactions not found".to_string(),
-            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-        ));
+        return Err(ApiError::internal("Some transactions not found"));
     }
 
-    let block_with_txs: NexusBlockWithTransactions = NexusBlockWithTransactions {
+    Ok(NexusBlockWithTransactions {
         transactions: txs,
         header: block.block.header,
+    })
+}
+
+pub async fn fetch_block_proof(
+    db: &Arc<Mutex<NodeDB>>,
+    schedule: &RuntimeSchedule,
+    block_hash_opt: Option<H256>,
+    block_number_opt: Option<u32>,
+) -> Result<NexusBlockProofHex, ApiError> {
+    let db_lock = db.lock().await;
+    //Unknown blocks have no proof either, unlike `/block` they are answered with 404.
+    let nexus_hash = match resolve_block_hash(&db_lock, block_hash_opt, block_number_opt) {
+        Ok(i) => i,
+        Err(e) if e.code == NOT_FOUND_CODE => return Err(e.with_status(StatusCode::NOT_FOUND)),
+        Err(e) => return Err(e),
     };
 
-    match serde_json::to_string(&block_with_txs) {
-        Ok(serialized_response) => Ok(warp::reply::with_status(
-            serialized_response,
-            warp::http::StatusCode::OK,
-        )),
-        Err(_) => Ok(warp::reply::with_status(
-            "Internal encoding error".to_string(),
-            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-        )),
+    let proof = match db_lock.get::<NexusBlockProof>(&[nexus_hash.as_slice(), b"-proof"].concat()) {
+        Ok(Some(i)) => i,
+        Ok(None) => return Err(ApiError::not_found("Block proof not found")),
+        Err(_) => return Err(ApiError::internal("Error retrieving block proof")),
+    };
+    //Proofs are verified before being stored, the schedule may have changed since.
+    match schedule.check_block_proof(&proof.proof) {
+        Ok(header) if header.hash() == nexus_hash => (),
+        _ => {
+            return Err(ApiError::internal(
+                "Stored block proof does not match the runtime schedule",
+            ))
+        }
     }
+
+    NexusBlockProofHex::try_from(&proof)
+        .map_err(|_| ApiError::internal("Stored block proof could not be decoded"))
 }
 
-/// Get account state and proof against state root at any height.
-#[utoipa::path(
-    get,
-    path = "/account",
-    tag = "nexus",
-    params(
-        ("app_account_id" = String, Query, description = "Account ID in hex format"),
-        ("block_hash" = Option<String>, Query, description = "Optional block hash in hex format. If not provided, uses latest state")
-    ),
-    responses(
-        (status = 200, description = "Account state found", body = AccountWithProof),
-        (status = 404, description = "Account not found", body = String),
-        (status = 400, description = "Invalid hash format", body = String),
-        (status = 500, description = "Internal error", body = String)
-    )
-)]
-async fn get_state(
-    db: Arc<Mutex<NodeDB>>,
-    state: Arc<Mutex<VmState>>,
-    app_account_id: &H256,
-    block_hash: Option<H256>,
-) -> Result<WithStatus<String>, Rejection> {
-    let state_lock = state.lock().await;
+pub async fn fetch_block_profile(
+    db: &Arc<Mutex<NodeDB>>,
+    block_hash_opt: Option<H256>,
+    block_number_opt: Option<u32>,
+) -> Result<BatchProfile, ApiError> {
     let db_lock = db.lock().await;
+    let nexus_hash = resolve_block_hash(&db_lock, block_hash_opt, block_number_opt)?;
 
-    let header_store: HeaderStore = match db_lock.get(b"previous_headers") {
-        Ok(Some(i)) => i,
-        Ok(None) => HeaderStore::new(32),
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Header store error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
+    match db_lock.get::<BatchProfile>(&[nexus_hash.as_slice(), b"-profile"].concat()) {
+        Ok(Some(i)) => Ok(i),
+        Ok(None) => {
+            Err(ApiError::not_found("Block profile not found").with_status(StatusCode::BAD_REQUEST))
         }
-    };
+        Err(_) => Err(ApiError::internal("Error retrieving block profile")),
+    }
+}
 
-    let version: u64 = match block_hash {
+/// Resolves the state version at the given block, or the latest committed version.
+fn version_at(
+    db_lock: &NodeDB,
+    state_lock: &VmState,
+    block_hash: Option<H256>,
+) -> Result<u64, ApiError> {
+    match block_hash {
         Some(i) => {
             match db_lock.get::<NexusBlockWithPointers>(&[i.as_slice(), b"-block"].concat()) {
-                Ok(Some(i)) => i.jmt_version,
-                Ok(None) => {
-                    return Ok(warp::reply::with_status(
-                        "Block hash not found".to_string(),
-                        warp::http::StatusCode::BAD_REQUEST,
-                    ))
-                }
-                Err(_) => {
-                    return Ok(warp::reply::with_status(
-                        "Internal db error".to_string(),
-                        warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-                    ))
-                }
+                Ok(Some(i)) => Ok(i.jmt_version),
+                Ok(None) => Err(ApiError::not_found("Block hash not found")
+                    .with_status(StatusCode::BAD_REQUEST)),
+                Err(_) => Err(ApiError::internal("Internal db error")),
             }
         }
         None => match state_lock.get_version(true) {
-            Ok(Some(i)) => i,
-            Ok(None) => 0,
-            Err(_) => {
-                return Ok(warp::reply::with_status(
-                    "Internal db error".to_string(),
-                    warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-                ))
-            }
+            Ok(Some(i)) => Ok(i),
+            Ok(None) => Ok(0),
+            Err(_) => Err(ApiError::internal("Internal db error")),
         },
+    }
+}
+
+pub async fn fetch_account(
+    db: &Arc<Mutex<NodeDB>>,
+    state: &Arc<Mutex<VmState>>,
+    app_account_id: &H256,
+    block_hash: Option<H256>,
+) -> Result<AccountWithProof, ApiError> {
+    let state_lock = state.lock().await;
+    let db_lock = db.lock().await;
+
+    let header_store: HeaderStore = match db_lock.get(b"previous_headers") {
+        Ok(Some(i)) => i,
+        Ok(None) => HeaderStore::new(32),
+        Err(_) => return Err(ApiError::internal("Header store error")),
     };
 
+    let version = version_at(&db_lock, &state_lock, block_hash)?;
+
     let (account_option, proof) = match state_lock.get_with_proof(app_account_id, version) {
         Ok(i) => i,
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Internal error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
+        Err(_) => return Err(ApiError::internal("Internal error")),
     };
     let root = match state_lock.get_root(version) {
         Ok(i) => i,
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Internal error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
+        Err(_) => return Err(ApiError::internal("Internal error")),
     };
 
     let account = account_option.unwrap_or_else(AccountState::zero);
@@ -400,39 +401,261 @@ async fn get_state(
         .collect();
     let value_hash = ValueHash::with::<Sha256>(account.encode()).0;
 
-    let response = AccountWithProof {
+    Ok(AccountWithProof {
         account: account.clone(),
         proof: siblings.clone(),
         value_hash: value_hash.clone(),
         account_encoded: hex::encode(account.encode()),
         nexus_header: match header_store.first() {
             Some(i) => i.clone(),
-            None => {
-                return Ok(warp::reply::with_status(
-                    "No headers available.".to_string(),
-                    warp::http::StatusCode::NOT_FOUND,
-                ))
-            }
+            None => return Err(ApiError::not_found("No headers available.")),
         },
         proof_hex: siblings.iter().map(|s| hex::encode(s)).collect(),
         value_hash_hex: hex::encode(value_hash),
         nexus_state_root_hex: hex::encode(root.as_fixed_slice()),
+    })
+}
+
+pub async fn fetch_header(
+    db: &Arc<Mutex<NodeDB>>,
+    avail_hash: &H256,
+) -> Result<NexusHeader, ApiError> {
+    let db_lock = db.lock().await;
+
+    let nexus_hash: H256 = match db_lock.get::<AvailToNexusPointer>(avail_hash.as_slice()) {
+        Ok(Some(i)) => i.nexus_hash,
+        Ok(None) => return Err(ApiError::not_found("Avail header not yet processed.")),
+        Err(_) => return Err(ApiError::internal("Node DB error. Cannot find mapping")),
     };
 
-    let serialized_response = match serde_json::to_string(&response) {
+    match db_lock.get(nexus_hash.as_slice()) {
+        Ok(Some(i)) => Ok(i),
+        Ok(None) => Err(ApiError::internal("Internal error")),
+        Err(_) => Err(ApiError::internal(
+            "Node DB error. Cannot find nexus header",
+        )),
+    }
+}
+
+pub async fn fetch_range(db: &Arc<Mutex<NodeDB>>) -> Result<Vec<H256>, ApiError> {
+    let db_lock = db.lock().await;
+
+    let header_store: HeaderStore = match db_lock.get(b"previous_headers") {
+        Ok(Some(i)) => i,
+        Ok(None) => HeaderStore::new(32),
+        Err(_) => return Err(ApiError::internal("Header store error")),
+    };
+
+    Ok(header_store.inner().iter().map(|h| h.hash()).collect())
+}
+
+pub async fn fetch_blocks(
+    db: &Arc<Mutex<NodeDB>>,
+    page: &PageRequest,
+) -> Result<Page<NexusBlock>, ApiError> {
+    let db_lock = db.lock().await;
+
+    list_blocks(&db_lock, page).map_err(|_| ApiError::internal("Error retrieving blocks"))
+}
+
+pub async fn fetch_app_txs(
+    db: &Arc<Mutex<NodeDB>>,
+    app_account_id: &H256,
+    page: &PageRequest,
+) -> Result<Page<TransactionWithStatus>, ApiError> {
+    let db_lock = db.lock().await;
+
+    list_app_txs(&db_lock, &AppAccountId::from(app_account_id.clone()), page)
+        .map_err(|_| ApiError::internal("Error retrieving app transactions"))
+}
+
+pub async fn fetch_account_owner(
+    db: &Arc<Mutex<NodeDB>>,
+    app_account_id: &H256,
+) -> Result<AccountOwner, ApiError> {
+    let db_lock = db.lock().await;
+
+    match get_account_owner(&db_lock, &AppAccountId::from(app_account_id.clone())) {
+        Ok(owner) => Ok(AccountOwner {
+            app_account_id: app_account_id.clone(),
+            owner: owner.map(H256::from),
+        }),
+        Err(_) => Err(ApiError::internal("Internal error")),
+    }
+}
+
+pub async fn fetch_accounts(
+    db: &Arc<Mutex<NodeDB>>,
+    state: &Arc<Mutex<VmState>>,
+    block_hash: Option<H256>,
+    page: &PageRequest,
+) -> Result<Page<AccountListItem>, ApiError> {
+    let state_lock = state.lock().await;
+    let db_lock = db.lock().await;
+
+    let version = version_at(&db_lock, &state_lock, block_hash)?;
+
+    list_accounts(&state_lock, version, page)
+        .map_err(|_| ApiError::internal("Error retrieving accounts"))
+}
+
+fn page_request(params: &HashMap<String, String>) -> Result<PageRequest, ApiError> {
+    let offset = match params.get("offset").map(|i| i.parse::<u64>()).transpose() {
         Ok(i) => i,
-        Err(e) => {
-            return Ok(warp::reply::with_status(
-                "Internal encoding error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ));
+        Err(_) => return Err(ApiError::invalid_params("Invalid offset")),
+    };
+    let limit = match params.get("limit").map(|i| i.parse::<u64>()).transpose() {
+        Ok(i) => i,
+        Err(_) => return Err(ApiError::invalid_params("Invalid limit")),
+    };
+
+    Ok(PageRequest::new(offset, limit))
+}
+
+/// Submit a new transaction to the mempool
+#[utoipa::path(
+    post,
+    path = "/tx",
+    tag = "nexus",
+    request_body = Transaction,
+    responses(
+        (status = 200, description = "Transaction added successfully", body = String),
+        (status = 400, description = "Transaction rejected", body = String),
+        (status = 500, description = "Internal mempool error", body = String)
+    )
+)]
+async fn submit_tx(
+    mempool: Mempool,
+    state: Arc<Mutex<VmState>>,
+    tx: Transaction,
+) -> Result<WithStatus<String>, Rejection> {
+    match add_tx(&mempool, &state, tx).await {
+        Ok(_) => Ok(warp::reply::with_status(
+            "Added tx".to_string(),
+            warp::http::StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(e.message, e.status)),
+    }
+}
+
+/// Get transaction and its status by hash
+#[utoipa::path(
+    get,
+    path = "/tx_status",
+    tag = "nexus",
+    params(
+        ("tx_hash" = String, Query, description = "Transaction hash in hex format")
+    ),
+    responses(
+        (status = 200, description = "Transaction status found", body = TransactionWithStatus),
+        (status = 404, description = "Transaction not found", body = String),
+        (status = 400, description = "Invalid hash format", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn tx_status(db: Arc<Mutex<NodeDB>>, tx_hash: H256) -> Result<WithStatus<String>, Rejection> {
+    reply(fetch_tx_status(&db, &tx_hash).await)
+}
+
+/// Get block by hash, number or latest
+#[utoipa::path(
+    get,
+    path = "/block",
+    tag = "nexus",
+    params(
+        ("block_hash" = Option<String>, Query, description = "Block hash in hex format. If not provided, returns the latest block"),
+        ("block_number" = Option<u32>, Query, description = "Block number to query. If not provided, returns the latest block")
+    ),
+    responses(
+        (status = 200, description = "Block found", body = NexusBlockWithTransactions),
+        (status = 404, description = "Block not found", body = String),
+        (status = 400, description = "Invalid hash format", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_block(
+    db: Arc<Mutex<NodeDB>>,
+    block_hash_opt: Option<H256>,
+    block_number_opt: Option<u32>,
+) -> Result<WithStatus<String>, Rejection> {
+    reply(fetch_block(&db, block_hash_opt, block_number_opt).await)
+}
+
+/// Get the proof of a block, with the calldata to verify it on EVM once wrapped in Groth16.
+#[utoipa::path(
+    get,
+    path = "/block_proof",
+    tag = "nexus",
+    params(
+        ("block_hash" = Option<String>, Query, description = "Block hash in hex format"),
+        ("block_number" = Option<u32>, Query, description = "Block number. If neither is provided, uses the latest block")
+    ),
+    responses(
+        (status = 200, description = "Block proof found", body = NexusBlockProofHex),
+        (status = 400, description = "Invalid parameters", body = String),
+        (status = 404, description = "Block proof not found", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_block_proof(
+    db: Arc<Mutex<NodeDB>>,
+    schedule: RuntimeSchedule,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    match block_params(&params) {
+        Ok((block_hash, block_number)) => {
+            reply(fetch_block_proof(&db, &schedule, block_hash, block_number).await)
         }
-    };
+        Err(e) => Ok(e),
+    }
+}
+
+/// Get the cycles and time spent proving a block.
+#[utoipa::path(
+    get,
+    path = "/block_profile",
+    tag = "nexus",
+    params(
+        ("block_hash" = Option<String>, Query, description = "Block hash in hex format"),
+        ("block_number" = Option<u32>, Query, description = "Block number. If neither is provided, uses the latest block")
+    ),
+    responses(
+        (status = 200, description = "Block profile found", body = BatchProfile),
+        (status = 400, description = "Block profile not found or invalid parameters", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_block_profile(
+    db: Arc<Mutex<NodeDB>>,
+    block_hash_opt: Option<H256>,
+    block_number_opt: Option<u32>,
+) -> Result<WithStatus<String>, Rejection> {
+    reply(fetch_block_profile(&db, block_hash_opt, block_number_opt).await)
+}
 
-    Ok(warp::reply::with_status::<String>(
-        serialized_response,
-        warp::http::StatusCode::OK,
-    ))
+/// Get account state and proof against state root at any height.
+#[utoipa::path(
+    get,
+    path = "/account",
+    tag = "nexus",
+    params(
+        ("app_account_id" = String, Query, description = "Account ID in hex format"),
+        ("block_hash" = Option<String>, Query, description = "Optional block hash in hex format. If not provided, uses latest state")
+    ),
+    responses(
+        (status = 200, description = "Account state found", body = AccountWithProof),
+        (status = 404, description = "Account not found", body = String),
+        (status = 400, description = "Invalid hash format", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_state(
+    db: Arc<Mutex<NodeDB>>,
+    state: Arc<Mutex<VmState>>,
+    app_account_id: &H256,
+    block_hash: Option<H256>,
+) -> Result<WithStatus<String>, Rejection> {
+    reply(fetch_account(&db, &state, app_account_id, block_hash).await)
 }
 
 /// Get account state in hex format
@@ -455,92 +678,11 @@ async fn get_state_hex(
     state: Arc<Mutex<VmState>>,
     app_account_id: &H256,
 ) -> Result<WithStatus<String>, Rejection> {
-    let state_lock = state.lock().await;
-    let db_lock = db.lock().await;
-
-    let header_store: HeaderStore = match db_lock.get(b"previous_headers") {
-        Ok(Some(i)) => i,
-        Ok(None) => HeaderStore::new(32),
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Header store error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
-    };
-    let current_version = match state_lock.get_version(true) {
-        Ok(Some(i)) => i,
-        Ok(None) => 0,
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Internal db error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
-    };
-
-    let (account_option, proof) = match state_lock.get_with_proof(app_account_id, current_version) {
-        Ok(i) => i,
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Internal error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
-    };
-    let root = match state_lock.get_root(current_version) {
-        Ok(i) => i,
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Internal error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
-    };
-
-    let account = account_option.unwrap_or_else(AccountState::zero);
-    let siblings: Vec<[u8; 32]> = proof
-        .siblings()
-        .iter()
-        .map(|s| s.hash::<Sha256>())
-        .collect();
-    let value_hash = ValueHash::with::<Sha256>(account.encode()).0;
-
-    let account_with_proof = AccountWithProof {
-        account: account.clone(),
-        proof: siblings.clone(),
-        value_hash: value_hash.clone(),
-        account_encoded: hex::encode(account.encode()),
-        nexus_header: match header_store.first() {
-            Some(i) => i.clone(),
-            None => {
-                return Ok(warp::reply::with_status(
-                    "No headers available.".to_string(),
-                    warp::http::StatusCode::NOT_FOUND,
-                ))
-            }
-        },
-        proof_hex: siblings.iter().map(|s| hex::encode(s)).collect(),
-        value_hash_hex: hex::encode(value_hash),
-        nexus_state_root_hex: hex::encode(root.as_fixed_slice()),
-    };
-
-    let response = AccountWithProofHex::from(account_with_proof);
-
-    let serialized_response = match serde_json::to_string(&response) {
-        Ok(i) => i,
-        Err(e) => {
-            return Ok(warp::reply::with_status(
-                "Internal encoding error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ));
-        }
-    };
-
-    Ok(warp::reply::with_status::<String>(
-        serialized_response,
-        warp::http::StatusCode::OK,
-    ))
+    reply(
+        fetch_account(&db, &state, app_account_id, None)
+            .await
+            .map(AccountWithProofHex::from),
+    )
 }
 
 /// Get header by Avail block hash
@@ -562,102 +704,213 @@ async fn get_header(
     db: Arc<Mutex<NodeDB>>,
     avail_hash: H256,
 ) -> Result<WithStatus<String>, Rejection> {
-    let db_lock = db.lock().await;
+    reply(fetch_header(&db, &avail_hash).await)
+}
 
-    let nexus_hash: H256 = match db_lock.get::<AvailToNexusPointer>(avail_hash.as_slice()) {
-        Ok(Some(i)) => i.nexus_hash,
-        Ok(None) => {
-            return Ok(warp::reply::with_status(
-                "Avail header not yet processed.".to_string(),
-                warp::http::StatusCode::NOT_FOUND,
-            ))
-        }
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Node DB error. Cannot find mapping".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
+/// Get the block range against which proofs can be submitted for state update.
+#[utoipa::path(
+    get,
+    path = "/range",
+    tag = "nexus",
+    responses(
+        (status = 200, description = "Block range retrieved successfully", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn range(db: Arc<Mutex<NodeDB>>) -> Result<WithStatus<String>, Rejection> {
+    reply(fetch_range(&db).await)
+}
+
+/// List blocks, latest first
+#[utoipa::path(
+    get,
+    path = "/blocks",
+    tag = "nexus",
+    params(
+        ("offset" = Option<u64>, Query, description = "Number of blocks to skip from the latest block"),
+        ("limit" = Option<u64>, Query, description = "Page size, defaults to 20 and is capped at 100")
+    ),
+    responses(
+        (status = 200, description = "Page of blocks", body = String),
+        (status = 400, description = "Invalid pagination params", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_blocks(
+    db: Arc<Mutex<NodeDB>>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    let page = match page_request(&params) {
+        Ok(i) => i,
+        Err(e) => return Ok(warp::reply::with_status(e.message, e.status)),
     };
 
-    let nexus_header: NexusHeader = match db_lock.get(nexus_hash.as_slice()) {
-        Ok(Some(i)) => i,
-        Ok(None) => {
-            return Ok(warp::reply::with_status(
-                "Internal error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Node DB error. Cannot find nexus header".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ))
-        }
+    reply(fetch_blocks(&db, &page).await)
+}
+
+/// List transactions submitted by an app, latest first
+#[utoipa::path(
+    get,
+    path = "/app_txs",
+    tag = "nexus",
+    params(
+        ("app_account_id" = String, Query, description = "Account ID in hex format"),
+        ("offset" = Option<u64>, Query, description = "Number of transactions to skip from the latest one"),
+        ("limit" = Option<u64>, Query, description = "Page size, defaults to 20 and is capped at 100")
+    ),
+    responses(
+        (status = 200, description = "Page of transactions", body = String),
+        (status = 400, description = "Invalid hash format or pagination params", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_app_txs(
+    db: Arc<Mutex<NodeDB>>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    let page = match page_request(&params) {
+        Ok(i) => i,
+        Err(e) => return Ok(warp::reply::with_status(e.message, e.status)),
     };
 
-    let serialized_response = match serde_json::to_string(&nexus_header) {
+    match params.get("app_account_id") {
+        Some(hash_str) => match H256::try_from(hash_str.as_str()) {
+            Ok(i) => reply(fetch_app_txs(&db, &i, &page).await),
+            Err(_) => Ok(warp::reply::with_status(
+                "Invalid hash".to_string(),
+                warp::http::StatusCode::BAD_REQUEST,
+            )),
+        },
+        None => Ok(warp::reply::with_status(
+            "Hash parameter not provided".to_string(),
+            warp::http::StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+/// List registered accounts at any height, in order of registration
+#[utoipa::path(
+    get,
+    path = "/accounts",
+    tag = "nexus",
+    params(
+        ("block_hash" = Option<String>, Query, description = "Optional block hash in hex format. If not provided, uses latest state"),
+        ("offset" = Option<u64>, Query, description = "Number of accounts to skip"),
+        ("limit" = Option<u64>, Query, description = "Page size, defaults to 20 and is capped at 100")
+    ),
+    responses(
+        (status = 200, description = "Page of accounts", body = String),
+        (status = 400, description = "Invalid hash format or pagination params", body = String),
+        (status = 500, description = "Internal error", body = String)
+    )
+)]
+async fn get_accounts(
+    db: Arc<Mutex<NodeDB>>,
+    state: Arc<Mutex<VmState>>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    let page = match page_request(&params) {
         Ok(i) => i,
-        Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Internal encoding error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ));
-        }
+        Err(e) => return Ok(warp::reply::with_status(e.message, e.status)),
+    };
+
+    let block_hash = match params.get("block_hash") {
+        Some(i) => match H256::try_from(i.as_str()) {
+            Ok(i) => Some(i),
+            Err(_) => {
+                return Ok(warp::reply::with_status(
+                    "Invalid hash".to_string(),
+                    warp::http::StatusCode::BAD_REQUEST,
+                ))
+            }
+        },
+        None => None,
     };
 
-    Ok(warp::reply::with_status::<String>(
-        serialized_response,
-        warp::http::StatusCode::OK,
-    ))
+    reply(fetch_accounts(&db, &state, block_hash, &page).await)
 }
 
-/// Get the block range against which proofs can be submitted for state update.
+/// Get the key registered as owner of an account
 #[utoipa::path(
     get,
-    path = "/range",
+    path = "/account_owner",
     tag = "nexus",
+    params(
+        ("app_account_id" = String, Query, description = "Account ID in hex format")
+    ),
     responses(
-        (status = 200, description = "Block range retrieved successfully", body = String),
+        (status = 200, description = "Registered owner, null if registration was not signed", body = AccountOwner),
+        (status = 400, description = "Invalid hash format", body = String),
         (status = 500, description = "Internal error", body = String)
     )
 )]
-async fn range(db: Arc<Mutex<NodeDB>>) -> Result<WithStatus<String>, Rejection> {
-    let db_lock = db.lock().await;
+async fn get_owner(
+    db: Arc<Mutex<NodeDB>>,
+    params: HashMap<String, String>,
+) -> Result<WithStatus<String>, Rejection> {
+    match params.get("app_account_id") {
+        Some(hash_str) => match H256::try_from(hash_str.as_str()) {
+            Ok(i) => reply(fetch_account_owner(&db, &i).await),
+            Err(_) => Ok(warp::reply::with_status(
+                "Invalid hash".to_string(),
+                warp::http::StatusCode::BAD_REQUEST,
+            )),
+        },
+        None => Ok(warp::reply::with_status(
+            "Hash parameter not provided".to_string(),
+            warp::http::StatusCode::BAD_REQUEST,
+        )),
+    }
+}
 
-    let header_store: HeaderStore = match db_lock.get(b"previous_headers") {
-        Ok(Some(i)) => i,
-        Ok(None) => HeaderStore::new(32),
+/// Parses the optional `block_hash` and `block_number` query parameters.
+fn block_params(
+    params: &HashMap<String, String>,
+) -> Result<(Option<H256>, Option<u32>), WithStatus<String>> {
+    let block_hash = match params
+        .get("block_hash")
+        .map(|hash_str| H256::try_from(hash_str.as_str()))
+        .transpose()
+    {
+        Ok(i) => i,
         Err(_) => {
-            return Ok(warp::reply::with_status(
-                "Header store error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+            return Err(warp::reply::with_status(
+                "Invalid hash".to_string(),
+                warp::http::StatusCode::BAD_REQUEST,
             ))
         }
     };
 
-    let range: Vec<H256> = header_store.inner().iter().map(|h| h.hash()).collect();
-    let serialized_range = match serde_json::to_string(&range) {
+    let block_number = match params
+        .get("block_number")
+        .map(|number_str| number_str.parse::<u32>())
+        .transpose()
+    {
         Ok(i) => i,
-        Err(e) => {
-            return Ok(warp::reply::with_status(
-                "Internal encoding error".to_string(),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ));
+        Err(_) => {
+            return Err(warp::reply::with_status(
+                "Invalid block number".to_string(),
+                warp::http::StatusCode::BAD_REQUEST,
+            ))
         }
     };
 
-    Ok(warp::reply::with_status::<String>(
-        serialized_range,
-        warp::http::StatusCode::OK,
-    ))
+    Ok((block_hash, block_number))
 }
 
 pub fn routes(
     mempool: Mempool,
     db: Arc<Mutex<NodeDB>>,
     vm_state: Arc<Mutex<VmState>>,
+    events: EventSender,
+    schedule: RuntimeSchedule,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    let json_rpc_context = JsonRpcContext {
+        mempool: mempool.clone(),
+        db: db.clone(),
+        vm_state: vm_state.clone(),
+        schedule: schedule.clone(),
+    };
     let mempool_clone = mempool.clone();
     let db_clone = db.clone();
     let vm_state_clone = vm_state.clone();
@@ -665,6 +918,14 @@ pub fn routes(
     let db_clone_3 = db.clone();
     let db_clone_4 = db.clone();
     let db_clone_5 = db.clone();
+    let db_clone_6 = db.clone();
+    let db_clone_7 = db.clone();
+    let db_clone_8 = db.clone();
+    let vm_state_clone_2 = vm_state.clone();
+    let vm_state_clone_3 = vm_state.clone();
+    let db_clone_9 = db.clone();
+    let db_clone_10 = db.clone();
+    let db_clone_11 = db.clone();
 
     let health_check = warp::path("health")
         .and(warp::get())
@@ -673,6 +934,7 @@ pub fn routes(
     let tx = warp::path("tx")
         .and(warp::post())
         .and(warp::any().map(move || mempool_clone.clone()))
+        .and(warp::any().map(move || vm_state_clone_3.clone()))
         .and(warp::body::json())
         .and_then(submit_tx);
     let tx_status = warp::path("tx_status")
@@ -706,35 +968,32 @@ pub fn routes(
         .and(warp::query::<HashMap<String, String>>())
         .and_then(
             |db: Arc<Mutex<NodeDB>>, params: HashMap<String, String>| async move {
-                let block_hash = match params
-                    .get("block_hash")
-                    .map(|hash_str| H256::try_from(hash_str.as_str()))
-                    .transpose()
-                    .map_err(|_| {
-                        warp::reply::with_status(
-                            "Invalid hash".to_string(),
-                            warp::http::StatusCode::BAD_REQUEST,
-                        )
-                    }) {
-                    Ok(i) => i,
-                    Err(e) => return Ok(e),
-                };
-
-                let block_number = match params
-                    .get("block_number")
-                    .map(|hash_str| hash_str.parse::<u32>())
-                    .transpose()
-                    .map_err(|_| {
-                        warp::reply::with_status(
-                            "Invalid block number".to_string(),
-                            warp::http::StatusCode::BAD_REQUEST,
-                        )
-                    }) {
-                    Ok(i) => i,
-                    Err(e) => return Ok(e),
-                };
-
-                get_block(db, block_hash, block_number).await
+                match block_params(&params) {
+                    Ok((block_hash, block_number)) => get_block(db, block_hash, block_number).await,
+                    Err(e) => Ok(e),
+                }
+            },
+        );
+
+    let block_proof = warp::path("block_proof")
+        .and(warp::get())
+        .and(warp::any().map(move || db_clone_10.clone()))
+        .and(warp::any().map(move || schedule.clone()))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(get_block_proof);
+
+    let block_profile = warp::path("block_profile")
+        .and(warp::get())
+        .and(warp::any().map(move || db_clone_11.clone()))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(
+            |db: Arc<Mutex<NodeDB>>, params: HashMap<String, String>| async move {
+                match block_params(&params) {
+                    Ok((block_hash, block_number)) => {
+                        get_block_profile(db, block_hash, block_number).await
+                    }
+                    Err(e) => Ok(e),
+                }
             },
         );
 
@@ -836,6 +1095,44 @@ pub fn routes(
             },
         );
 
+    let blocks = warp::path("blocks")
+        .and(warp::get())
+        .and(warp::any().map(move || db_clone_6.clone()))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(get_blocks);
+
+    let app_txs = warp::path("app_txs")
+        .and(warp::get())
+        .and(warp::any().map(move || db_clone_7.clone()))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(get_app_txs);
+
+    let accounts = warp::path("accounts")
+        .and(warp::get())
+        .and(warp::any().map(move || db_clone_8.clone()))
+        .and(warp::any().map(move || vm_state_clone_2.clone()))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(get_accounts);
+
+    let account_owner = warp::path("account_owner")
+        .and(warp::get())
+        .and(warp::any().map(move || db_clone_9.clone()))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(get_owner);
+
+    let json_rpc = warp::path("rpc")
+        .and(warp::post())
+        .and(warp::any().map(move || json_rpc_context.clone()))
+        .and(warp::body::bytes())
+        .and_then(handle_json_rpc);
+
+    let ws = warp::path("ws")
+        .and(warp::ws())
+        .and(warp::any().map(move || events.clone()))
+        .map(|ws: warp::ws::Ws, events: EventSender| {
+            ws.on_upgrade(move |socket| handle_ws(socket, events))
+        });
+
     let config = Arc::new(Config::from("/api-doc.json"));
     let api_doc = warp::path("api-doc.json")
         .and(warp::get())
@@ -851,10 +1148,18 @@ pub fn routes(
     tx.or(health_check)
         .or(tx_status)
         .or(block)
+        .or(block_proof)
+        .or(block_profile)
         .or(submit_batch)
         .or(header)
         .or(account)
         .or(account_hex)
+        .or(blocks)
+        .or(app_txs)
+        .or(accounts)
+        .or(account_owner)
+        .or(json_rpc)
+        .or(ws)
         .or(api_doc)
         .or(swagger_ui)
 }
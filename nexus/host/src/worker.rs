@@ -0,0 +1,294 @@
+use crate::{new_prover, ProvingJob};
+use anyhow::{anyhow, Context, Error};
+use nexus_core::{
+    types::Proof as NexusProof,
+    zkvm::{
+        any::{AnyProof as Proof, AnyProver as Prover},
+        ProverMode, ProvingStats, ZKVMBackend,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::sync::{watch, Mutex};
+use tracing::{debug, error, info, warn};
+use warp::{http::StatusCode, hyper::body::Bytes, Filter, Rejection, Reply};
+
+//Jobs carry the pre state and recursion proofs, well above warp's defaults.
+pub const DEFAULT_MAX_JOB_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Settings of a prover worker.
+#[derive(Clone, Debug)]
+pub struct WorkerConfig {
+    /// Interface the worker listens on, only reachable from the same machine by default.
+    pub host: String,
+    pub port: u32,
+    /// Secret nodes have to send as a bearer token, jobs are accepted from anyone when not set.
+    pub token: Option<String>,
+    /// Largest job accepted, in bytes.
+    pub max_job_size: u64,
+}
+
+impl WorkerConfig {
+    pub fn new(port: u32) -> Self {
+        Self {
+            host: String::from("127.0.0.1"),
+            port,
+            token: None,
+            max_job_size: DEFAULT_MAX_JOB_SIZE,
+        }
+    }
+}
+
+/// Body of `POST /prove`, bincode encoded.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkerRequest {
+    pub backend: ZKVMBackend,
+    pub prover_mode: ProverMode,
+    /// Runtime to prove the job with, the one built into the worker when not set.
+    pub elf: Option<Vec<u8>>,
+    pub job: ProvingJob,
+}
+
+/// Response of `POST /prove`, bincode encoded. Errors are returned as plain text.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerResponse {
+    pub proof: NexusProof,
+    pub stats: Option<ProvingStats>,
+}
+
+/// Client of the prover workers. Jobs are spread round-robin, falling back
+/// to the next worker when one fails.
+#[derive(Clone)]
+pub struct ProverWorkers {
+    client: reqwest::Client,
+    urls: Vec<String>,
+    token: Option<String>,
+    next: Arc<AtomicUsize>,
+}
+
+impl ProverWorkers {
+    pub fn new(urls: Vec<String>) -> Result<Self, Error> {
+        if urls.is_empty() {
+            return Err(anyhow!("At least one prover worker is required"));
+        }
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            urls: urls
+                .into_iter()
+                .map(|url| url.trim_end_matches('/').to_string())
+                .collect(),
+            token: None,
+            next: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Sends the secret the workers were started with along with each job.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+
+        self
+    }
+
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    pub async fn prove(&self, request: &WorkerRequest) -> Result<WorkerResponse, Error> {
+        let body = bincode::serialize(request)?;
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut last_error = anyhow!("No prover worker available");
+
+        for offset in 0..self.urls.len() {
+            let url = &self.urls[(start + offset) % self.urls.len()];
+
+            match self.prove_on(url, body.clone()).await {
+                Ok(i) => return Ok(i),
+                Err(e) => {
+                    warn!(worker = %url, error = ?e, "⚠️ Prover worker failed");
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    async fn prove_on(&self, url: &str, body: Vec<u8>) -> Result<WorkerResponse, Error> {
+        let mut request = self
+            .client
+            .post(format!("{}/prove", url))
+            .header("content-type", "application/octet-stream");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.body(body).send().await?;
+        let status = response.status();
+        let bytes = response.bytes().await?;
+
+        if !status.is_success() {
+            return Err(anyhow!("{}: {}", status, String::from_utf8_lossy(&bytes)));
+        }
+
+        Ok(bincode::deserialize(&bytes)?)
+    }
+}
+
+pub fn worker_routes(
+    config: &WorkerConfig,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    //Proving saturates the box, jobs are queued and proven one at a time.
+    let lock = Arc::new(Mutex::new(()));
+    let token = config.token.clone();
+
+    let health = warp::path("health")
+        .and(warp::get())
+        .map(|| warp::reply::json(&serde_json::json!({"status": "Alive ser."})));
+
+    let prove = warp::path("prove")
+        .and(warp::post())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::body::content_length_limit(config.max_job_size))
+        .and(warp::body::bytes())
+        .and(warp::any().map(move || token.clone()))
+        .and(warp::any().map(move || lock.clone()))
+        .and_then(prove_handler);
+
+    health.or(prove)
+}
+
+/// Compares in constant time, so the secret cannot be guessed from response times.
+fn authorized(token: &Option<String>, authorization: &Option<String>) -> bool {
+    let token = match token {
+        Some(i) => i,
+        None => return true,
+    };
+    let given = match authorization
+        .as_ref()
+        .and_then(|i| i.strip_prefix("Bearer "))
+    {
+        Some(i) => i.as_bytes(),
+        None => return false,
+    };
+
+    given.len() == token.len()
+        && given
+            .iter()
+            .zip(token.as_bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn prove_handler(
+    authorization: Option<String>,
+    body: Bytes,
+    token: Option<String>,
+    lock: Arc<Mutex<()>>,
+) -> Result<impl Reply, Rejection> {
+    if !authorized(&token, &authorization) {
+        warn!("⚠️ Proving job rejected, invalid token");
+        return Ok(warp::reply::with_status(
+            String::from("Invalid worker token").into_bytes(),
+            StatusCode::UNAUTHORIZED,
+        ));
+    }
+
+    let request: WorkerRequest = match bincode::deserialize(&body) {
+        Ok(i) => i,
+        Err(e) => {
+            return Ok(warp::reply::with_status(
+                format!("Invalid proving job: {}", e).into_bytes(),
+                StatusCode::BAD_REQUEST,
+            ))
+        }
+    };
+
+    let guard = lock.lock_owned().await;
+    info!(
+        backend = ?request.backend,
+        mode = ?request.prover_mode,
+        tx_count = request.job.txs.len(),
+        "🔨 Proving job received"
+    );
+
+    let task = tokio::task::spawn_blocking(move || {
+        //Held until proving ends, even if the node stops waiting for the proof.
+        let _guard = guard;
+        prove(request)
+    });
+
+    let response = match task.await {
+        Ok(Ok(i)) => i,
+        Ok(Err(e)) => {
+            error!(error = ?e, "❌ Proving job failed");
+            return Ok(warp::reply::with_status(
+                e.to_string().into_bytes(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+        Err(e) => {
+            error!(error = ?e, "❌ Proving task panicked");
+            return Ok(warp::reply::with_status(
+                String::from("Proving task panicked").into_bytes(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+    };
+
+    match bincode::serialize(&response) {
+        Ok(i) => {
+            info!("✅ Proving job completed");
+            Ok(warp::reply::with_status(i, StatusCode::OK))
+        }
+        Err(e) => Ok(warp::reply::with_status(
+            e.to_string().into_bytes(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
+}
+
+fn prove(request: WorkerRequest) -> Result<WorkerResponse, Error> {
+    let prover = match request.elf {
+        Some(elf) => Prover::with_backend(request.backend, elf, request.prover_mode)?,
+        None => new_prover(request.backend, request.prover_mode)?,
+    };
+    let (proof, stats) = request.job.prove::<Prover, Proof>(prover, &|progress| {
+        debug!(?progress, "Proving progress");
+    })?;
+
+    Ok(WorkerResponse {
+        proof: proof.try_into()?,
+        stats,
+    })
+}
+
+pub fn run_worker(
+    config: WorkerConfig,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let address = SocketAddr::from_str(format!("{}:{}", config.host, config.port).as_str())
+            .context("Unable to parse host address from config")
+            .unwrap();
+
+        info!("👷 Prover worker running on: {:?}", &address);
+        if config.token.is_none() {
+            warn!("⚠️ No worker token set, jobs are accepted from anyone reaching the worker");
+        }
+
+        let (_, server) =
+            warp::serve(worker_routes(&config)).bind_with_graceful_shutdown(address, async move {
+                shutdown_rx.changed().await.ok();
+                info!("💤 Shutdown signal received. Stopping prover worker...");
+            });
+
+        server.await;
+
+        info!("✅ Exited prover worker");
+    })
+}
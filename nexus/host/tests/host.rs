@@ -1,12 +1,15 @@
 use anyhow::Error;
 use avail_subxt::Header;
-use host::{run_nexus, setup_components};
+use host::{run_nexus, setup_components, ProvingConfig};
 use mockall::predicate::*;
 use mockall::*;
+use nexus_core::zkvm::any::AnyProof as Proof;
+#[cfg(all(feature = "mock", not(any(feature = "risc0", feature = "sp1"))))]
+use nexus_core::zkvm::mock::MockZKVM as ZKVM;
 #[cfg(any(feature = "risc0"))]
-use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
-#[cfg(any(feature = "sp1"))]
-use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
+use nexus_core::zkvm::risczero::ZKVM;
+#[cfg(all(feature = "sp1", not(feature = "risc0")))]
+use nexus_core::zkvm::sp1::SP1ZKVM as ZKVM;
 use nexus_core::{
     state_machine::StateMachine,
     types::{
@@ -14,10 +17,10 @@ use nexus_core::{
         NexusBlockWithTransactions, StatementDigest, SubmitProof, Transaction, TransactionStatus,
         TransactionWithStatus, TxParams, TxSignature, H256,
     },
-    zkvm::ProverMode,
+    zkvm::{ProverMode, ZKVMBackend},
 };
 use nexus_core::{traits::NexusTransaction, types::NexusHeader};
-use relayer::Relayer;
+use relayer::{types::AppBlockData, Relayer};
 use reqwest::Client;
 use std::future::Future;
 use std::sync::Arc;
@@ -32,6 +35,11 @@ mock! {
     impl Relayer for Relayer {
       fn receiver(&mut self) -> Arc<tokio::sync::Mutex<UnboundedReceiver<Header>>>;
       fn get_header_hash(&self, height: u32) -> impl Future<Output = H256> + Send;
+      fn get_app_data(
+        &self,
+        header_hash: H256,
+        app_ids: Vec<AppId>,
+      ) -> impl Future<Output = Result<Vec<AppBlockData>, Error>> + Send;
       fn start(&self, start_height: u32) -> impl Future<Output = ()> + Send;
       fn stop(&self);
     }
@@ -109,7 +117,8 @@ async fn test_empty_batches() {
             Arc::new(Mutex::new(mock_relayer)),
             node_db,
             state_machine,
-            (prover_mode, 6999),
+            (prover_mode, ZKVMBackend::default(), 6999),
+            ProvingConfig::default(),
             state,
             shutdown_rx,
         )
@@ -215,7 +224,8 @@ async fn test_out_of_order_headers() {
             Arc::new(Mutex::new(mock_relayer)),
             node_db.clone(),
             state_machine,
-            (prover_mode, 7000),
+            (prover_mode, ZKVMBackend::default(), 7000),
+            ProvingConfig::default(),
             state,
             shutdown_rx,
         )
@@ -322,7 +332,8 @@ async fn test_state_root_for_empty_batches() {
             Arc::new(Mutex::new(mock_relayer)),
             node_db_clone.clone(),
             state_machine,
-            (prover_mode, 7002),
+            (prover_mode, ZKVMBackend::default(), 7002),
+            ProvingConfig::default(),
             state,
             shutdown_rx,
         )
@@ -357,6 +368,7 @@ async fn test_state_root_for_empty_batches() {
     assert_eq!(old_headers.first().unwrap().state_root, H256::zero())
 }
 
+#[cfg(any(feature = "risc0", feature = "sp1"))]
 #[tokio::test]
 async fn test_init_account_tx() {
     use serde_json;
@@ -461,7 +473,8 @@ async fn test_init_account_tx() {
         Arc::new(Mutex::new(mock_relayer)),
         node_db_clone.clone(),
         state_machine,
-        (prover_mode, 7003),
+        (prover_mode, ZKVMBackend::default(), 7003),
+        ProvingConfig::default(),
         state_clone,
         shutdown_rx,
     )
@@ -509,6 +522,8 @@ async fn test_init_account_tx() {
                 3963634887, 3768818894, 2608717727, 685163898, 341397292, 1233383743, 1619524616,
                 2323598105
             ]),
+            proving_system: ZKVMBackend::Risc0,
+            owner: [0; 32],
         })
     )
 }
@@ -516,6 +531,7 @@ async fn test_init_account_tx() {
 #[cfg(feature = "risc0")]
 #[tokio::test]
 async fn test_update_tx() {
+    use host::rpc::fetch_block_profile;
     use serde_json;
     use tokio::fs;
     let db_path = "./tests/db/test_update_tx";
@@ -636,7 +652,8 @@ async fn test_update_tx() {
         Arc::new(Mutex::new(mock_relayer)),
         node_db_clone.clone(),
         state_machine,
-        (prover_mode, 7004),
+        (prover_mode, ZKVMBackend::default(), 7004),
+        ProvingConfig::default(),
         state_clone,
         shutdown_rx,
     )
@@ -687,10 +704,27 @@ async fn test_update_tx() {
                 3963634887, 3768818894, 2608717727, 685163898, 341397292, 1233383743, 1619524616,
                 2323598105
             ]),
+            proving_system: ZKVMBackend::Risc0,
+            owner: [0; 32],
         })
-    )
+    );
+
+    // Region cycles are parsed from what the runtime wrote to stdout while proving.
+    let mut recursion_cycles = 0;
+    for number in 0..=10 {
+        let profile = fetch_block_profile(&node_db, None, Some(number))
+            .await
+            .expect("Block profile not stored");
+
+        assert!(profile.pre_state_cycles > 0);
+        assert!(profile.stf_cycles >= profile.recursion_cycles);
+        assert!(profile.total_cycles > profile.pre_state_cycles + profile.stf_cycles);
+        recursion_cycles += profile.recursion_cycles;
+    }
+    assert!(recursion_cycles > 0);
 }
 
+#[cfg(any(feature = "risc0", feature = "sp1"))]
 #[tokio::test]
 async fn test_transaction_status() {
     use serde_json;
@@ -812,7 +846,8 @@ async fn test_transaction_status() {
                 TransactionWithStatus {
                     transaction: tx,
                     status: TransactionStatus::Successful,
-                    block_hash: Some(latest_block.hash())
+                    block_hash: Some(latest_block.hash()),
+                    error: None,
                 }
             );
 
@@ -825,7 +860,8 @@ async fn test_transaction_status() {
         Arc::new(Mutex::new(mock_relayer)),
         node_db_clone.clone(),
         state_machine,
-        (prover_mode, 7005),
+        (prover_mode, ZKVMBackend::default(), 7005),
+        ProvingConfig::default(),
         state_clone,
         shutdown_rx,
     )
@@ -838,6 +874,7 @@ async fn test_transaction_status() {
     };
 }
 
+#[cfg(any(feature = "risc0", feature = "sp1"))]
 #[tokio::test]
 async fn test_get_state_api() {
     use serde_json;
@@ -984,6 +1021,8 @@ async fn test_get_state_api() {
                         3963634887, 3768818894, 2608717727, 685163898, 341397292, 1233383743,
                         1619524616, 2323598105
                     ]),
+                    proving_system: ZKVMBackend::Risc0,
+                    owner: [0; 32],
                 }
             );
 
@@ -1035,6 +1074,8 @@ async fn test_get_state_api() {
                         3963634887, 3768818894, 2608717727, 685163898, 341397292, 1233383743,
                         1619524616, 2323598105
                     ]),
+                    proving_system: ZKVMBackend::Risc0,
+                    owner: [0; 32],
                 }
             );
             shutdown_tx_clone.send(true).unwrap();
@@ -1046,7 +1087,8 @@ async fn test_get_state_api() {
         Arc::new(Mutex::new(mock_relayer)),
         node_db_clone.clone(),
         state_machine,
-        (prover_mode, 7006),
+        (prover_mode, ZKVMBackend::default(), 7006),
+        ProvingConfig::default(),
         state_clone,
         shutdown_rx,
     )
@@ -1059,6 +1101,7 @@ async fn test_get_state_api() {
     };
 }
 
+#[cfg(any(feature = "risc0", feature = "sp1"))]
 #[tokio::test]
 async fn test_get_block_api() {
     use serde_json;
@@ -1183,7 +1226,8 @@ async fn test_get_block_api() {
         Arc::new(Mutex::new(mock_relayer)),
         node_db_clone.clone(),
         state_machine,
-        (prover_mode, 7007),
+        (prover_mode, ZKVMBackend::default(), 7007),
+        ProvingConfig::default(),
         state_clone,
         shutdown_rx,
     )
@@ -1195,3 +1239,1442 @@ async fn test_get_block_api() {
         }
     };
 }
+
+#[tokio::test]
+async fn test_json_rpc() {
+    use host::builtin_schedule;
+    use host::events::event_channel;
+    use host::index::{Page, MAX_PAGE_LIMIT};
+    use host::jsonrpc::{JsonRpcResponse, METHOD_NOT_FOUND_CODE, PARSE_ERROR_CODE};
+    use host::rpc::{routes, NOT_FOUND_CODE};
+    use nexus_core::mempool::Mempool;
+    use nexus_core::types::NexusBlock;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_json_rpc";
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let (node_db, state) = setup_components(db_path);
+    let mempool = Mempool::new(node_db.clone(), ZKVMBackend::default());
+    let routes = routes(
+        mempool,
+        node_db,
+        state,
+        event_channel(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+
+    let batch = serde_json::json!([
+        {"jsonrpc": "2.0", "method": "nexus_getRange", "id": 1},
+        {"jsonrpc": "2.0", "method": "nexus_unknownMethod", "id": 2},
+        {"jsonrpc": "2.0", "method": "nexus_getTransactionStatus", "params": [hex::encode([1u8; 32])], "id": 3},
+        {"jsonrpc": "2.0", "method": "nexus_health"}
+    ]);
+    let response = warp::test::request()
+        .method("POST")
+        .path("/rpc")
+        .body(batch.to_string())
+        .reply(&routes)
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    let responses: Vec<JsonRpcResponse> =
+        serde_json::from_slice(response.body()).expect("Failed to parse batch response");
+
+    // Notification without id does not get a response.
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0].id, serde_json::json!(1));
+    assert_eq!(responses[0].result, Some(serde_json::json!([])));
+    assert_eq!(
+        responses[1].error.as_ref().map(|e| e.code),
+        Some(METHOD_NOT_FOUND_CODE)
+    );
+    assert_eq!(
+        responses[2].error.as_ref().map(|e| e.code),
+        Some(NOT_FOUND_CODE)
+    );
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/rpc")
+        .body("{not json")
+        .reply(&routes)
+        .await;
+    let response: JsonRpcResponse =
+        serde_json::from_slice(response.body()).expect("Failed to parse error response");
+
+    assert_eq!(response.error.map(|e| e.code), Some(PARSE_ERROR_CODE));
+
+    // A null id is not a notification, the response echoes it.
+    let response = warp::test::request()
+        .method("POST")
+        .path("/rpc")
+        .body(r#"{"jsonrpc": "2.0", "method": "nexus_health", "id": null}"#)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 200);
+    let response: JsonRpcResponse =
+        serde_json::from_slice(response.body()).expect("Failed to parse null id response");
+
+    assert_eq!(response.id, serde_json::Value::Null);
+    assert!(response.result.is_some());
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/rpc")
+        .body(r#"{"jsonrpc": "2.0", "method": "nexus_health"}"#)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 204);
+
+    let response = warp::test::request()
+        .method("GET")
+        .path("/blocks?offset=0&limit=500")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 200);
+    let page: Page<NexusBlock> =
+        serde_json::from_slice(response.body()).expect("Failed to parse blocks page");
+
+    assert_eq!(page.total, 0);
+    assert_eq!(page.limit, MAX_PAGE_LIMIT);
+    assert!(page.items.is_empty());
+
+    let response = warp::test::request()
+        .method("GET")
+        .path("/accounts?limit=abc")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_signed_transactions() {
+    use host::builtin_schedule;
+    use host::events::event_channel;
+    use host::rpc::routes;
+    use nexus_core::mempool::Mempool;
+    use nexus_core::types::AccountOwner;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_signed_transactions";
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let (node_db, state) = setup_components(db_path);
+    let mempool = Mempool::new(node_db.clone(), ZKVMBackend::default());
+    let routes = routes(
+        mempool,
+        node_db,
+        state,
+        event_channel(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+
+    let app_account_id = AppAccountId::from(AppId(100));
+    let tx = Transaction {
+        signature: TxSignature([0u8; 64]),
+        params: TxParams::InitAccount(InitAccount {
+            app_id: app_account_id.clone(),
+            statement: StatementDigest([0u32; 8]),
+            start_nexus_hash: H256::zero(),
+            proving_system: ZKVMBackend::Risc0,
+        }),
+        signer: Some([1u8; 32]),
+    };
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/tx")
+        .json(&tx)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = warp::test::request()
+        .method("GET")
+        .path(&format!(
+            "/account_owner?app_account_id={}",
+            hex::encode(app_account_id.as_h256().as_slice())
+        ))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 200);
+    let account_owner: AccountOwner =
+        serde_json::from_slice(response.body()).expect("Failed to parse account owner");
+
+    assert_eq!(account_owner.owner, None);
+}
+
+#[tokio::test]
+async fn test_submit_proof_envelope() {
+    use host::builtin_schedule;
+    use host::events::event_channel;
+    use host::rpc::routes;
+    use nexus_core::mempool::Mempool;
+    use nexus_core::types::{Proof as NexusProof, ProofEnvelope};
+    use nexus_core::zkvm::ReceiptKind;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_submit_proof_envelope";
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let (node_db, state) = setup_components(db_path);
+    let app_account_id = AppAccountId::from(AppId(100));
+    {
+        let mut state_lock = state.lock().await;
+        let (tree_update_batch, _) = state_lock
+            .update_set(
+                [(
+                    app_account_id.as_h256(),
+                    Some(AccountState {
+                        statement: StatementDigest([2u32; 8]),
+                        proving_system: ZKVMBackend::Risc0,
+                        ..AccountState::zero()
+                    }),
+                )]
+                .into_iter()
+                .collect(),
+                1,
+            )
+            .unwrap();
+        state_lock.update_version(1).unwrap();
+        state_lock.commit(&tree_update_batch.node_batch).unwrap();
+    }
+    let mempool = Mempool::new(node_db.clone(), ZKVMBackend::Risc0);
+    let routes = routes(
+        mempool,
+        node_db,
+        state,
+        event_channel(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+
+    let submit_proof = |proof: NexusProof| Transaction {
+        signature: TxSignature([0u8; 64]),
+        params: TxParams::SubmitProof(SubmitProof {
+            proof,
+            nexus_hash: H256::zero(),
+            state_root: H256::zero(),
+            height: 1,
+            app_id: AppAccountId::from(AppId(100)),
+            data: None,
+        }),
+        signer: None,
+    };
+
+    // Untagged proofs are rejected.
+    let response = warp::test::request()
+        .method("POST")
+        .path("/tx")
+        .json(&submit_proof(NexusProof(vec![0u8; 32])))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let mut envelope = ProofEnvelope::new(
+        ZKVMBackend::Mock,
+        ReceiptKind::Succinct,
+        StatementDigest([1u32; 8]),
+        vec![],
+    );
+    let response = warp::test::request()
+        .method("POST")
+        .path("/tx")
+        .json(&submit_proof(NexusProof::from(envelope.clone())))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 400);
+    assert!(String::from_utf8_lossy(response.body()).contains("Mock"));
+
+    envelope.version = 2;
+    let response = warp::test::request()
+        .method("POST")
+        .path("/tx")
+        .json(&submit_proof(NexusProof::from(envelope)))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 400);
+    assert!(String::from_utf8_lossy(response.body()).contains("version"));
+
+    // Proofs of another statement than the one of the account are rejected.
+    let envelope = ProofEnvelope::new(
+        ZKVMBackend::Risc0,
+        ReceiptKind::Succinct,
+        StatementDigest([1u32; 8]),
+        vec![],
+    );
+    let response = warp::test::request()
+        .method("POST")
+        .path("/tx")
+        .json(&submit_proof(NexusProof::from(envelope)))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 400);
+    assert!(String::from_utf8_lossy(response.body()).contains("statement"));
+}
+
+#[cfg(any(feature = "risc0", feature = "sp1"))]
+#[tokio::test]
+async fn test_foreign_mock_account() {
+    use nexus_core::types::{NexusRollupPI, Proof as NexusProof, ProofEnvelope};
+    use nexus_core::zkvm::ReceiptKind;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_foreign_mock_account";
+    let app_account_id = AppAccountId::from(AppId(101));
+    let statement = StatementDigest([7u32; 8]);
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let file_content = fs::read_to_string("tests/data/avail_headers.json")
+        .await
+        .expect("Failed to read headers JSON file");
+    let headers: Vec<Header> =
+        serde_json::from_str(&file_content).expect("Failed to parse headers JSON file");
+
+    let (node_db, state) = setup_components(db_path);
+    let mut mock_relayer = MockRelayer::new();
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<Header>();
+    let receiver_arc: Arc<Mutex<UnboundedReceiver<Header>>> = Arc::new(Mutex::new(receiver));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    mock_relayer
+        .expect_receiver()
+        .returning(move || receiver_arc.clone());
+    mock_relayer.expect_stop().returning(move || ());
+
+    let node_db_clone = node_db.clone();
+    let app_account_id_clone = app_account_id.clone();
+    let statement_clone = statement.clone();
+    mock_relayer.expect_start().returning(move |_| {
+        let headers = headers.clone();
+        let sender_in_box = sender.clone();
+        let node_db_in_box = node_db_clone.clone();
+        let shutdown_tx_clone = shutdown_tx.clone();
+        let app_account_id = app_account_id_clone.clone();
+        let statement = statement_clone.clone();
+
+        Box::pin(async move {
+            sender_in_box
+                .send(headers[0].clone())
+                .expect("Failed to send header in mock");
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            // The runtime is not proven with the mock backend, but verifies mock proofs itself.
+            let init_tx = Transaction {
+                signature: TxSignature([0u8; 64]),
+                params: TxParams::InitAccount(InitAccount {
+                    app_id: app_account_id.clone(),
+                    statement: statement.clone(),
+                    start_nexus_hash: H256::zero(),
+                    proving_system: ZKVMBackend::Mock,
+                }),
+                signer: None,
+            };
+            let response = Client::new()
+                .post("http://127.0.0.1:7011/tx")
+                .json(&init_tx)
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+
+            sender_in_box
+                .send(headers[1].clone())
+                .expect("Failed to send header in mock");
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            let nexus_hash = {
+                let db_lock = node_db_in_box.lock().await;
+                let header_store: HeaderStore = match db_lock.get(b"previous_headers") {
+                    Ok(Some(i)) => i,
+                    _ => panic!("No header store found"),
+                };
+
+                header_store.first().unwrap().hash()
+            };
+            let public_inputs = NexusRollupPI {
+                nexus_hash,
+                state_root: H256::from([5u8; 32]),
+                height: 1,
+                start_nexus_hash: H256::zero(),
+                app_id: app_account_id.clone(),
+                img_id: statement.clone(),
+                rollup_hash: None,
+            };
+            // Encoded as a mock proof of a program that committed the public inputs.
+            let journal = vec![bincode::serialize(&public_inputs).unwrap()];
+            let envelope = ProofEnvelope::new(
+                ZKVMBackend::Mock,
+                ReceiptKind::Fake,
+                statement.clone(),
+                bincode::serialize(&(statement.clone(), journal)).unwrap(),
+            );
+            let submit_proof_tx = Transaction {
+                signature: TxSignature([0u8; 64]),
+                params: TxParams::SubmitProof(SubmitProof {
+                    proof: NexusProof::from(envelope),
+                    nexus_hash,
+                    state_root: public_inputs.state_root,
+                    height: 1,
+                    app_id: app_account_id.clone(),
+                    data: None,
+                }),
+                signer: None,
+            };
+            let response = Client::new()
+                .post("http://127.0.0.1:7011/tx")
+                .json(&submit_proof_tx)
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+
+            sender_in_box
+                .send(headers[2].clone())
+                .expect("Failed to send header in mock");
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            shutdown_tx_clone.send(true).unwrap();
+        })
+    });
+
+    let state_machine = StateMachine::<ZKVM, Proof>::new(state.clone());
+    match run_nexus(
+        Arc::new(Mutex::new(mock_relayer)),
+        node_db.clone(),
+        state_machine,
+        (ProverMode::MockProof, ZKVMBackend::default(), 7011),
+        ProvingConfig::default(),
+        state.clone(),
+        shutdown_rx,
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(e) => {
+            panic!("Nexus exited with error unexpected error: {:?}", e);
+        }
+    };
+
+    let state_lock = state.lock().await;
+    let current_version = match state_lock.get_version(true) {
+        Ok(Some(i)) => i,
+        Ok(None) => panic!("No version found"),
+        Err(e) => panic!("Internal db error: {:?}", e),
+    };
+    let (account_option, _) =
+        match state_lock.get_with_proof(&H256::from(app_account_id.0), current_version) {
+            Ok(i) => i,
+            Err(e) => panic!("State call failed with error: {:?}", e),
+        };
+
+    let account = account_option.expect("Mock account not registered");
+    assert_eq!(account.proving_system, ZKVMBackend::Mock);
+    assert_eq!(account.height, 1);
+    assert_eq!(account.state_root, [5u8; 32]);
+    drop(state_lock);
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn test_prover_worker() {
+    use host::worker::{run_worker, ProverWorkers, WorkerConfig};
+    use host::{builtin_schedule, execute_batch, execute_batch_async, new_prover, runtime_elf};
+    use nexus_core::runtime::NEXUS_RUNTIME_VERSION;
+    use nexus_core::types::{AvailHeader, Proof as NexusProof};
+    use nexus_core::zkvm::proving::ProvingOptions;
+    use tokio::fs;
+
+    let local_db_path = "./tests/db/test_prover_worker_local";
+    let remote_db_path = "./tests/db/test_prover_worker_remote";
+    for db_path in [local_db_path, remote_db_path] {
+        if let Err(e) = fs::remove_dir_all(db_path).await {
+            eprintln!("Failed to clean up database folder: {:?}", e);
+        }
+    }
+
+    let file_content = fs::read_to_string("tests/data/avail_headers.json")
+        .await
+        .expect("Failed to read headers JSON file");
+    let headers: Vec<Header> =
+        serde_json::from_str(&file_content).expect("Failed to parse headers JSON file");
+    let header = AvailHeader::from(&headers[0]);
+    let txs: Vec<Transaction> = vec![];
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut config = WorkerConfig::new(7100);
+    config.token = Some(String::from("worker-secret"));
+    let worker = run_worker(config, shutdown_rx);
+    sleep(Duration::from_secs(1)).await;
+
+    // Jobs without the worker's token are turned away before being decoded.
+    let response = Client::new()
+        .post("http://127.0.0.1:7100/prove")
+        .bearer_auth("other-secret")
+        .body(vec![0u8; 32])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    // The first worker is unreachable, the job falls back to the second one.
+    let workers = ProverWorkers::new(vec![
+        String::from("http://127.0.0.1:7101"),
+        String::from("http://127.0.0.1:7100/"),
+    ])
+    .unwrap()
+    .with_token(Some(String::from("worker-secret")));
+
+    let (_, state) = setup_components(remote_db_path);
+    let mut state_machine = StateMachine::<ZKVM, Proof>::new(state);
+    let mut header_store = HeaderStore::new(32);
+    let schedule = builtin_schedule(ZKVMBackend::default()).unwrap();
+    let (proof, remote_header, _, _, profile) = execute_batch_async::<ZKVM>(
+        &txs,
+        &mut state_machine,
+        &header,
+        &mut header_store,
+        &schedule.upgrades()[0],
+        Some(&workers),
+        &ProverMode::MockProof,
+        &ProvingOptions::default(),
+    )
+    .await
+    .expect("Remote proving failed");
+    assert_eq!(proof.backend(), ZKVMBackend::default());
+    assert_eq!(profile.tx_count, 0);
+    assert_eq!(remote_header.runtime_version, NEXUS_RUNTIME_VERSION);
+    assert_eq!(header_store.first(), Some(&remote_header));
+
+    // Block proofs verify against the runtime active at the height of the block.
+    let block_proof: NexusProof = proof.try_into().unwrap();
+    let proven_header = schedule
+        .verify_block_proof(
+            &block_proof,
+            Some(runtime_elf(ZKVMBackend::default()).unwrap()),
+            ProverMode::MockProof,
+        )
+        .unwrap();
+    assert_eq!(proven_header, remote_header);
+
+    let (_, state) = setup_components(local_db_path);
+    let mut state_machine = StateMachine::<ZKVM, Proof>::new(state);
+    let (_, local_header, _, _, _) = execute_batch::<_, Proof, ZKVM>(
+        &txs,
+        &mut state_machine,
+        &header,
+        &mut HeaderStore::new(32),
+        new_prover(ZKVMBackend::default(), ProverMode::MockProof).unwrap(),
+    )
+    .await
+    .expect("Local proving failed");
+    assert_eq!(remote_header, local_header);
+
+    shutdown_tx.send(true).unwrap();
+    worker.await.unwrap();
+
+    for db_path in [local_db_path, remote_db_path] {
+        if let Err(e) = fs::remove_dir_all(db_path).await {
+            eprintln!("Failed to clean up database folder: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_update_nexus_block_calldata() {
+    use ethabi::{decode, ParamType, Token, U256};
+    use nexus_core::utils::hasher::{Digest, Sha256};
+    use parity_scale_codec::Encode;
+
+    let header = NexusHeader {
+        parent_hash: H256::from([1u8; 32]),
+        prev_state_root: H256::from([2u8; 32]),
+        state_root: H256::from([3u8; 32]),
+        tx_root: H256::from([4u8; 32]),
+        avail_header_hash: H256::from([5u8; 32]),
+        number: 7,
+        runtime_version: 2,
+    };
+    let seal = vec![0xab; 8];
+    let calldata = host::update_nexus_block_calldata(&header, seal.clone());
+
+    let tokens = decode(
+        &[
+            ParamType::Uint(256),
+            ParamType::Tuple(vec![ParamType::FixedBytes(32), ParamType::FixedBytes(32)]),
+            ParamType::Bytes,
+            ParamType::Bytes,
+        ],
+        &calldata[4..],
+    )
+    .unwrap();
+    assert_eq!(tokens[0], Token::Uint(U256::from(7)));
+    assert_eq!(tokens[3], Token::Bytes(seal));
+
+    // The contract reads the block from the encoded header, so the layout has to hold.
+    let encoded = header.encode();
+    assert_eq!(tokens[2], Token::Bytes(encoded.clone()));
+    assert_eq!(encoded.len(), 168);
+    assert_eq!(&encoded[64..96], header.state_root.as_slice());
+    assert_eq!(&encoded[160..164], &7u32.to_le_bytes());
+    assert_eq!(&encoded[164..168], &2u32.to_le_bytes());
+    assert_eq!(
+        Sha256::digest(&encoded).as_slice(),
+        header.hash().as_slice()
+    );
+    assert_eq!(
+        tokens[1],
+        Token::Tuple(vec![
+            Token::FixedBytes(header.state_root.as_slice().to_vec()),
+            Token::FixedBytes(header.hash().as_slice().to_vec()),
+        ])
+    );
+}
+
+#[test]
+fn test_batch_profile_regions() {
+    use host::BatchProfile;
+    use nexus_core::zkvm::{ProvingStats, PRE_STATE_REGION, RECURSION_REGION, STF_REGION};
+
+    let stats = ProvingStats {
+        total_cycles: 1000,
+        segments: 2,
+        regions: [
+            (PRE_STATE_REGION.to_string(), 100),
+            (STF_REGION.to_string(), 500),
+            (RECURSION_REGION.to_string(), 200),
+            ("unrelated".to_string(), 50),
+        ]
+        .into_iter()
+        .collect(),
+    };
+
+    let profile = BatchProfile::new(3, &stats, Duration::from_millis(1500));
+    assert_eq!(
+        profile,
+        BatchProfile {
+            tx_count: 3,
+            total_cycles: 1000,
+            segments: 2,
+            pre_state_cycles: 100,
+            stf_cycles: 500,
+            recursion_cycles: 200,
+            proving_time_ms: 1500,
+        }
+    );
+
+    // Regions the guest never entered are reported as zero.
+    let profile = BatchProfile::new(0, &ProvingStats::default(), Duration::ZERO);
+    assert_eq!(profile.recursion_cycles, 0);
+}
+
+#[tokio::test]
+async fn test_prove_async_interrupted() {
+    use nexus_core::zkvm::proving::{
+        prove_async, ProvingInterrupted, ProvingOptions, ProvingPhase, ProvingProgress,
+    };
+
+    let slow_proof = |_: &dyn Fn(ProvingProgress)| -> Result<(), Error> {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        Ok(())
+    };
+
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    let options = ProvingOptions {
+        cancel: Some(cancel_rx),
+        ..Default::default()
+    };
+    let cancel = tokio::spawn(async move {
+        sleep(Duration::from_millis(100)).await;
+        cancel_tx.send(true).unwrap();
+    });
+    let error = prove_async(slow_proof, options).await.unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<ProvingInterrupted>(),
+        Some(&ProvingInterrupted::Cancelled)
+    );
+    cancel.await.unwrap();
+
+    let options = ProvingOptions {
+        timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    };
+    let error = prove_async(slow_proof, options).await.unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<ProvingInterrupted>(),
+        Some(&ProvingInterrupted::TimedOut(Duration::from_millis(100)))
+    );
+
+    let reported = Arc::new(std::sync::Mutex::new(vec![]));
+    let reported_clone = reported.clone();
+    let options = ProvingOptions {
+        on_progress: Some(Arc::new(move |progress: ProvingProgress| {
+            reported_clone.lock().unwrap().push(progress)
+        })),
+        ..Default::default()
+    };
+    let proof = prove_async(
+        |progress: &dyn Fn(ProvingProgress)| {
+            progress(ProvingProgress::Phase(ProvingPhase::Proving));
+            progress(ProvingProgress::Segments { done: 1, total: 1 });
+            Ok(42u32)
+        },
+        options,
+    )
+    .await
+    .unwrap();
+    assert_eq!(proof, 42);
+    assert_eq!(
+        *reported.lock().unwrap(),
+        vec![
+            ProvingProgress::Phase(ProvingPhase::Proving),
+            ProvingProgress::Segments { done: 1, total: 1 }
+        ]
+    );
+}
+
+#[test]
+fn test_block_proving_backoff() {
+    use host::proving_backoff;
+
+    assert_eq!(proving_backoff(1), Duration::from_secs(2));
+    assert_eq!(proving_backoff(9), Duration::from_secs(512));
+    assert_eq!(proving_backoff(10), Duration::from_secs(600));
+    assert_eq!(proving_backoff(u32::MAX), Duration::from_secs(600));
+}
+
+#[test]
+fn test_runtime_schedule() {
+    use host::scheduled_elf;
+    use nexus_core::runtime::{RuntimeSchedule, RuntimeUpgrade, NEXUS_RUNTIME_VERSION};
+    use nexus_core::types::StatementDigest;
+
+    let runtime = |version: u32, activation_height: u32| RuntimeUpgrade {
+        version,
+        activation_height,
+        backend: ZKVMBackend::Risc0,
+        img_id: StatementDigest([version; 8]),
+        elf: None,
+    };
+    let header = |number: u32, runtime_version: u32| NexusHeader {
+        parent_hash: H256::zero(),
+        prev_state_root: H256::zero(),
+        state_root: H256::zero(),
+        tx_root: H256::zero(),
+        avail_header_hash: H256::zero(),
+        number,
+        runtime_version,
+    };
+
+    let schedule = RuntimeSchedule::new(vec![runtime(2, 10), runtime(1, 0)]).unwrap();
+    assert_eq!(schedule.active(ZKVMBackend::Risc0, 9).unwrap().version, 1);
+    assert_eq!(schedule.active(ZKVMBackend::Risc0, 10).unwrap().version, 2);
+    assert!(schedule.active(ZKVMBackend::Sp1, 10).is_err());
+
+    // Blocks have to be produced and proven by the runtime active at their height.
+    let img_id = StatementDigest([2; 8]);
+    assert!(schedule
+        .check_block(ZKVMBackend::Risc0, &header(10, 2), &img_id)
+        .is_ok());
+    assert!(schedule
+        .check_block(ZKVMBackend::Risc0, &header(10, 1), &img_id)
+        .is_err());
+    assert!(schedule
+        .check_block(ZKVMBackend::Risc0, &header(9, 2), &img_id)
+        .is_err());
+    assert!(schedule
+        .check_block(ZKVMBackend::Risc0, &header(11, 2), &StatementDigest([1; 8]))
+        .is_err());
+
+    // Upgrades only move forward.
+    assert!(RuntimeSchedule::new(vec![runtime(1, 0), runtime(1, 10)]).is_err());
+    assert!(RuntimeSchedule::new(vec![runtime(2, 0), runtime(1, 10)]).is_err());
+
+    // Runtimes other than the built in one need their ELF.
+    assert!(scheduled_elf(&runtime(NEXUS_RUNTIME_VERSION, 0))
+        .unwrap()
+        .is_none());
+    assert!(scheduled_elf(&runtime(NEXUS_RUNTIME_VERSION + 1, 10)).is_err());
+
+    // Nodes only execute the blocks of the runtime compiled in.
+    assert!(runtime(NEXUS_RUNTIME_VERSION, 0).check_native().is_ok());
+    assert!(runtime(NEXUS_RUNTIME_VERSION + 1, 10)
+        .check_native()
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_genesis() {
+    use host::index::get_account_owner;
+    use host::{apply_genesis, builtin_schedule};
+    use nexus_core::genesis::{Genesis, GenesisAccount};
+    use nexus_core::types::AvailHeader;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_genesis";
+    let app_account_id = AppAccountId::from(AppId(100));
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let file_content = fs::read_to_string("tests/data/avail_headers.json")
+        .await
+        .expect("Failed to read headers JSON file");
+    let headers: Vec<Header> =
+        serde_json::from_str(&file_content).expect("Failed to parse headers JSON file");
+    let genesis = Genesis {
+        network_id: String::from("nexus-test"),
+        start_avail_block: headers[0].number - 1,
+        start_avail_hash: AvailHeader::from(&headers[0]).parent_hash,
+        runtime_version: 1,
+        accounts: vec![GenesisAccount {
+            app_id: app_account_id.clone(),
+            statement: StatementDigest([1, 2, 3, 4, 5, 6, 7, 8]),
+            start_nexus_hash: H256::zero(),
+            proving_system: ZKVMBackend::Risc0,
+            owner: Some(H256::from([7u8; 32])),
+        }],
+    };
+    let runtime = builtin_schedule(ZKVMBackend::default()).unwrap().upgrades()[0].clone();
+
+    let (node_db, state) = setup_components(db_path);
+    let genesis_header = apply_genesis(&node_db, &state, &genesis, &runtime)
+        .await
+        .expect("Failed to apply genesis");
+    assert_eq!(genesis_header.number, 0);
+    assert_eq!(genesis_header.parent_hash, genesis.network_hash());
+    assert_ne!(genesis_header.state_root, H256::zero());
+
+    // Applying the genesis again only checks it, the one of another network is rejected.
+    assert_eq!(
+        apply_genesis(&node_db, &state, &genesis, &runtime)
+            .await
+            .unwrap(),
+        genesis_header
+    );
+    let mut other_genesis = genesis.clone();
+    other_genesis.network_id = String::from("nexus-other");
+    assert!(apply_genesis(&node_db, &state, &other_genesis, &runtime)
+        .await
+        .is_err());
+
+    // Mock the Relayer instance, which has to start right after the genesis Avail block.
+    let mut mock_relayer = MockRelayer::new();
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<Header>();
+    let receiver_arc: Arc<Mutex<UnboundedReceiver<Header>>> = Arc::new(Mutex::new(receiver));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    mock_relayer
+        .expect_receiver()
+        .returning(move || receiver_arc.clone());
+    mock_relayer.expect_stop().returning(move || ());
+    mock_relayer
+        .expect_start()
+        .with(eq(genesis.start_avail_block + 1))
+        .returning(move |_| {
+            let header = headers[0].clone();
+            let sender_in_box = sender.clone();
+            let shutdown_tx_clone = shutdown_tx.clone();
+
+            Box::pin(async move {
+                sender_in_box
+                    .send(header)
+                    .expect("Failed to send header in mock");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                shutdown_tx_clone.send(true).unwrap();
+            })
+        });
+
+    let state_machine = StateMachine::<ZKVM, Proof>::new(state.clone());
+    match run_nexus(
+        Arc::new(Mutex::new(mock_relayer)),
+        node_db.clone(),
+        state_machine,
+        (ProverMode::MockProof, ZKVMBackend::default(), 7008),
+        ProvingConfig::default(),
+        state.clone(),
+        shutdown_rx,
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(e) => {
+            panic!("Nexus exited with error unexpected error: {:?}", e);
+        }
+    };
+
+    // Block 1 is built on the genesis block and its state.
+    let db_lock = node_db.lock().await;
+    let old_headers: HeaderStore = match db_lock.get(b"previous_headers") {
+        Ok(Some(i)) => i,
+        Ok(None) => panic!("No header store found"),
+        Err(e) => panic!("Internal db error: {:?}", e),
+    };
+    let latest_header = old_headers.first().unwrap();
+    assert_eq!(latest_header.number, 1);
+    assert_eq!(latest_header.parent_hash, genesis_header.hash());
+    assert_eq!(latest_header.prev_state_root, genesis_header.state_root);
+    assert_eq!(
+        get_account_owner(&db_lock, &app_account_id).unwrap(),
+        Some([7u8; 32])
+    );
+    drop(db_lock);
+
+    let state_lock = state.lock().await;
+    let account = match state_lock.get(&H256::from(app_account_id.0), 1) {
+        Ok(i) => i,
+        Err(e) => panic!("State call failed with error: {:?}", e),
+    };
+    assert_eq!(account, Some(genesis.accounts[0].account_state()));
+    drop(state_lock);
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn test_websocket_subscriptions() {
+    use host::builtin_schedule;
+    use host::events::{event_channel, NexusEvent};
+    use host::rpc::routes;
+    use nexus_core::mempool::Mempool;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_websocket_subscriptions";
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let (node_db, state) = setup_components(db_path);
+    let events = event_channel();
+    let filter = routes(
+        Mempool::new(node_db.clone(), ZKVMBackend::default()),
+        node_db,
+        state,
+        events.clone(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+    let mut client = warp::test::ws()
+        .path("/ws")
+        .handshake(filter)
+        .await
+        .expect("Websocket handshake failed");
+
+    let subscribed_hash = H256::from([1u8; 32]);
+    let requests = vec![
+        (serde_json::json!({ "method": "subscribe_headers" }), true),
+        // Hashes that are not 32 bytes are rejected without closing the connection.
+        (
+            serde_json::json!({ "method": "subscribe_tx_status", "tx_hashes": ["0102"] }),
+            false,
+        ),
+        (
+            serde_json::json!({
+                "method": "subscribe_tx_status",
+                "tx_hashes": [hex::encode(subscribed_hash.as_slice())]
+            }),
+            true,
+        ),
+    ];
+    for (request, accepted) in requests {
+        client.send_text(request.to_string()).await;
+        let response: serde_json::Value =
+            serde_json::from_str(client.recv().await.unwrap().to_str().unwrap()).unwrap();
+        assert_eq!(response.get("result").is_some(), accepted);
+    }
+
+    let header = NexusHeader {
+        parent_hash: H256::zero(),
+        prev_state_root: H256::zero(),
+        state_root: H256::zero(),
+        tx_root: H256::zero(),
+        avail_header_hash: H256::zero(),
+        number: 1,
+        runtime_version: 1,
+    };
+    let tx_status = |tx_hash: H256| NexusEvent::TxStatus {
+        tx_hash,
+        status: TransactionStatus::Successful,
+        block_hash: Some(header.hash()),
+        error: None,
+    };
+    events.send(tx_status(H256::from([2u8; 32]))).unwrap();
+    events
+        .send(NexusEvent::NewHeader {
+            header: header.clone(),
+        })
+        .unwrap();
+    events.send(tx_status(subscribed_hash.clone())).unwrap();
+
+    // Only events of subscriptions are pushed, in order.
+    let received: NexusEvent =
+        serde_json::from_str(client.recv().await.unwrap().to_str().unwrap()).unwrap();
+    match received {
+        NexusEvent::NewHeader { header: i } => assert_eq!(i, header),
+        event => panic!("Unexpected event {:?}", event),
+    }
+    let received: NexusEvent =
+        serde_json::from_str(client.recv().await.unwrap().to_str().unwrap()).unwrap();
+    match received {
+        NexusEvent::TxStatus { tx_hash, .. } => assert_eq!(tx_hash, subscribed_hash),
+        event => panic!("Unexpected event {:?}", event),
+    }
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn test_list_accounts() {
+    use host::builtin_schedule;
+    use host::events::event_channel;
+    use host::index::{list_accounts, AccountListItem, Page, PageRequest};
+    use host::rpc::routes;
+    use nexus_core::mempool::Mempool;
+    use std::collections::HashMap;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_list_accounts";
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let (node_db, state) = setup_components(db_path);
+    let account = |statement: u32| AccountState {
+        statement: StatementDigest([statement; 8]),
+        state_root: [0u8; 32],
+        start_nexus_hash: [0u8; 32],
+        last_proof_height: 0,
+        height: 0,
+        proving_system: ZKVMBackend::Risc0,
+        owner: [0; 32],
+    };
+    let updates: Vec<(u64, HashMap<H256, Option<AccountState>>)> = vec![
+        (
+            1,
+            (1u8..=3)
+                .map(|i| (H256::from([i; 32]), Some(account(i as u32))))
+                .collect(),
+        ),
+        (
+            2,
+            vec![(H256::from([2u8; 32]), Some(account(20)))]
+                .into_iter()
+                .collect(),
+        ),
+    ];
+    {
+        let mut state_lock = state.lock().await;
+        for (version, set) in updates {
+            let (tree_update_batch, _) = state_lock.update_set(set, version).unwrap();
+            state_lock.update_version(version).unwrap();
+            state_lock.commit(&tree_update_batch.node_batch).unwrap();
+        }
+    }
+
+    // Accounts are listed from the state tree at the requested version.
+    {
+        let state_lock = state.lock().await;
+        let page = list_accounts(&state_lock, 1, &PageRequest::new(None, None)).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.items[1].account, account(2));
+
+        let page = list_accounts(&state_lock, 2, &PageRequest::new(None, None)).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(
+            page.items
+                .iter()
+                .map(|i| i.app_account_id.clone())
+                .collect::<Vec<AppAccountId>>(),
+            (1u8..=3).map(|i| AppAccountId([i; 32])).collect::<Vec<_>>()
+        );
+        assert_eq!(page.items[1].account, account(20));
+    }
+
+    let routes = routes(
+        Mempool::new(node_db.clone(), ZKVMBackend::default()),
+        node_db,
+        state,
+        event_channel(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+    let response = warp::test::request()
+        .method("GET")
+        .path("/accounts?offset=2&limit=2")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 200);
+    let page: Page<AccountListItem> =
+        serde_json::from_slice(response.body()).expect("Failed to parse accounts page");
+
+    assert_eq!(page.total, 3);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].app_account_id, AppAccountId([3u8; 32]));
+    assert_eq!(page.items[0].account, account(3));
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn test_failed_tx_error() {
+    use host::events::event_channel;
+    use host::rpc::routes;
+    use host::{apply_genesis, builtin_schedule};
+    use nexus_core::errors::TxError;
+    use nexus_core::genesis::{Genesis, GenesisAccount};
+    use nexus_core::mempool::Mempool;
+    use nexus_core::types::AvailHeader;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_failed_tx_error";
+    let app_account_id = AppAccountId::from(AppId(100));
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let file_content = fs::read_to_string("tests/data/avail_headers.json")
+        .await
+        .expect("Failed to read headers JSON file");
+    let headers: Vec<Header> =
+        serde_json::from_str(&file_content).expect("Failed to parse headers JSON file");
+
+    // The account is registered at genesis, so registering it again fails.
+    let (node_db, state) = setup_components(db_path);
+    let genesis = Genesis {
+        network_id: String::from("nexus-test"),
+        start_avail_block: headers[0].number - 1,
+        start_avail_hash: AvailHeader::from(&headers[0]).parent_hash,
+        runtime_version: 1,
+        accounts: vec![GenesisAccount {
+            app_id: app_account_id.clone(),
+            statement: StatementDigest([1u32; 8]),
+            start_nexus_hash: H256::zero(),
+            proving_system: ZKVMBackend::Risc0,
+            owner: None,
+        }],
+    };
+    let runtime = builtin_schedule(ZKVMBackend::default()).unwrap().upgrades()[0].clone();
+    apply_genesis(&node_db, &state, &genesis, &runtime)
+        .await
+        .expect("Failed to apply genesis");
+
+    let tx = Transaction {
+        signature: TxSignature([0u8; 64]),
+        params: TxParams::InitAccount(InitAccount {
+            app_id: app_account_id.clone(),
+            statement: StatementDigest([2u32; 8]),
+            start_nexus_hash: H256::zero(),
+            proving_system: ZKVMBackend::default(),
+        }),
+        signer: None,
+    };
+    let tx_hash = tx.hash();
+
+    let mut mock_relayer = MockRelayer::new();
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<Header>();
+    let receiver_arc: Arc<Mutex<UnboundedReceiver<Header>>> = Arc::new(Mutex::new(receiver));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    mock_relayer
+        .expect_receiver()
+        .returning(move || receiver_arc.clone());
+    mock_relayer.expect_stop().returning(move || ());
+    mock_relayer.expect_start().returning(move |_| {
+        let header = headers[0].clone();
+        let sender_in_box = sender.clone();
+        let shutdown_tx_clone = shutdown_tx.clone();
+        let tx = tx.clone();
+
+        Box::pin(async move {
+            let response = Client::new()
+                .post("http://127.0.0.1:7009/tx")
+                .json(&tx)
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+
+            sender_in_box
+                .send(header)
+                .expect("Failed to send header in mock");
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            shutdown_tx_clone.send(true).unwrap();
+        })
+    });
+
+    let state_machine = StateMachine::<ZKVM, Proof>::new(state.clone());
+    match run_nexus(
+        Arc::new(Mutex::new(mock_relayer)),
+        node_db.clone(),
+        state_machine,
+        (ProverMode::MockProof, ZKVMBackend::default(), 7009),
+        ProvingConfig::default(),
+        state.clone(),
+        shutdown_rx,
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(e) => {
+            panic!("Nexus exited with error unexpected error: {:?}", e);
+        }
+    };
+
+    // The failure reason is returned along with the status.
+    let routes = routes(
+        Mempool::new(node_db.clone(), ZKVMBackend::default()),
+        node_db,
+        state,
+        event_channel(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+    let response = warp::test::request()
+        .method("GET")
+        .path(&format!(
+            "/tx_status?tx_hash={}",
+            hex::encode(tx_hash.as_slice())
+        ))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 200);
+    let tx_with_status: TransactionWithStatus =
+        serde_json::from_slice(response.body()).expect("Failed to parse tx status");
+
+    assert_eq!(tx_with_status.status, TransactionStatus::Failed);
+    assert!(tx_with_status.block_hash.is_some());
+    assert_eq!(
+        tx_with_status.error,
+        Some(TxError {
+            code: 2,
+            message: String::from("Account already initiated."),
+        })
+    );
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+}
+
+fn signed_tx(key: &ed25519_dalek::SigningKey, params: TxParams) -> Transaction {
+    use ed25519_dalek::Signer;
+
+    let mut tx = Transaction {
+        signature: TxSignature([0u8; 64]),
+        params,
+        signer: Some(key.verifying_key().to_bytes()),
+    };
+    tx.signature = TxSignature(key.sign(tx.hash().as_slice()).to_bytes());
+
+    tx
+}
+
+#[test]
+fn test_stf_enforces_account_owner() {
+    use nexus_core::errors::NexusTxError;
+    use nexus_core::stf::StateTransitionFunction;
+    use nexus_core::types::{Proof as NexusProof, TransactionZKVM};
+
+    let owner = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+    let other = ed25519_dalek::SigningKey::from_bytes(&[2u8; 32]);
+    let stf = StateTransitionFunction::<ZKVM>::with_backend(ZKVMBackend::default());
+    let app_account_id = AppAccountId::from(AppId(100));
+    let headers = HeaderStore::new(32);
+
+    let init_account = TxParams::InitAccount(InitAccount {
+        app_id: app_account_id.clone(),
+        statement: StatementDigest([1u32; 8]),
+        start_nexus_hash: H256::zero(),
+        proving_system: ZKVMBackend::default(),
+    });
+    let (_, account) = stf
+        .execute_tx(
+            &TransactionZKVM::from(&signed_tx(&owner, init_account)),
+            (&app_account_id, &AccountState::zero()),
+            &headers,
+        )
+        .expect("Signed registration should succeed");
+    assert_eq!(account.owner, owner.verifying_key().to_bytes());
+
+    let submit_proof = TxParams::SubmitProof(SubmitProof {
+        proof: NexusProof(vec![]),
+        nexus_hash: H256::zero(),
+        state_root: H256::zero(),
+        height: 1,
+        app_id: app_account_id.clone(),
+        data: None,
+    });
+    let execute = |tx: Transaction| {
+        stf.execute_tx(
+            &TransactionZKVM::from(&tx),
+            (&app_account_id, &account),
+            &headers,
+        )
+    };
+
+    let result = execute(signed_tx(&other, submit_proof.clone()));
+    assert_eq!(result.unwrap_err(), NexusTxError::NotAccountOwner);
+
+    let mut tx = signed_tx(&owner, submit_proof.clone());
+    tx.signer = None;
+    assert_eq!(execute(tx).unwrap_err(), NexusTxError::NotAccountOwner);
+
+    //Claiming the owner key without its signature does not help.
+    let mut tx = signed_tx(&other, submit_proof);
+    tx.signer = Some(owner.verifying_key().to_bytes());
+    assert_eq!(execute(tx).unwrap_err(), NexusTxError::InvalidSignature);
+}
+
+#[tokio::test]
+async fn test_signed_registration_round_trip() {
+    use host::builtin_schedule;
+    use host::events::event_channel;
+    use host::rpc::routes;
+    use nexus_core::mempool::Mempool;
+    use nexus_core::types::AccountOwner;
+    use tokio::fs;
+
+    let db_path = "./tests/db/test_signed_registration_round_trip";
+    let app_account_id = AppAccountId::from(AppId(100));
+    let key = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+
+    let file_content = fs::read_to_string("tests/data/avail_headers.json")
+        .await
+        .expect("Failed to read headers JSON file");
+    let headers: Vec<Header> =
+        serde_json::from_str(&file_content).expect("Failed to parse headers JSON file");
+
+    let (node_db, state) = setup_components(db_path);
+    let tx = signed_tx(
+        &key,
+        TxParams::InitAccount(InitAccount {
+            app_id: app_account_id.clone(),
+            statement: StatementDigest([1u32; 8]),
+            start_nexus_hash: H256::zero(),
+            proving_system: ZKVMBackend::default(),
+        }),
+    );
+    let tx_hash = tx.hash();
+
+    let mut mock_relayer = MockRelayer::new();
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<Header>();
+    let receiver_arc: Arc<Mutex<UnboundedReceiver<Header>>> = Arc::new(Mutex::new(receiver));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    mock_relayer
+        .expect_receiver()
+        .returning(move || receiver_arc.clone());
+    mock_relayer.expect_stop().returning(move || ());
+    mock_relayer.expect_start().returning(move |_| {
+        let header = headers[0].clone();
+        let sender_in_box = sender.clone();
+        let shutdown_tx_clone = shutdown_tx.clone();
+        let tx = tx.clone();
+
+        Box::pin(async move {
+            let response = Client::new()
+                .post("http://127.0.0.1:7010/tx")
+                .json(&tx)
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+
+            sender_in_box
+                .send(header)
+                .expect("Failed to send header in mock");
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            shutdown_tx_clone.send(true).unwrap();
+        })
+    });
+
+    let state_machine = StateMachine::<ZKVM, Proof>::new(state.clone());
+    match run_nexus(
+        Arc::new(Mutex::new(mock_relayer)),
+        node_db.clone(),
+        state_machine,
+        (ProverMode::MockProof, ZKVMBackend::default(), 7010),
+        ProvingConfig::default(),
+        state.clone(),
+        shutdown_rx,
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(e) => {
+            panic!("Nexus exited with error unexpected error: {:?}", e);
+        }
+    };
+
+    let routes = routes(
+        Mempool::new(node_db.clone(), ZKVMBackend::default()),
+        node_db,
+        state,
+        event_channel(),
+        builtin_schedule(ZKVMBackend::default()).unwrap(),
+    );
+    let response = warp::test::request()
+        .method("GET")
+        .path(&format!(
+            "/tx_status?tx_hash={}",
+            hex::encode(tx_hash.as_slice())
+        ))
+        .reply(&routes)
+        .await;
+    let tx_with_status: TransactionWithStatus =
+        serde_json::from_slice(response.body()).expect("Failed to parse tx status");
+    assert_eq!(tx_with_status.status, TransactionStatus::Successful);
+
+    // The signer is the owner both in the state tree and in the owner index.
+    let owner = key.verifying_key().to_bytes();
+    let app_account_id_hex = hex::encode(app_account_id.as_h256().as_slice());
+    let response = warp::test::request()
+        .method("GET")
+        .path(&format!("/account?app_account_id={}", app_account_id_hex))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), 200);
+    let account: AccountWithProof =
+        serde_json::from_slice(response.body()).expect("Failed to parse account");
+    assert_eq!(account.account.owner, owner);
+
+    let response = warp::test::request()
+        .method("GET")
+        .path(&format!(
+            "/account_owner?app_account_id={}",
+            app_account_id_hex
+        ))
+        .reply(&routes)
+        .await;
+    let account_owner: AccountOwner =
+        serde_json::from_slice(response.body()).expect("Failed to parse account owner");
+    assert_eq!(account_owner.owner, Some(H256::from(owner)));
+
+    if let Err(e) = fs::remove_dir_all(db_path).await {
+        eprintln!("Failed to clean up database folder: {:?}", e);
+    }
+}
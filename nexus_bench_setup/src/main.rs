@@ -1,4 +1,7 @@
-use adapter_sdk::{api::NexusAPI, types::AdapterConfig};
+use adapter_sdk::{
+    api::NexusAPI,
+    types::{AdapterConfig, SigningKeySource},
+};
 use anyhow::Error;
 use geth_methods::{ADAPTER_ELF, ADAPTER_ID};
 use nexus_core::db::NodeDB;
@@ -8,8 +11,8 @@ use nexus_core::types::{
     AccountWithProof, AppAccountId, AppId, AvailHeader, HeaderStore, InitAccount, NexusHeader,
     NexusRollupPI, StatementDigest, SubmitProof, Transaction, TxParams, TxSignature, H256,
 };
-use nexus_core::zkvm::ProverMode;
-use nexus_host::execute_batch;
+use nexus_core::zkvm::{ProverMode, ZKVMBackend};
+use nexus_host::{execute_batch, new_prover};
 use risc0_zkvm::{default_prover, ExecutorEnv};
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
@@ -24,10 +27,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use nexus_core::zkvm::any::{AnyProof as Proof, AnyProver as Prover};
+use nexus_core::zkvm::risczero::RiscZeroProof;
 #[cfg(feature = "risc0")]
-use nexus_core::zkvm::risczero::{RiscZeroProof as Proof, RiscZeroProver as Prover, ZKVM};
-#[cfg(feature = "sp1")]
-use nexus_core::zkvm::sp1::{Sp1Proof as Proof, Sp1Prover as Prover, SP1ZKVM as ZKVM};
+use nexus_core::zkvm::risczero::ZKVM;
+#[cfg(all(feature = "sp1", not(feature = "risc0")))]
+use nexus_core::zkvm::sp1::SP1ZKVM as ZKVM;
 
 #[cfg(any(feature = "sp1"))]
 use env_logger;
@@ -91,7 +96,9 @@ async fn generate_init_account_transactions(
                 app_id: AppAccountId::from(AppId(txn_index as u32)),
                 statement: StatementDigest(ADAPTER_ID),
                 start_nexus_hash: header.hash(),
+                proving_system: ZKVMBackend::Risc0,
             }),
+            signer: None,
         };
         init_account_transactions.push(tx);
     }
@@ -99,12 +106,12 @@ async fn generate_init_account_transactions(
     let json = serde_json::to_string_pretty(&init_account_transactions).unwrap();
     fs::write("mock_data/init_account_txns.json", json).unwrap();
 
-    let (_, header, _, _) = execute_batch::<Prover, Proof, ZKVM>(
+    let (_, header, _, _, _) = execute_batch::<Prover, Proof, ZKVM>(
         &init_account_transactions,
         state_machine,
         &avail_headers[1],
         header_store,
-        prover_mode.clone(),
+        new_prover(ZKVMBackend::default(), prover_mode.clone()).unwrap(),
     )
     .await
     .unwrap();
@@ -123,7 +130,13 @@ async fn generate_submit_proof_transactions(prover_mode: ProverMode, header: Nex
             vk: [0u8; 32],
             rollup_start_height: 606460,
             prover_mode: prover_mode.clone(),
+            proving_system: Some(ZKVMBackend::Risc0),
             avail_url: String::from("wss://turing-rpc.avail.so:443/ws"),
+            nexus_url: String::from("http://127.0.0.1:7000"),
+            signing_key: SigningKeySource::DevSeed(format!("{:064x}", txn_index + 1)),
+            start_nexus_hash: None,
+            api_addr: String::from("127.0.0.1:3031"),
+            avail_signing_key: None,
         };
 
         // Retrieve or initialize the adapter state data from the database
@@ -168,7 +181,7 @@ async fn generate_submit_proof_transactions(prover_mode: ProverMode, header: Nex
             }
         };
 
-        let recursive_proof = Proof(prove_info.receipt);
+        let recursive_proof = Proof::from(RiscZeroProof(prove_info.receipt));
 
         let tx = Transaction {
             signature: TxSignature([0u8; 64]),
@@ -186,6 +199,7 @@ async fn generate_submit_proof_transactions(prover_mode: ProverMode, header: Nex
                 height: public_inputs.height,
                 data: None,
             }),
+            signer: None,
         };
 
         submit_proof_transactions.push(tx.clone());
@@ -282,12 +296,12 @@ async fn main() -> Result<(), Error> {
         }
     };
 
-    let (_, header, _, _) = execute_batch::<Prover, Proof, ZKVM>(
+    let (_, header, _, _, _) = execute_batch::<Prover, Proof, ZKVM>(
         &mock_txs,
         &mut state_machine,
         &avail_headers[0],
         &mut header_store,
-        prover_mode.clone(),
+        new_prover(ZKVMBackend::default(), prover_mode.clone()).unwrap(),
     )
     .await
     .unwrap();
@@ -0,0 +1,216 @@
+use clap::Subcommand;
+use nexus_core::genesis::{Genesis, GenesisAccount};
+use nexus_core::runtime::NEXUS_RUNTIME_VERSION;
+use nexus_core::types::{AppAccountId, StatementDigest, H256};
+use nexus_core::zkvm::ZKVMBackend;
+use std::fs;
+use std::process::exit;
+
+#[derive(Subcommand, Debug)]
+pub enum GenesisCommands {
+    /// Writes a genesis file
+    Generate {
+        /// Network the chain runs on, e.g. nexus-devnet
+        #[arg(long)]
+        network_id: String,
+
+        /// Avail block the chain starts at
+        #[arg(long)]
+        start_avail_block: u32,
+
+        /// Hash of the start Avail block, hex encoded
+        #[arg(long, value_parser = parse_hash)]
+        start_avail_hash: [u8; 32],
+
+        /// Runtime version of the genesis block
+        #[arg(long, default_value_t = NEXUS_RUNTIME_VERSION)]
+        runtime_version: u32,
+
+        /// Pre-registered account as app_id,statement,start_nexus_hash,proving_system[,owner],
+        /// hex encoded, the statement being the image ID of the rollup
+        #[arg(long = "account", value_parser = parse_account)]
+        accounts: Vec<GenesisAccount>,
+
+        /// Path the genesis file is written to
+        #[arg(short, long, default_value_t = String::from("genesis.json"))]
+        out: String,
+    },
+
+    /// Checks a genesis file
+    Validate {
+        /// Path of the genesis file
+        path: String,
+
+        /// Avail RPC the start block is checked against
+        #[arg(long, default_value_t = String::from("wss://turing-rpc.avail.so:443/ws"))]
+        avail_rpc: String,
+
+        /// Skips checking the start block against Avail
+        #[arg(long)]
+        offline: bool,
+    },
+}
+
+pub fn run_genesis(genesis_cmd: GenesisCommands) {
+    match genesis_cmd {
+        GenesisCommands::Generate {
+            network_id,
+            start_avail_block,
+            start_avail_hash,
+            runtime_version,
+            accounts,
+            out,
+        } => generate_genesis(
+            Genesis {
+                network_id,
+                start_avail_block,
+                start_avail_hash: H256::from(start_avail_hash),
+                runtime_version,
+                accounts,
+            },
+            &out,
+        ),
+        GenesisCommands::Validate {
+            path,
+            avail_rpc,
+            offline,
+        } => validate_genesis(
+            &path,
+            match offline {
+                true => None,
+                false => Some(&avail_rpc),
+            },
+        ),
+    }
+}
+
+fn parse_hash(value: &str) -> Result<[u8; 32], String> {
+    let bytes = match hex::decode(value.trim_start_matches("0x")) {
+        Ok(i) => i,
+        Err(e) => return Err(format!("Invalid hex {}: {}", value, e)),
+    };
+
+    bytes
+        .try_into()
+        .map_err(|_| format!("Expected 32 bytes, got {}", value))
+}
+
+fn parse_account(value: &str) -> Result<GenesisAccount, String> {
+    let fields: Vec<&str> = value.split(',').collect();
+    if fields.len() != 4 && fields.len() != 5 {
+        return Err(format!(
+            "Expected app_id,statement,start_nexus_hash,proving_system[,owner], got {}",
+            value
+        ));
+    }
+
+    Ok(GenesisAccount {
+        app_id: AppAccountId(parse_hash(fields[0])?),
+        statement: StatementDigest::from_le_bytes(parse_hash(fields[1])?),
+        start_nexus_hash: H256::from(parse_hash(fields[2])?),
+        proving_system: fields[3]
+            .parse::<ZKVMBackend>()
+            .map_err(|e| e.to_string())?,
+        owner: match fields.get(4) {
+            Some(i) => Some(H256::from(parse_hash(i)?)),
+            None => None,
+        },
+    })
+}
+
+fn generate_genesis(genesis: Genesis, out: &str) {
+    if let Err(e) = genesis.validate() {
+        eprintln!("Invalid genesis: {}", e);
+        exit(1);
+    }
+
+    let json = serde_json::to_string_pretty(&genesis).expect("Failed to serialize genesis");
+    if let Err(e) = fs::write(out, json) {
+        eprintln!("Failed to write genesis to {}: {}", out, e);
+        exit(1);
+    }
+
+    println!(
+        "Genesis of network {} with {} account(s) written to {}",
+        genesis.network_id,
+        genesis.accounts.len(),
+        out
+    );
+}
+
+/// Hash of the Avail block at `height`, `None` when it is not produced yet.
+async fn avail_block_hash(avail_rpc: &str, height: u32) -> Result<Option<H256>, String> {
+    let (subxt_client, _) = avail_subxt::build_client(avail_rpc.to_string(), false)
+        .await
+        .map_err(|e| format!("Failed to connect to Avail RPC {}: {}", avail_rpc, e))?;
+
+    match subxt_client.rpc().block_hash(Some(height.into())).await {
+        Ok(i) => Ok(i.map(|hash| H256::from(hash.to_fixed_bytes()))),
+        Err(e) => Err(format!("Failed to get Avail block {}: {}", height, e)),
+    }
+}
+
+/// Checks the start block of the genesis is the Avail block at its height.
+fn check_start_block(genesis: &Genesis, avail_rpc: &str) -> Result<(), String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    match runtime.block_on(avail_block_hash(avail_rpc, genesis.start_avail_block))? {
+        Some(i) if i == genesis.start_avail_hash => Ok(()),
+        Some(i) => Err(format!(
+            "Start Avail block {} has hash {}, not {}",
+            genesis.start_avail_block, i, genesis.start_avail_hash
+        )),
+        None => Err(format!(
+            "Start Avail block {} not found on {}",
+            genesis.start_avail_block, avail_rpc
+        )),
+    }
+}
+
+fn validate_genesis(path: &str, avail_rpc: Option<&str>) {
+    let genesis = match Genesis::from_file(path) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Invalid genesis file {}: {}", path, e);
+            exit(1);
+        }
+    };
+
+    println!("Network: {}", genesis.network_id);
+    println!(
+        "Start Avail block: {} ({})",
+        genesis.start_avail_block, genesis.start_avail_hash
+    );
+    match avail_rpc {
+        Some(avail_rpc) => {
+            if let Err(e) = check_start_block(&genesis, avail_rpc) {
+                eprintln!("Invalid genesis file {}: {}", path, e);
+                exit(1);
+            }
+            println!("Start Avail block found on {}", avail_rpc);
+        }
+        None => println!("Start Avail block not checked against Avail"),
+    }
+    println!("Runtime version: {}", genesis.runtime_version);
+    if genesis.runtime_version != NEXUS_RUNTIME_VERSION {
+        println!(
+            "Runtime version {} is not built in, the node needs a runtime schedule activating it at height 0",
+            genesis.runtime_version
+        );
+    }
+    for account in genesis.accounts.iter() {
+        println!(
+            "Account {}: {:?} rollup, owner {}",
+            account.app_id,
+            account.proving_system,
+            match &account.owner {
+                Some(i) => i.to_string(),
+                None => String::from("none"),
+            }
+        );
+    }
+    println!("Genesis file {} is valid", path);
+}
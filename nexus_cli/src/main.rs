@@ -4,6 +4,11 @@ use std::fs;
 use std::path::Path;
 use std::process::{exit, Command as Cmd};
 
+#[cfg(any(feature = "genesis"))]
+mod genesis;
+#[cfg(any(feature = "genesis"))]
+use genesis::{run_genesis, GenesisCommands};
+
 /// Simple CLI to manage nexus services
 #[derive(Parser, Debug)]
 #[command(version, about = "A simple CLI for managing repository tasks", long_about = None)]
@@ -19,6 +24,9 @@ enum Commands {
         /// Runs the command in development mode
         #[arg(long)]
         dev: bool,
+        /// Optional genesis file the chain is started from
+        #[arg(long)]
+        genesis: Option<String>,
         #[command(subcommand)]
         zkvm: Option<ZKVMOptions>,
     },
@@ -52,6 +60,13 @@ enum Commands {
         #[arg(short, long)]
         env: Option<String>,
     },
+
+    /// Generates and validates genesis files
+    #[cfg(any(feature = "genesis"))]
+    Genesis {
+        #[command(subcommand)]
+        genesis_cmd: GenesisCommands,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -113,8 +128,10 @@ fn main() {
             app_id,
             zkvm,
         } => run_zksync(&url, &zksync_dir, dev, app_id, zkvm),
-        Commands::Nexus { dev, zkvm } => run_nexus(&nexus_dir, dev, zkvm),
+        Commands::Nexus { dev, genesis, zkvm } => run_nexus(&nexus_dir, dev, genesis, zkvm),
         Commands::Init { env } => init_env(env),
+        #[cfg(any(feature = "genesis"))]
+        Commands::Genesis { genesis_cmd } => run_genesis(genesis_cmd),
     }
 }
 
@@ -181,7 +198,7 @@ fn run_zksync(api_url: &str, zksync_dir: &Path, dev: bool, app_id: u64, zkvm: Op
     }
 }
 
-fn run_nexus(nexus_dir: &Path, dev: bool, zkvm: Option<ZKVMOptions>) {
+fn run_nexus(nexus_dir: &Path, dev: bool, genesis: Option<String>, zkvm: Option<ZKVMOptions>) {
     println!("Running nexus at {:?}", nexus_dir);
 
     let mut command = Cmd::new("cargo");
@@ -204,9 +221,24 @@ fn run_nexus(nexus_dir: &Path, dev: bool, zkvm: Option<ZKVMOptions>) {
         }
     }
 
+    let mut node_args: Vec<String> = vec![];
     if dev {
         command.env("RISC0_DEV_MODE", "true");
-        command.arg("--").arg("--dev");
+        node_args.push(String::from("--dev"));
+    }
+    if let Some(path) = genesis {
+        //The node runs from its own directory.
+        let path = match fs::canonicalize(&path) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("Genesis file {} not found: {}", path, e);
+                exit(1);
+            }
+        };
+        node_args.push(format!("--genesis={}", path.display()));
+    }
+    if !node_args.is_empty() {
+        command.arg("--").args(node_args);
     }
 
     let status = command.status().expect("Failed to execute `cargo run`");
@@ -4,9 +4,12 @@ const require = createRequire(import.meta.url);
 const proofManagerAbi = require("./abi/proofManager.json");
 
 import { Provider } from "zksync-ethers";
-import { AccountState } from "./types/index.js";
+import { AccountState, ProvingSystem } from "./types/index.js";
 import { hexlify } from "ethers";
 
+// Order of the zkVM backends in the Nexus runtime
+const PROVING_SYSTEMS: ProvingSystem[] = ["Risc0", "Sp1", "Mock"];
+
 class ProofManagerClient {
   // provider = ethers.Provider( ... mail box ....);
   private proofManager: Contract;
@@ -48,12 +51,17 @@ class ProofManagerClient {
 
     const lastProofHeight = accountState.last_proof_height;
     const height = accountState.height;
+    // Encoded as the index of the backend, as in the Nexus account state
+    const provingSystem = PROVING_SYSTEMS.indexOf(accountState.proving_system);
+    const owner = "0x" + accountState.owner;
     const accountStateOnChain = {
       statementDigest,
       stateRoot,
       startNexusHash,
       lastProofHeight,
       height,
+      provingSystem,
+      owner,
     };
 
     // Call the updateChainState function on the smart contract
@@ -27,6 +27,8 @@ export declare namespace NexusProofManager {
     startNexusHash: BytesLike;
     lastProofHeight: BigNumberish;
     height: BigNumberish;
+    provingSystem: BigNumberish;
+    owner: BytesLike;
   };
 
   export type AccountStateStructOutput = [
@@ -34,13 +36,17 @@ export declare namespace NexusProofManager {
     stateRoot: string,
     startNexusHash: string,
     lastProofHeight: bigint,
-    height: bigint
+    height: bigint,
+    provingSystem: bigint,
+    owner: string
   ] & {
     statementDigest: string;
     stateRoot: string;
     startNexusHash: string;
     lastProofHeight: bigint;
     height: bigint;
+    provingSystem: bigint;
+    owner: string;
   };
 
   export type NexusBlockStruct = { stateRoot: BytesLike; blockHash: BytesLike };
@@ -159,6 +159,16 @@ const _abi = [
             type: "uint128",
             internalType: "uint128",
           },
+          {
+            name: "provingSystem",
+            type: "uint8",
+            internalType: "uint8",
+          },
+          {
+            name: "owner",
+            type: "bytes32",
+            internalType: "bytes32",
+          },
         ],
       },
     ],
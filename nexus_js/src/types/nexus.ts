@@ -2,12 +2,16 @@ type NexusState = {
   stateRoot: string;
 };
 
+type ProvingSystem = "Risc0" | "Sp1" | "Mock";
+
 type AccountState = {
   statement: string;
   state_root: string;
   start_nexus_hash: string;
   last_proof_height: number;
   height: number;
+  proving_system: ProvingSystem;
+  owner: string;
 };
 
 type AccountApiResponse = {
@@ -23,6 +27,7 @@ type AccountApiResponse = {
       state_root: string;
       avail_header_hash: string;
       number: number;
+      runtime_version: number;
     };
     value_hash_hex: string;
   };
@@ -30,6 +35,7 @@ type AccountApiResponse = {
 
 export {
   NexusState,
+  ProvingSystem,
   AccountState,
   AccountApiResponse,
 }
@@ -1,7 +1,21 @@
 pub mod types;
-use crate::types::Header;
+use crate::types::{AppBlockData, AppExtrinsicWithProof, Header};
+use anyhow::{anyhow, Error};
+use avail_core::AppId as AvailAppId;
+use avail_subxt::api::runtime_types::{
+    bounded_collections::bounded_vec::BoundedVec, da_control::pallet::Call as DaCall,
+    da_runtime::RuntimeCall,
+};
+use avail_subxt::avail::PairSigner;
+use avail_subxt::config::substrate::H256 as AvailH256;
 use avail_subxt::config::Header as HeaderTrait;
-use nexus_core::types::H256;
+use avail_subxt::primitives::AppUncheckedExtrinsic;
+use avail_subxt::subxt::rpc::rpc_params;
+use avail_subxt::tx;
+use nexus_core::types::{AppId, AvailHeader, DataProof, Extension, H256};
+use parity_scale_codec::Decode;
+use serde::Deserialize;
+use sp_core::{sr25519::Pair, Pair as _};
 use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::{
@@ -20,10 +34,42 @@ pub struct SimpleRelayer {
 pub trait Relayer {
     fn receiver(&mut self) -> Arc<tokio::sync::Mutex<UnboundedReceiver<Header>>>;
     fn get_header_hash(&self, height: u32) -> impl Future<Output = H256> + Send;
+    fn get_app_data(
+        &self,
+        header_hash: H256,
+        app_ids: Vec<AppId>,
+    ) -> impl Future<Output = Result<Vec<AppBlockData>, Error>> + Send;
     fn start(&self, start_height: u32) -> impl Future<Output = ()> + Send;
     fn stop(&self);
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcDataProof {
+    #[serde(alias = "root")]
+    data_root: AvailH256,
+    proof: Vec<AvailH256>,
+    number_of_leaves: u32,
+    leaf_index: u32,
+    leaf: AvailH256,
+}
+
+impl From<RpcDataProof> for DataProof {
+    fn from(value: RpcDataProof) -> Self {
+        DataProof {
+            data_root: H256::from(value.data_root.to_fixed_bytes()),
+            proof: value
+                .proof
+                .iter()
+                .map(|p| H256::from(p.to_fixed_bytes()))
+                .collect(),
+            number_of_leaves: value.number_of_leaves,
+            leaf_index: value.leaf_index,
+            leaf: H256::from(value.leaf.to_fixed_bytes()),
+        }
+    }
+}
+
 impl Relayer for SimpleRelayer {
     fn receiver(&mut self) -> Arc<tokio::sync::Mutex<UnboundedReceiver<Header>>> {
         self.receiver.clone()
@@ -44,6 +90,81 @@ impl Relayer for SimpleRelayer {
         }
     }
 
+    fn get_app_data(
+        &self,
+        header_hash: H256,
+        app_ids: Vec<AppId>,
+    ) -> impl Future<Output = Result<Vec<AppBlockData>, Error>> + Send {
+        async move {
+            let (subxt_client, _) = avail_subxt::build_client(self.rpc_url.clone(), false)
+                .await
+                .map_err(|e| anyhow!("Error building client: {}", e))?;
+            let hash = AvailH256::from(header_hash.as_fixed_slice().clone());
+
+            let header = match subxt_client.rpc().header(Some(hash)).await {
+                Ok(Some(i)) => AvailHeader::from(&i),
+                Ok(None) => return Err(anyhow!("Header not found for hash {:?}", header_hash)),
+                Err(e) => return Err(anyhow!("Error getting header {:?}: {}", header_hash, e)),
+            };
+
+            let block = match subxt_client.rpc().block(Some(hash)).await {
+                Ok(Some(i)) => i.block,
+                Ok(None) => return Err(anyhow!("Block not found for hash {:?}", header_hash)),
+                Err(e) => return Err(anyhow!("Error getting block {:?}: {}", header_hash, e)),
+            };
+
+            let Extension::V3(extension) = &header.extension;
+            let mut app_data: Vec<AppBlockData> = app_ids
+                .iter()
+                .map(|app_id| AppBlockData {
+                    header_hash: header_hash.clone(),
+                    app_id: app_id.clone(),
+                    lookup_range: extension.app_lookup.range_of(app_id),
+                    extrinsics: vec![],
+                    submissions: vec![],
+                })
+                .collect();
+            let mut submissions: Vec<AppId> = vec![];
+
+            for (index, extrinsic) in block.extrinsics.iter().enumerate() {
+                let extrinsic = match AppUncheckedExtrinsic::decode(&mut &extrinsic.0[..]) {
+                    Ok(i) => i,
+                    Err(_) => continue,
+                };
+                let app_id = extrinsic.app_id().0;
+                let data = match extrinsic.function {
+                    RuntimeCall::DataAvailability(DaCall::submit_data { data }) => data.0,
+                    _ => continue,
+                };
+                //Every data submission is a leaf of the data root, whatever the app.
+                submissions.push(AppId(app_id));
+
+                let entry = match app_data.iter_mut().find(|i| i.app_id.0 == app_id) {
+                    Some(i) => i,
+                    None => continue,
+                };
+
+                let data_proof: RpcDataProof = subxt_client
+                    .rpc()
+                    .request("kate_queryDataProof", rpc_params![index as u32, hash])
+                    .await
+                    .map_err(|e| anyhow!("Error getting data proof for tx {}: {}", index, e))?;
+
+                entry.extrinsics.push(AppExtrinsicWithProof {
+                    index: index as u32,
+                    data,
+                    data_proof: DataProof::from(data_proof),
+                });
+            }
+
+            for entry in app_data.iter_mut() {
+                entry.submissions = submissions.clone();
+            }
+
+            Ok(app_data)
+        }
+    }
+
     fn start(&self, start_height: u32) -> impl Future<Output = ()> + Send {
         async move {
             println!("Started client.");
@@ -167,4 +288,29 @@ impl SimpleRelayer {
             stop: stop_tx,
         }
     }
+
+    /// Submits data to Avail under the given app ID, returning the hash of the block
+    /// it was included in once that block is finalized.
+    pub async fn submit_data(
+        &self,
+        seed: &str,
+        app_id: AppId,
+        data: Vec<u8>,
+    ) -> Result<H256, Error> {
+        let (subxt_client, _) = avail_subxt::build_client(self.rpc_url.clone(), false)
+            .await
+            .map_err(|e| anyhow!("Error building client: {}", e))?;
+        let pair = Pair::from_string(seed, None)
+            .map_err(|e| anyhow!("Invalid avail signing seed: {:?}", e))?;
+        let signer = PairSigner::new(pair);
+
+        let call = avail_subxt::api::tx()
+            .data_availability()
+            .submit_data(BoundedVec(data));
+        let tx = tx::send_then_finalized(&subxt_client, &call, &signer, AvailAppId(app_id.0))
+            .await
+            .map_err(|e| anyhow!("Error submitting data: {}", e))?;
+
+        Ok(H256::from(tx.block_hash().to_fixed_bytes()))
+    }
 }
@@ -1 +1,25 @@
 pub use avail_subxt::primitives::Header;
+use nexus_core::types::{AppId, DataProof, Range, H256};
+
+/// A single data submission of an app, along with its position in the block.
+#[derive(Clone, Debug)]
+pub struct AppExtrinsicWithProof {
+    /// Index of the extrinsic within the block.
+    pub index: u32,
+    /// Raw data submitted by the extrinsic.
+    pub data: Vec<u8>,
+    /// Inclusion proof of the data against the header data root.
+    pub data_proof: DataProof,
+}
+
+/// App specific data of a block, for a single app ID.
+#[derive(Clone, Debug)]
+pub struct AppBlockData {
+    pub header_hash: H256,
+    pub app_id: AppId,
+    /// Chunk range of the app in the data matrix, `None` if the app has no data in the block.
+    pub lookup_range: Option<Range<u32>>,
+    pub extrinsics: Vec<AppExtrinsicWithProof>,
+    /// App id of every data submission of the block, whatever the app, in block order.
+    pub submissions: Vec<AppId>,
+}